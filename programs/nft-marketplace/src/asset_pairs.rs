@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use crate::ErrorCode;
+
+// Instructions moving several assets take them as (asset, collection) pairs in
+// remaining_accounts. Assets without a collection pass the mpl-core program id instead.
+pub struct AssetPair<'a, 'info> {
+    pub asset: &'a AccountInfo<'info>,
+    pub collection: Option<&'a AccountInfo<'info>>,
+}
+
+pub fn asset_pairs<'a, 'info>(
+    remaining_accounts: &'a [AccountInfo<'info>],
+) -> Result<Vec<AssetPair<'a, 'info>>> {
+    require!(remaining_accounts.len().is_multiple_of(2), ErrorCode::InvalidAsset);
+
+    Ok(remaining_accounts
+        .chunks(2)
+        .map(|pair| AssetPair {
+            asset: &pair[0],
+            collection: if pair[1].key() == mpl_core::ID { None } else { Some(&pair[1]) },
+        })
+        .collect())
+}
+
+// Same as asset_pairs but the assets must match `expected` in order
+pub fn expected_asset_pairs<'a, 'info>(
+    remaining_accounts: &'a [AccountInfo<'info>],
+    expected: &[Pubkey],
+) -> Result<Vec<AssetPair<'a, 'info>>> {
+    let pairs = asset_pairs(remaining_accounts)?;
+    require!(
+        pairs.iter().map(|pair| pair.asset.key()).eq(expected.iter().copied()),
+        ErrorCode::InvalidAsset
+    );
+    Ok(pairs)
+}
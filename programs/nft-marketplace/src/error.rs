@@ -19,4 +19,148 @@ pub enum ErrorCode {
 
     #[msg("Asset does not match listing")]
     InvalidAsset,
+
+    #[msg("Invalid drop configuration")]
+    InvalidDropConfig,
+
+    #[msg("Drop has not started")]
+    DropNotStarted,
+
+    #[msg("Drop is sold out")]
+    DropSoldOut,
+
+    #[msg("Wallet mint limit reached")]
+    WalletMintLimitReached,
+
+    #[msg("Wallet is not on the allowlist")]
+    NotAllowlisted,
+
+    #[msg("Listing is reserved for another buyer")]
+    ReservedForOtherBuyer,
+
+    #[msg("Invalid swap terms")]
+    InvalidSwap,
+
+    #[msg("Too many assets")]
+    TooManyAssets,
+
+    #[msg("Math overflow")]
+    MathOverflow,
+
+    #[msg("Price is below the minimum")]
+    PriceTooLow,
+
+    #[msg("Price is above the maximum")]
+    PriceTooHigh,
+
+    #[msg("Minimum price must be <= maximum price")]
+    InvalidPriceBounds,
+
+    #[msg("Buyer and seller must differ")]
+    SelfTrade,
+
+    #[msg("Fee tiers must be strictly ascending by volume and at most 8")]
+    InvalidFeeTiers,
+
+    #[msg("Reward mint cannot be changed once configured")]
+    InvalidRewardMint,
+
+    #[msg("No rewards to claim")]
+    NoRewardsToClaim,
+
+    #[msg("Account is already on the latest layout version")]
+    AlreadyMigrated,
+
+    #[msg("Listing is still active")]
+    ListingStillActive,
+
+    #[msg("Invalid loan terms")]
+    InvalidLoanTerms,
+
+    #[msg("Loan is past due")]
+    LoanExpired,
+
+    #[msg("Loan is not yet due")]
+    LoanNotExpired,
+
+    #[msg("Invalid rental terms")]
+    InvalidRentalTerms,
+
+    #[msg("Asset is currently rented")]
+    AssetRented,
+
+    #[msg("Asset is not rented")]
+    NotRented,
+
+    #[msg("Rental period has not ended")]
+    RentalNotExpired,
+
+    #[msg("Invalid installment plan")]
+    InvalidInstallmentPlan,
+
+    #[msg("Listing does not offer an installment plan")]
+    NoInstallmentPlan,
+
+    #[msg("Installment is past due")]
+    InstallmentOverdue,
+
+    #[msg("Installment is not yet overdue")]
+    InstallmentNotOverdue,
+
+    #[msg("Bundle must contain at least one asset")]
+    EmptyBundle,
+
+    #[msg("Invalid vault parameters")]
+    InvalidVaultConfig,
+
+    #[msg("Vault is not in the required state")]
+    InvalidVaultState,
+
+    #[msg("Bid is too low")]
+    BidTooLow,
+
+    #[msg("Buyout auction has ended")]
+    AuctionEnded,
+
+    #[msg("Buyout auction has not ended")]
+    AuctionNotEnded,
+
+    #[msg("Nothing to redeem")]
+    NothingToRedeem,
+
+    #[msg("Invalid trait bid")]
+    InvalidTraitBid,
+
+    #[msg("Asset does not have the required traits")]
+    TraitMismatch,
+
+    #[msg("Invalid or missing price feed")]
+    InvalidPriceFeed,
+
+    #[msg("Oracle price is stale")]
+    StalePrice,
+
+    #[msg("Oracle price confidence is too wide")]
+    PriceConfidenceTooWide,
+
+    #[msg("Oracle price is below the listing floor")]
+    PriceBelowFloor,
+
+    #[msg("Relist price and relist listing account must be passed together")]
+    InvalidRelist,
+
+    #[msg("Invalid pool parameters")]
+    InvalidPoolConfig,
+
+    #[msg("Pool still holds NFTs")]
+    PoolNotEmpty,
+
+    #[msg("Pool price has reached zero")]
+    PoolPriceExhausted,
+
+    #[msg("Price exceeds the trader's limit")]
+    SlippageExceeded,
+
+    #[msg("Fee payer is not an approved relayer")]
+    RelayerNotApproved,
 }
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use crate::{ErrorCode, FeeRounding};
+
+pub const BPS_DENOMINATOR: u128 = 10_000;
+
+// Fee in lamports for `amount` at `bps`, rounded per policy and never below `min_fee`
+pub fn compute_fee(amount: u64, bps: u64, rounding: FeeRounding, min_fee: u64) -> Result<u64> {
+    let product = (amount as u128)
+        .checked_mul(bps as u128)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let quotient = product / BPS_DENOMINATOR;
+    let remainder = product % BPS_DENOMINATOR;
+
+    let rounded = match rounding {
+        FeeRounding::Floor => quotient,
+        FeeRounding::Ceil => quotient + u128::from(remainder > 0),
+        // Banker's rounding: exact halves go to the even neighbour
+        FeeRounding::HalfEven => {
+            let doubled = remainder * 2;
+            if doubled > BPS_DENOMINATOR || (doubled == BPS_DENOMINATOR && quotient % 2 == 1) {
+                quotient + 1
+            } else {
+                quotient
+            }
+        }
+    };
+
+    let fee = u64::try_from(rounded).map_err(|_| ErrorCode::MathOverflow)?;
+    Ok(fee.max(min_fee))
+}
+
+// Fee deducted from `amount` itself (seller / recipient proceeds): capped at
+// `amount` so a large `min_fee` can never make the remainder underflow
+pub fn compute_fee_from_proceeds(amount: u64, bps: u64, rounding: FeeRounding, min_fee: u64) -> Result<u64> {
+    Ok(compute_fee(amount, bps, rounding, min_fee)?.min(amount))
+}
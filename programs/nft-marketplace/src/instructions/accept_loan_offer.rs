@@ -0,0 +1,148 @@
+use anchor_lang::prelude::*;
+use mpl_core::accounts::BaseAssetV1;
+use mpl_core::instructions::TransferV1CpiBuilder;
+use mpl_core::types::UpdateAuthority;
+use crate::{Loan, LoanOffer, Market, ErrorCode};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AcceptLoanOffer<'info> {
+    /// The borrower escrowing their NFT
+    #[account(mut)]
+    pub borrower: Signer<'info>,
+
+    /// The lender receiving the offer rent back
+    /// CHECK: Validated against offer.lender
+    #[account(mut)]
+    pub lender: UncheckedAccount<'info>,
+
+    /// The offer being taken
+    #[account(
+        mut,
+        seeds = [b"loan_offer", offer.lender.as_ref(), offer.seed.to_le_bytes().as_ref()],
+        bump = offer.bump,
+        has_one = lender,
+        has_one = collection,
+        close = lender  // Principal goes to borrower, rent back to lender
+    )]
+    pub offer: Account<'info, LoanOffer>,
+
+    /// The loan - PDA with seeds [b"loan", asset]
+    #[account(
+        init,
+        payer = borrower,
+        space = Loan::DISCRIMINATOR.len() + Loan::INIT_SPACE,
+        seeds = [b"loan", asset.key().as_ref()],
+        bump
+    )]
+    pub loan: Account<'info, Loan>,
+
+    /// The collateral (mpl-core asset)
+    /// CHECK: Checked to belong to offer.collection
+    #[account(mut)]
+    pub asset: UncheckedAccount<'info>,
+
+    /// The collection the collateral belongs to
+    /// CHECK: Validated against offer.collection
+    pub collection: UncheckedAccount<'info>,
+
+    /// MPL Core program for NFT transfer
+    /// CHECK: Pinned to the mpl-core program id
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Market account (event sequence number)
+    #[account(
+        mut,
+        seeds = [b"market"],
+        bump = market.bump,
+    )]
+    pub market: Account<'info, Market>,
+}
+
+#[event]
+pub struct LoanStarted {
+    pub seq: u64,
+    pub market: Pubkey,
+    pub loan: Pubkey,
+    pub offer: Pubkey,
+    pub lender: Pubkey,
+    pub borrower: Pubkey,
+    pub collection: Pubkey,
+    pub asset: Pubkey,
+    pub principal: u64,
+    pub apr_bps: u64,
+    pub due_at: i64,
+    pub timestamp: i64,
+}
+
+pub fn process_accept_loan_offer(ctx: Context<AcceptLoanOffer>) -> Result<()> {
+    let offer = &ctx.accounts.offer;
+    let clock = Clock::get()?;
+
+    // Collateral must be an asset of the offer's collection
+    require_keys_eq!(*ctx.accounts.asset.owner, mpl_core::ID, ErrorCode::InvalidAsset);
+    let asset = BaseAssetV1::from_bytes(&ctx.accounts.asset.data.borrow())
+        .map_err(|_| ErrorCode::InvalidAsset)?;
+    require!(
+        asset.update_authority == UpdateAuthority::Collection(offer.collection),
+        ErrorCode::InvalidAsset
+    );
+
+    let principal = offer.amount;
+    let due_at = clock.unix_timestamp
+        .checked_add(offer.duration)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    let loan = &mut ctx.accounts.loan;
+    loan.lender = offer.lender;
+    loan.borrower = ctx.accounts.borrower.key();
+    loan.collection = offer.collection;
+    loan.asset = ctx.accounts.asset.key();
+    loan.principal = principal;
+    loan.apr_bps = offer.apr_bps;
+    loan.start_at = clock.unix_timestamp;
+    loan.due_at = due_at;
+    loan.bump = ctx.bumps.loan;
+
+    // Escrow the collateral in the loan PDA
+    TransferV1CpiBuilder::new(&ctx.accounts.mpl_core_program.to_account_info())
+        .asset(&ctx.accounts.asset.to_account_info())
+        .collection(Some(&ctx.accounts.collection.to_account_info()))
+        .payer(&ctx.accounts.borrower.to_account_info())
+        .authority(Some(&ctx.accounts.borrower.to_account_info()))
+        .new_owner(&ctx.accounts.loan.to_account_info())
+        .invoke()?;
+
+    // Release the escrowed principal to the borrower (offer PDA is program owned)
+    ctx.accounts.offer.sub_lamports(principal)?;
+    ctx.accounts.borrower.add_lamports(principal)?;
+
+    msg!(
+        "Loan started: {} - {} lamports against asset {} due at {}",
+        ctx.accounts.loan.key(),
+        principal,
+        ctx.accounts.asset.key(),
+        due_at
+    );
+
+    let seq = ctx.accounts.market.next_event_seq()?;
+    emit_cpi!(LoanStarted {
+        seq,
+        market: ctx.accounts.market.key(),
+        loan: ctx.accounts.loan.key(),
+        offer: ctx.accounts.offer.key(),
+        lender: ctx.accounts.lender.key(),
+        borrower: ctx.accounts.borrower.key(),
+        collection: ctx.accounts.collection.key(),
+        asset: ctx.accounts.asset.key(),
+        principal,
+        apr_bps: ctx.accounts.loan.apr_bps,
+        due_at,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
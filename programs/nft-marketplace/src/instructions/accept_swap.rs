@@ -0,0 +1,132 @@
+use anchor_lang::prelude::*;
+use mpl_core::instructions::TransferV1CpiBuilder;
+use crate::asset_pairs::expected_asset_pairs;
+use crate::{Market, SwapOffer, ErrorCode};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AcceptSwap<'info> {
+    /// Party B, handing over the requested assets
+    #[account(mut)]
+    pub taker: Signer<'info>,
+
+    /// Party A, receiving the requested assets and the swap rent
+    /// CHECK: Validated against swap.maker
+    #[account(mut)]
+    pub maker: UncheckedAccount<'info>,
+
+    /// The swap being completed
+    #[account(
+        mut,
+        seeds = [b"swap", swap.maker.as_ref(), swap.seed.to_le_bytes().as_ref()],
+        bump = swap.bump,
+        has_one = maker,
+        has_one = taker,
+        close = maker  // Return rent to maker
+    )]
+    pub swap: Account<'info, SwapOffer>,
+
+    /// MPL Core program for NFT transfers
+    /// CHECK: Pinned to the mpl-core program id
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Market account (event sequence number)
+    #[account(
+        mut,
+        seeds = [b"market"],
+        bump = market.bump,
+    )]
+    pub market: Account<'info, Market>,
+    // remaining_accounts (after the event CPI accounts): (asset, collection)
+    // pairs for the offered assets, followed by pairs for the requested assets
+}
+
+#[event]
+pub struct SwapCompleted {
+    pub seq: u64,
+    pub market: Pubkey,
+    pub swap: Pubkey,
+    pub maker: Pubkey,
+    pub taker: Pubkey,
+    pub offered_assets: Vec<Pubkey>,
+    pub requested_assets: Vec<Pubkey>,
+    pub offered_lamports: u64,
+    pub timestamp: i64,
+}
+
+pub fn process_accept_swap<'info>(
+    ctx: Context<'_, '_, 'info, 'info, AcceptSwap<'info>>,
+) -> Result<()> {
+    let swap = &ctx.accounts.swap;
+    let clock = Clock::get()?;
+
+    let split = swap.offered_assets.len() * 2;
+    require!(ctx.remaining_accounts.len() >= split, ErrorCode::InvalidAsset);
+    let (offered_accounts, requested_accounts) = ctx.remaining_accounts.split_at(split);
+    let offered = expected_asset_pairs(offered_accounts, &swap.offered_assets)?;
+    let requested = expected_asset_pairs(requested_accounts, &swap.requested_assets)?;
+
+    // Prepare swap PDA signer seeds
+    let seed_bytes = swap.seed.to_le_bytes();
+    let seeds = &[
+        b"swap",
+        swap.maker.as_ref(),
+        seed_bytes.as_ref(),
+        &[swap.bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    // Transfer offered NFTs from swap PDA to taker
+    for pair in &offered {
+        TransferV1CpiBuilder::new(&ctx.accounts.mpl_core_program.to_account_info())
+            .asset(pair.asset)
+            .collection(pair.collection)
+            .payer(&ctx.accounts.taker.to_account_info())
+            .authority(Some(&ctx.accounts.swap.to_account_info()))  // PDA is authority
+            .new_owner(&ctx.accounts.taker.to_account_info())
+            .invoke_signed(signer)?;  // Sign with PDA seeds
+    }
+
+    // Transfer requested NFTs from taker to maker
+    for pair in &requested {
+        TransferV1CpiBuilder::new(&ctx.accounts.mpl_core_program.to_account_info())
+            .asset(pair.asset)
+            .collection(pair.collection)
+            .payer(&ctx.accounts.taker.to_account_info())
+            .authority(Some(&ctx.accounts.taker.to_account_info()))
+            .new_owner(&ctx.accounts.maker.to_account_info())
+            .invoke()?;
+    }
+
+    // Release escrowed lamports to taker (rent goes back to maker on close)
+    let offered_lamports = swap.offered_lamports;
+    if offered_lamports > 0 {
+        ctx.accounts.swap.sub_lamports(offered_lamports)?;
+        ctx.accounts.taker.add_lamports(offered_lamports)?;
+    }
+
+    msg!(
+        "Swap completed: {} between maker {} and taker {}",
+        ctx.accounts.swap.key(),
+        ctx.accounts.maker.key(),
+        ctx.accounts.taker.key()
+    );
+
+    let seq = ctx.accounts.market.next_event_seq()?;
+    emit_cpi!(SwapCompleted {
+        seq,
+        market: ctx.accounts.market.key(),
+        swap: ctx.accounts.swap.key(),
+        maker: ctx.accounts.maker.key(),
+        taker: ctx.accounts.taker.key(),
+        offered_assets: ctx.accounts.swap.offered_assets.clone(),
+        requested_assets: ctx.accounts.swap.requested_assets.clone(),
+        offered_lamports,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+use mpl_core::instructions::TransferV1CpiBuilder;
+use crate::asset_pairs::asset_pairs;
+use crate::{BundleListing, Market, ErrorCode};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(seed: u64)]
+pub struct AddBundleListing<'info> {
+    /// The seller listing the bundle
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    /// The bundle escrow - PDA with seeds [b"bundle", seller, seed]
+    #[account(
+        init,
+        payer = seller,
+        space = BundleListing::DISCRIMINATOR.len() + BundleListing::INIT_SPACE,
+        seeds = [b"bundle", seller.key().as_ref(), seed.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub bundle: Account<'info, BundleListing>,
+
+    /// MPL Core program for NFT transfers
+    /// CHECK: Pinned to the mpl-core program id
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Market account (for validation and event sequence)
+    #[account(
+        mut,
+        seeds = [b"market"],
+        bump = market.bump,
+    )]
+    pub market: Account<'info, Market>,
+    // remaining_accounts (after the event CPI accounts): (asset, collection)
+    // pairs for the bundled assets
+}
+
+#[event]
+pub struct BundleListed {
+    pub seq: u64,
+    pub market: Pubkey,
+    pub bundle: Pubkey,
+    pub seller: Pubkey,
+    pub assets: Vec<Pubkey>,
+    pub price: u64,
+    pub timestamp: i64,
+}
+
+pub fn process_add_bundle_listing<'info>(
+    ctx: Context<'_, '_, 'info, 'info, AddBundleListing<'info>>,
+    seed: u64,
+    price: u64,
+) -> Result<()> {
+    let pairs = asset_pairs(ctx.remaining_accounts)?;
+    let assets: Vec<Pubkey> = pairs.iter().map(|pair| pair.asset.key()).collect();
+    let market = &ctx.accounts.market;
+    let clock = Clock::get()?;
+
+    require!(!assets.is_empty(), ErrorCode::EmptyBundle);
+    require!(assets.len() <= BundleListing::MAX_ASSETS, ErrorCode::TooManyAssets);
+
+    // Bundles can span collections, so only the market-wide bounds apply
+    require!(price >= market.min_price, ErrorCode::PriceTooLow);
+    require!(price <= market.max_price, ErrorCode::PriceTooHigh);
+
+    let bundle = &mut ctx.accounts.bundle;
+    bundle.seller = ctx.accounts.seller.key();
+    bundle.seed = seed;
+    bundle.assets = assets.clone();
+    bundle.price = price;
+    bundle.created_at = clock.unix_timestamp;
+    bundle.bump = ctx.bumps.bundle;
+
+    // Transfer every NFT from seller to bundle PDA (escrow)
+    for pair in &pairs {
+        TransferV1CpiBuilder::new(&ctx.accounts.mpl_core_program.to_account_info())
+            .asset(pair.asset)
+            .collection(pair.collection)
+            .payer(&ctx.accounts.seller.to_account_info())
+            .authority(Some(&ctx.accounts.seller.to_account_info()))
+            .new_owner(&ctx.accounts.bundle.to_account_info())
+            .invoke()?;
+    }
+
+    msg!(
+        "Bundle listed: {} with {} assets at price: {} lamports",
+        ctx.accounts.bundle.key(),
+        assets.len(),
+        price
+    );
+
+    ctx.accounts.market.record_listing()?;
+
+    let seq = ctx.accounts.market.next_event_seq()?;
+    emit_cpi!(BundleListed {
+        seq,
+        market: ctx.accounts.market.key(),
+        bundle: ctx.accounts.bundle.key(),
+        seller: ctx.accounts.seller.key(),
+        assets,
+        price,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
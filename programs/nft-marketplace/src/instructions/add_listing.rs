@@ -1,7 +1,9 @@
 use anchor_lang::prelude::*;
-use mpl_core::instructions::TransferV1CpiBuilder;
-use crate::{Listing, Market};
+use mpl_core::instructions::{AddPluginV1CpiBuilder, TransferV1CpiBuilder};
+use mpl_core::types::{FreezeDelegate, Plugin, PluginAuthority, TransferDelegate};
+use crate::{Listing, ListingMode, Market};
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct AddListing<'info> {
     /// The seller listing their NFT
@@ -23,8 +25,9 @@ pub struct AddListing<'info> {
     )]
     pub listing: Account<'info, Listing>,
 
-    /// Market account (for validation)
+    /// Market account (for validation and event sequence)
     #[account(
+        mut,
         seeds = [b"market"],
         bump = market.bump,
     )]
@@ -34,13 +37,22 @@ pub struct AddListing<'info> {
     /// CHECK: Validated as mpl-core collection
     pub collection: UncheckedAccount<'info>,
 
+    /// Per-collection price bounds, may not exist
+    /// CHECK: PDA with seeds [b"collection_config", collection], read only if initialized
+    #[account(
+        seeds = [b"collection_config", collection.key().as_ref()],
+        bump
+    )]
+    pub collection_config: UncheckedAccount<'info>,
+
     /// The NFT asset being listed
     /// CHECK: Validated as mpl-core asset from the collection
     #[account(mut)]
     pub asset: UncheckedAccount<'info>,
 
     /// MPL Core program for NFT transfer
-    /// CHECK: mpl-core program
+    /// CHECK: Pinned to the mpl-core program id
+    #[account(address = mpl_core::ID)]
     pub mpl_core_program: UncheckedAccount<'info>,
 
     pub system_program: Program<'info, System>,
@@ -48,18 +60,29 @@ pub struct AddListing<'info> {
 
 #[event]
 pub struct ListingCreated {
+    pub seq: u64,
+    pub market: Pubkey,
     pub listing: Pubkey,
     pub seller: Pubkey,
     pub collection: Pubkey,
     pub asset: Pubkey,
     pub price: u64,
+    pub allowlist_root: Option<[u8; 32]>,
+    pub reserved_buyer: Option<Pubkey>,
+    pub mode: ListingMode,
     pub timestamp: i64,
 }
 
 pub fn process_add_listing(
     ctx: Context<AddListing>,
     price: u64,
+    allowlist_root: Option<[u8; 32]>,
+    reserved_buyer: Option<Pubkey>,
+    mode: ListingMode,
 ) -> Result<()> {
+    // Validate price against collection / market bounds
+    ctx.accounts.market.check_price(price, &ctx.accounts.collection_config)?;
+
     let listing = &mut ctx.accounts.listing;
     let clock = Clock::get()?;
 
@@ -71,30 +94,72 @@ pub fn process_add_listing(
     listing.is_active = true;
     listing.created_at = clock.unix_timestamp;
     listing.bump = ctx.bumps.listing;
+    listing.allowlist_root = allowlist_root;
+    listing.reserved_buyer = reserved_buyer;
+    listing.mode = mode;
+    listing.version = Listing::VERSION;
+    listing.installment_plan = None;
+    listing.usd_pricing = None;
+
+    match mode {
+        ListingMode::Escrow => {
+            // Transfer NFT from seller to listing PDA (escrow)
+            TransferV1CpiBuilder::new(&ctx.accounts.mpl_core_program.to_account_info())
+                .asset(&ctx.accounts.asset.to_account_info())
+                .collection(Some(&ctx.accounts.collection.to_account_info()))
+                .payer(&ctx.accounts.seller.to_account_info())
+                .authority(Some(&ctx.accounts.seller.to_account_info()))
+                .new_owner(&ctx.accounts.listing.to_account_info())
+                .invoke()?;
+        }
+        ListingMode::Delegated => {
+            // NFT stays in the seller's wallet: freeze it and let the listing PDA
+            // thaw + transfer it on sale
+            AddPluginV1CpiBuilder::new(&ctx.accounts.mpl_core_program.to_account_info())
+                .asset(&ctx.accounts.asset.to_account_info())
+                .collection(Some(&ctx.accounts.collection.to_account_info()))
+                .payer(&ctx.accounts.seller.to_account_info())
+                .authority(Some(&ctx.accounts.seller.to_account_info()))
+                .system_program(&ctx.accounts.system_program.to_account_info())
+                .plugin(Plugin::FreezeDelegate(FreezeDelegate { frozen: true }))
+                .init_authority(PluginAuthority::Address { address: ctx.accounts.listing.key() })
+                .invoke()?;
 
-    // Transfer NFT from seller to listing PDA (escrow)
-    TransferV1CpiBuilder::new(&ctx.accounts.mpl_core_program.to_account_info())
-        .asset(&ctx.accounts.asset.to_account_info())
-        .collection(Some(&ctx.accounts.collection.to_account_info()))
-        .payer(&ctx.accounts.seller.to_account_info())
-        .authority(Some(&ctx.accounts.seller.to_account_info()))
-        .new_owner(&ctx.accounts.listing.to_account_info())
-        .invoke()?;
+            AddPluginV1CpiBuilder::new(&ctx.accounts.mpl_core_program.to_account_info())
+                .asset(&ctx.accounts.asset.to_account_info())
+                .collection(Some(&ctx.accounts.collection.to_account_info()))
+                .payer(&ctx.accounts.seller.to_account_info())
+                .authority(Some(&ctx.accounts.seller.to_account_info()))
+                .system_program(&ctx.accounts.system_program.to_account_info())
+                .plugin(Plugin::TransferDelegate(TransferDelegate {}))
+                .init_authority(PluginAuthority::Address { address: ctx.accounts.listing.key() })
+                .invoke()?;
+        }
+    }
 
     msg!(
-        "Listing created: {} for asset: {} at price: {} lamports (NFT now in escrow)",
+        "Listing created: {} for asset: {} at price: {} lamports (NFT {})",
         ctx.accounts.listing.key(),
         ctx.accounts.asset.key(),
-        price
+        price,
+        if mode == ListingMode::Escrow { "now in escrow" } else { "frozen in seller wallet" }
     );
 
+    ctx.accounts.market.record_listing()?;
+
     // Emit listing created event
-    emit!(ListingCreated {
+    let seq = ctx.accounts.market.next_event_seq()?;
+    emit_cpi!(ListingCreated {
+        seq,
+        market: ctx.accounts.market.key(),
         listing: ctx.accounts.listing.key(),
         seller: ctx.accounts.seller.key(),
         collection: ctx.accounts.collection.key(),
         asset: ctx.accounts.asset.key(),
         price,
+        allowlist_root,
+        reserved_buyer,
+        mode,
         timestamp: clock.unix_timestamp,
     });
 
@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+use crate::{Market, RelayerApproval};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AddRelayer<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"market"],
+        bump = market.bump,
+        has_one = admin
+    )]
+    pub market: Account<'info, Market>,
+
+    /// The relayer allowed to sponsor purchases
+    /// CHECK: Only used as a PDA seed
+    pub relayer: UncheckedAccount<'info>,
+
+    /// Allowlist entry - PDA with seeds [b"relayer", market, relayer]
+    #[account(
+        init,
+        payer = admin,
+        space = RelayerApproval::DISCRIMINATOR.len() + RelayerApproval::INIT_SPACE,
+        seeds = [b"relayer", market.key().as_ref(), relayer.key().as_ref()],
+        bump
+    )]
+    pub relayer_approval: Account<'info, RelayerApproval>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct RelayerAdded {
+    pub seq: u64,
+    pub market: Pubkey,
+    pub relayer: Pubkey,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+pub fn process_add_relayer(ctx: Context<AddRelayer>) -> Result<()> {
+    let clock = Clock::get()?;
+
+    let relayer_approval = &mut ctx.accounts.relayer_approval;
+    relayer_approval.market = ctx.accounts.market.key();
+    relayer_approval.relayer = ctx.accounts.relayer.key();
+    relayer_approval.added_at = clock.unix_timestamp;
+    relayer_approval.bump = ctx.bumps.relayer_approval;
+
+    msg!("Relayer {} may now sponsor purchases", ctx.accounts.relayer.key());
+
+    let seq = ctx.accounts.market.next_event_seq()?;
+    emit_cpi!(RelayerAdded {
+        seq,
+        market: ctx.accounts.market.key(),
+        relayer: ctx.accounts.relayer.key(),
+        admin: ctx.accounts.admin.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
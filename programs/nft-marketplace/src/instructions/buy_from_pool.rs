@@ -0,0 +1,159 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use mpl_core::instructions::TransferV1CpiBuilder;
+use crate::{fees, Market, Pool, ErrorCode};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct BuyFromPool<'info> {
+    /// The trader buying an NFT
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    /// The pool selling the NFT
+    #[account(
+        mut,
+        seeds = [b"pool", pool.owner.as_ref(), pool.seed.to_le_bytes().as_ref()],
+        bump = pool.bump,
+        has_one = collection,
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// The NFT being bought
+    /// CHECK: Ownership by the pool PDA is enforced by mpl-core on transfer
+    #[account(mut)]
+    pub asset: UncheckedAccount<'info>,
+
+    /// The collection the NFT belongs to
+    /// CHECK: Validated against pool.collection
+    pub collection: UncheckedAccount<'info>,
+
+    /// Fee recipient receiving marketplace fee
+    /// CHECK: Validated against market.fee_recipient
+    #[account(mut)]
+    pub fee_recipient: UncheckedAccount<'info>,
+
+    /// MPL Core program for NFT transfer
+    /// CHECK: Pinned to the mpl-core program id
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Market account for fee configuration and event sequence
+    #[account(
+        mut,
+        seeds = [b"market"],
+        bump = market.bump,
+    )]
+    pub market: Account<'info, Market>,
+}
+
+#[event]
+pub struct PoolNftBought {
+    pub seq: u64,
+    pub market: Pubkey,
+    pub pool: Pubkey,
+    pub buyer: Pubkey,
+    pub asset: Pubkey,
+    pub fee_recipient: Pubkey,
+    pub price: u64,
+    pub fee_amount: u64,       // Taker fee paid by the buyer
+    pub spot_price: u64,
+    pub timestamp: i64,
+}
+
+pub fn process_buy_from_pool(ctx: Context<BuyFromPool>, max_price: u64) -> Result<()> {
+    let pool = &ctx.accounts.pool;
+    let market = &ctx.accounts.market;
+    let clock = Clock::get()?;
+
+    require!(pool.owner != ctx.accounts.buyer.key(), ErrorCode::SelfTrade);
+    require!(
+        market.fee_recipient == ctx.accounts.fee_recipient.key(),
+        ErrorCode::InvalidFeeRecipient
+    );
+
+    // Buyer pays the current spot price, the curve then moves up
+    let price = pool.spot_price;
+    require!(price <= max_price, ErrorCode::SlippageExceeded);
+    let next_spot_price = pool.price_after_buy()?;
+
+    // Taker fee is paid by the buyer on top of the curve price
+    let fee_amount = fees::compute_fee(
+        price,
+        market.taker_fee_bps,
+        market.fee_rounding,
+        market.min_fee,
+    )?;
+
+    // Payment: buyer -> pool, buyer -> fee recipient
+    transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.buyer.to_account_info(),
+                to: ctx.accounts.pool.to_account_info(),
+            },
+        ),
+        price,
+    )?;
+    if fee_amount > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.buyer.to_account_info(),
+                    to: ctx.accounts.fee_recipient.to_account_info(),
+                },
+            ),
+            fee_amount,
+        )?;
+    }
+
+    // Prepare pool PDA signer seeds
+    let seed_bytes = pool.seed.to_le_bytes();
+    let seeds = &[b"pool", pool.owner.as_ref(), seed_bytes.as_ref(), &[pool.bump]];
+    let signer = &[&seeds[..]];
+
+    // Transfer NFT to buyer using the pool PDA as authority
+    TransferV1CpiBuilder::new(&ctx.accounts.mpl_core_program.to_account_info())
+        .asset(&ctx.accounts.asset.to_account_info())
+        .collection(Some(&ctx.accounts.collection.to_account_info()))
+        .payer(&ctx.accounts.buyer.to_account_info())
+        .authority(Some(&ctx.accounts.pool.to_account_info()))
+        .new_owner(&ctx.accounts.buyer.to_account_info())
+        .invoke_signed(signer)?;
+
+    let pool = &mut ctx.accounts.pool;
+    pool.spot_price = next_spot_price;
+    pool.sol_balance = pool.sol_balance.checked_add(price).ok_or(ErrorCode::MathOverflow)?;
+    pool.nft_count = pool.nft_count.checked_sub(1).ok_or(ErrorCode::MathOverflow)?;
+
+    ctx.accounts.market.record_volume(price, fee_amount)?;
+
+    msg!(
+        "Pool sale: asset {} sold by pool {} to {} for {} lamports (next spot price {})",
+        ctx.accounts.asset.key(),
+        ctx.accounts.pool.key(),
+        ctx.accounts.buyer.key(),
+        price,
+        next_spot_price
+    );
+
+    let seq = ctx.accounts.market.next_event_seq()?;
+    emit_cpi!(PoolNftBought {
+        seq,
+        market: ctx.accounts.market.key(),
+        pool: ctx.accounts.pool.key(),
+        buyer: ctx.accounts.buyer.key(),
+        asset: ctx.accounts.asset.key(),
+        fee_recipient: ctx.accounts.fee_recipient.key(),
+        price,
+        fee_amount,
+        spot_price: next_spot_price,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
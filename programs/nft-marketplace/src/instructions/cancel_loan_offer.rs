@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+use crate::{LoanOffer, Market};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelLoanOffer<'info> {
+    /// The lender withdrawing the offer
+    #[account(mut)]
+    pub lender: Signer<'info>,
+
+    /// The offer being canceled
+    #[account(
+        mut,
+        seeds = [b"loan_offer", offer.lender.as_ref(), offer.seed.to_le_bytes().as_ref()],
+        bump = offer.bump,
+        has_one = lender,
+        close = lender  // Return rent and escrowed principal to lender
+    )]
+    pub offer: Account<'info, LoanOffer>,
+
+    /// Market account (event sequence number)
+    #[account(
+        mut,
+        seeds = [b"market"],
+        bump = market.bump,
+    )]
+    pub market: Account<'info, Market>,
+}
+
+#[event]
+pub struct LoanOfferCanceled {
+    pub seq: u64,
+    pub market: Pubkey,
+    pub offer: Pubkey,
+    pub lender: Pubkey,
+    pub collection: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+pub fn process_cancel_loan_offer(ctx: Context<CancelLoanOffer>) -> Result<()> {
+    msg!(
+        "Loan offer canceled: {} - {} lamports returned to lender {}",
+        ctx.accounts.offer.key(),
+        ctx.accounts.offer.amount,
+        ctx.accounts.lender.key()
+    );
+
+    let seq = ctx.accounts.market.next_event_seq()?;
+    emit_cpi!(LoanOfferCanceled {
+        seq,
+        market: ctx.accounts.market.key(),
+        offer: ctx.accounts.offer.key(),
+        lender: ctx.accounts.lender.key(),
+        collection: ctx.accounts.offer.collection,
+        amount: ctx.accounts.offer.amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+use mpl_core::instructions::TransferV1CpiBuilder;
+use crate::asset_pairs::expected_asset_pairs;
+use crate::{Market, SwapOffer};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelSwap<'info> {
+    /// Party A, canceling the swap
+    #[account(mut)]
+    pub maker: Signer<'info>,
+
+    /// The swap being canceled
+    #[account(
+        mut,
+        seeds = [b"swap", swap.maker.as_ref(), swap.seed.to_le_bytes().as_ref()],
+        bump = swap.bump,
+        has_one = maker,
+        close = maker  // Return rent and escrowed lamports to maker
+    )]
+    pub swap: Account<'info, SwapOffer>,
+
+    /// MPL Core program for NFT transfers
+    /// CHECK: Pinned to the mpl-core program id
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Market account (event sequence number)
+    #[account(
+        mut,
+        seeds = [b"market"],
+        bump = market.bump,
+    )]
+    pub market: Account<'info, Market>,
+    // remaining_accounts (after the event CPI accounts): (asset, collection)
+    // pairs for the offered assets
+}
+
+#[event]
+pub struct SwapCanceled {
+    pub seq: u64,
+    pub market: Pubkey,
+    pub swap: Pubkey,
+    pub maker: Pubkey,
+    pub taker: Pubkey,
+    pub offered_assets: Vec<Pubkey>,
+    pub requested_assets: Vec<Pubkey>,
+    pub offered_lamports: u64,
+    pub timestamp: i64,
+}
+
+pub fn process_cancel_swap<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CancelSwap<'info>>,
+) -> Result<()> {
+    let swap = &ctx.accounts.swap;
+    let clock = Clock::get()?;
+
+    let offered = expected_asset_pairs(ctx.remaining_accounts, &swap.offered_assets)?;
+
+    // Prepare swap PDA signer seeds
+    let seed_bytes = swap.seed.to_le_bytes();
+    let seeds = &[
+        b"swap",
+        swap.maker.as_ref(),
+        seed_bytes.as_ref(),
+        &[swap.bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    // Transfer offered NFTs from swap PDA back to maker
+    for pair in &offered {
+        TransferV1CpiBuilder::new(&ctx.accounts.mpl_core_program.to_account_info())
+            .asset(pair.asset)
+            .collection(pair.collection)
+            .payer(&ctx.accounts.maker.to_account_info())
+            .authority(Some(&ctx.accounts.swap.to_account_info()))  // PDA is authority
+            .new_owner(&ctx.accounts.maker.to_account_info())
+            .invoke_signed(signer)?;  // Sign with PDA seeds
+    }
+
+    msg!(
+        "Swap canceled: {} - {} assets returned to maker {}",
+        ctx.accounts.swap.key(),
+        offered.len(),
+        ctx.accounts.maker.key()
+    );
+
+    let seq = ctx.accounts.market.next_event_seq()?;
+    emit_cpi!(SwapCanceled {
+        seq,
+        market: ctx.accounts.market.key(),
+        swap: ctx.accounts.swap.key(),
+        maker: ctx.accounts.maker.key(),
+        taker: ctx.accounts.swap.taker,
+        offered_assets: ctx.accounts.swap.offered_assets.clone(),
+        requested_assets: ctx.accounts.swap.requested_assets.clone(),
+        offered_lamports: ctx.accounts.swap.offered_lamports,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
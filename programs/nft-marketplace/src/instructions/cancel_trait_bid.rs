@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+use crate::{Market, TraitBid};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelTraitBid<'info> {
+    /// The bidder withdrawing their bid
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+
+    /// The bid being canceled
+    #[account(
+        mut,
+        seeds = [b"trait_bid", bid.bidder.as_ref(), bid.seed.to_le_bytes().as_ref()],
+        bump = bid.bump,
+        has_one = bidder,
+        close = bidder  // Escrowed lamports + rent back to bidder
+    )]
+    pub bid: Account<'info, TraitBid>,
+
+    /// Market account (event sequence number)
+    #[account(
+        mut,
+        seeds = [b"market"],
+        bump = market.bump,
+    )]
+    pub market: Account<'info, Market>,
+}
+
+#[event]
+pub struct TraitBidCanceled {
+    pub seq: u64,
+    pub market: Pubkey,
+    pub bid: Pubkey,
+    pub bidder: Pubkey,
+    pub collection: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+pub fn process_cancel_trait_bid(ctx: Context<CancelTraitBid>) -> Result<()> {
+    msg!(
+        "Trait bid canceled: {} - {} lamports returned to bidder {}",
+        ctx.accounts.bid.key(),
+        ctx.accounts.bid.amount,
+        ctx.accounts.bidder.key()
+    );
+
+    let seq = ctx.accounts.market.next_event_seq()?;
+    emit_cpi!(TraitBidCanceled {
+        seq,
+        market: ctx.accounts.market.key(),
+        bid: ctx.accounts.bid.key(),
+        bidder: ctx.accounts.bidder.key(),
+        collection: ctx.accounts.bid.collection,
+        amount: ctx.accounts.bid.amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,138 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{mint_to, transfer, Mint, MintTo, Token, TokenAccount, Transfer};
+use crate::{Market, RewardConfig, TraderStats, ErrorCode};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimRewards<'info> {
+    #[account(mut)]
+    pub claimer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"market"],
+        bump = market.bump,
+    )]
+    pub market: Account<'info, Market>,
+
+    /// Reward program settings - PDA with seeds [b"reward_config", market]
+    #[account(
+        seeds = [b"reward_config", market.key().as_ref()],
+        bump = reward_config.bump,
+        has_one = reward_mint
+    )]
+    pub reward_config: Account<'info, RewardConfig>,
+
+    #[account(mut)]
+    pub reward_mint: Account<'info, Mint>,
+
+    /// Program-controlled vault (used when the reward config is not mint authority)
+    #[account(
+        mut,
+        associated_token::mint = reward_mint,
+        associated_token::authority = reward_config,
+    )]
+    pub reward_vault: Account<'info, TokenAccount>,
+
+    /// Claimer's reward token account
+    #[account(
+        init_if_needed,
+        payer = claimer,
+        associated_token::mint = reward_mint,
+        associated_token::authority = claimer,
+    )]
+    pub claimer_token_account: Account<'info, TokenAccount>,
+
+    /// Claimer's trade counters holding accrued rewards
+    #[account(
+        mut,
+        seeds = [b"trader_stats", claimer.key().as_ref()],
+        bump = trader_stats.bump,
+    )]
+    pub trader_stats: Account<'info, TraderStats>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct RewardsClaimed {
+    pub seq: u64,
+    pub market: Pubkey,
+    pub wallet: Pubkey,
+    pub reward_mint: Pubkey,
+    pub amount: u64,
+    pub minted: bool,
+    pub timestamp: i64,
+}
+
+pub fn process_claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
+    let amount = ctx.accounts.trader_stats.rewards_accrued;
+    require!(amount > 0, ErrorCode::NoRewardsToClaim);
+
+    let market_key = ctx.accounts.market.key();
+    let reward_config_key = ctx.accounts.reward_config.key();
+    let seeds = &[
+        b"reward_config",
+        market_key.as_ref(),
+        &[ctx.accounts.reward_config.bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    // Mint new rewards when the program controls the mint, otherwise pay from the vault
+    let minted = Option::<Pubkey>::from(ctx.accounts.reward_mint.mint_authority) == Some(reward_config_key);
+    if minted {
+        mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.reward_mint.to_account_info(),
+                    to: ctx.accounts.claimer_token_account.to_account_info(),
+                    authority: ctx.accounts.reward_config.to_account_info(),
+                },
+                signer,
+            ),
+            amount,
+        )?;
+    } else {
+        require!(
+            ctx.accounts.reward_vault.amount >= amount,
+            ErrorCode::InsufficientFunds
+        );
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.reward_vault.to_account_info(),
+                    to: ctx.accounts.claimer_token_account.to_account_info(),
+                    authority: ctx.accounts.reward_config.to_account_info(),
+                },
+                signer,
+            ),
+            amount,
+        )?;
+    }
+
+    let trader_stats = &mut ctx.accounts.trader_stats;
+    trader_stats.rewards_accrued = 0;
+    trader_stats.rewards_claimed = trader_stats.rewards_claimed
+        .checked_add(amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    msg!("Rewards claimed: {} to {}", amount, ctx.accounts.claimer.key());
+
+    let seq = ctx.accounts.market.next_event_seq()?;
+    emit_cpi!(RewardsClaimed {
+        seq,
+        market: ctx.accounts.market.key(),
+        wallet: ctx.accounts.claimer.key(),
+        reward_mint: ctx.accounts.reward_mint.key(),
+        amount,
+        minted,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
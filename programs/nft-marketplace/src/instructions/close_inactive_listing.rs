@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+use crate::{Listing, Market, ErrorCode};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseInactiveListing<'info> {
+    /// Anyone can sweep sold listings, rent always goes back to the seller
+    pub closer: Signer<'info>,
+
+    /// The original seller receiving the listing rent
+    /// CHECK: Validated against listing.seller
+    #[account(mut)]
+    pub seller: UncheckedAccount<'info>,
+
+    /// The sold listing being closed
+    #[account(
+        mut,
+        seeds = [
+            b"listing",
+            listing.seller.as_ref(),
+            listing.collection.as_ref(),
+            listing.asset.as_ref()
+        ],
+        bump = listing.bump,
+        has_one = seller,
+        close = seller  // Return rent to seller
+    )]
+    pub listing: Account<'info, Listing>,
+
+    /// Market account (event sequence number)
+    #[account(
+        mut,
+        seeds = [b"market"],
+        bump = market.bump,
+    )]
+    pub market: Account<'info, Market>,
+}
+
+#[event]
+pub struct ListingClosed {
+    pub seq: u64,
+    pub market: Pubkey,
+    pub listing: Pubkey,
+    pub seller: Pubkey,
+    pub closer: Pubkey,
+    pub collection: Pubkey,
+    pub asset: Pubkey,
+    pub rent: u64,
+    pub timestamp: i64,
+}
+
+pub fn process_close_inactive_listing(ctx: Context<CloseInactiveListing>) -> Result<()> {
+    let listing = &ctx.accounts.listing;
+    require!(!listing.is_active, ErrorCode::ListingStillActive);
+
+    msg!(
+        "Inactive listing closed: {} - rent returned to seller {}",
+        listing.key(),
+        listing.seller
+    );
+
+    let seq = ctx.accounts.market.next_event_seq()?;
+    emit_cpi!(ListingClosed {
+        seq,
+        market: ctx.accounts.market.key(),
+        listing: ctx.accounts.listing.key(),
+        seller: ctx.accounts.listing.seller,
+        closer: ctx.accounts.closer.key(),
+        collection: ctx.accounts.listing.collection,
+        asset: ctx.accounts.listing.asset,
+        rent: ctx.accounts.listing.get_lamports(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
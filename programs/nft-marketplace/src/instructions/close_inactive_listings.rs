@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use crate::instructions::close_inactive_listing::ListingClosed;
+use crate::{Listing, Market, ErrorCode};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseInactiveListings<'info> {
+    /// Anyone can sweep sold listings, rent always goes back to the sellers
+    pub closer: Signer<'info>,
+
+    /// Market account (event sequence number)
+    #[account(
+        mut,
+        seeds = [b"market"],
+        bump = market.bump,
+    )]
+    pub market: Account<'info, Market>,
+    // remaining_accounts (after the event CPI accounts): (listing, seller) pairs
+}
+
+pub fn process_close_inactive_listings<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CloseInactiveListings<'info>>,
+) -> Result<()> {
+    let remaining = ctx.remaining_accounts;
+    require!(
+        !remaining.is_empty() && remaining.len().is_multiple_of(2),
+        ErrorCode::InvalidAsset
+    );
+    let clock = Clock::get()?;
+
+    for pair in remaining.chunks(2) {
+        let (listing_info, seller_info) = (&pair[0], &pair[1]);
+
+        // Checks owner and discriminator
+        let listing = Account::<Listing>::try_from(listing_info)?;
+        require!(!listing.is_active, ErrorCode::ListingStillActive);
+        require_keys_eq!(listing.seller, seller_info.key(), ErrorCode::Unauthorized);
+
+        let rent = listing_info.lamports();
+        let (collection, asset) = (listing.collection, listing.asset);
+        listing.close(seller_info.clone())?;
+
+        let seq = ctx.accounts.market.next_event_seq()?;
+        emit_cpi!(ListingClosed {
+            seq,
+            market: ctx.accounts.market.key(),
+            listing: listing_info.key(),
+            seller: seller_info.key(),
+            closer: ctx.accounts.closer.key(),
+            collection,
+            asset,
+            rent,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    msg!("Inactive listings closed: {}", remaining.len() / 2);
+
+    Ok(())
+}
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use crate::{Market, Pool, ErrorCode};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClosePool<'info> {
+    /// The pool owner shutting the pool down
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// The pool being closed
+    #[account(
+        mut,
+        seeds = [b"pool", pool.owner.as_ref(), pool.seed.to_le_bytes().as_ref()],
+        bump = pool.bump,
+        has_one = owner,
+        close = owner  // Remaining lamports + rent back to owner
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// Market account (event sequence number)
+    #[account(
+        mut,
+        seeds = [b"market"],
+        bump = market.bump,
+    )]
+    pub market: Account<'info, Market>,
+}
+
+#[event]
+pub struct PoolClosed {
+    pub seq: u64,
+    pub market: Pubkey,
+    pub pool: Pubkey,
+    pub owner: Pubkey,
+    pub sol_balance: u64,
+    pub timestamp: i64,
+}
+
+pub fn process_close_pool(ctx: Context<ClosePool>) -> Result<()> {
+    // NFTs would be stranded in a closed PDA
+    require!(ctx.accounts.pool.nft_count == 0, ErrorCode::PoolNotEmpty);
+
+    msg!(
+        "Pool closed: {} - {} lamports returned to owner {}",
+        ctx.accounts.pool.key(),
+        ctx.accounts.pool.sol_balance,
+        ctx.accounts.owner.key()
+    );
+
+    let seq = ctx.accounts.market.next_event_seq()?;
+    emit_cpi!(PoolClosed {
+        seq,
+        market: ctx.accounts.market.key(),
+        pool: ctx.accounts.pool.key(),
+        owner: ctx.accounts.owner.key(),
+        sol_balance: ctx.accounts.pool.sol_balance,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
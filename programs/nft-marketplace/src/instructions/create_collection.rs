@@ -1,6 +1,8 @@
 use anchor_lang::prelude::*;
 use mpl_core::instructions::CreateCollectionV2CpiBuilder;
+use crate::Market;
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct CreateCollection<'info> {
     /// The collection account to create (mpl-core collection)
@@ -18,12 +20,23 @@ pub struct CreateCollection<'info> {
     pub system_program: Program<'info, System>,
 
     /// MPL Core program
-    /// CHECK: mpl-core program
+    /// CHECK: Pinned to the mpl-core program id
+    #[account(address = mpl_core::ID)]
     pub mpl_core_program: UncheckedAccount<'info>,
+
+    /// Market account (event sequence number)
+    #[account(
+        mut,
+        seeds = [b"market"],
+        bump = market.bump,
+    )]
+    pub market: Account<'info, Market>,
 }
 
 #[event]
 pub struct CollectionCreated {
+    pub seq: u64,
+    pub market: Pubkey,
     pub collection: Pubkey,
     pub update_authority: Pubkey,
     pub name: String,
@@ -45,7 +58,10 @@ pub fn process_create_collection(ctx: Context<CreateCollection>, name: String, u
     msg!("Collection created: {}",ctx.accounts.collection.key());
 
     // Emit collection created event
-    emit!(CollectionCreated {
+    let seq = ctx.accounts.market.next_event_seq()?;
+    emit_cpi!(CollectionCreated {
+        seq,
+        market: ctx.accounts.market.key(),
         collection: ctx.accounts.collection.key(),
         update_authority: ctx.accounts.update_authority.key(),
         name,
@@ -0,0 +1,128 @@
+use anchor_lang::prelude::*;
+use mpl_core::instructions::CreateCollectionV2CpiBuilder;
+use crate::{Drop, Market, ErrorCode};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct DropConfig {
+    pub name: String,                     // Asset name prefix, minted as "<name> #<n>"
+    pub uri: String,                      // Base metadata uri, minted as "<uri>/<n>.json"
+    pub price: u64,                       // Price in lamports per mint
+    pub max_supply: u64,                  // Hard cap on minted assets
+    pub wallet_limit: u64,                // Max mints per wallet (0 = unlimited)
+    pub allowlist_root: Option<[u8; 32]>, // Merkle root of allowlisted wallets
+    pub allowlist_start: i64,             // Unix timestamp the allowlist phase opens
+    pub public_start: i64,                // Unix timestamp the public phase opens
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateDrop<'info> {
+    /// The creator receiving primary sale proceeds
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// The collection account to create (mpl-core collection)
+    #[account(mut)]
+    pub collection: Signer<'info>,
+
+    /// The drop account - PDA with seeds [b"drop", collection]
+    /// Also the collection update authority so it can sign mints
+    #[account(
+        init,
+        payer = creator,
+        space = Drop::DISCRIMINATOR.len() + Drop::INIT_SPACE,
+        seeds = [b"drop", collection.key().as_ref()],
+        bump
+    )]
+    pub drop: Account<'info, Drop>,
+
+    pub system_program: Program<'info, System>,
+
+    /// MPL Core program
+    /// CHECK: Pinned to the mpl-core program id
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: UncheckedAccount<'info>,
+
+    /// Market account (event sequence number)
+    #[account(
+        mut,
+        seeds = [b"market"],
+        bump = market.bump,
+    )]
+    pub market: Account<'info, Market>,
+}
+
+#[event]
+pub struct DropCreated {
+    pub seq: u64,
+    pub market: Pubkey,
+    pub drop: Pubkey,
+    pub creator: Pubkey,
+    pub collection: Pubkey,
+    pub price: u64,
+    pub max_supply: u64,
+    pub wallet_limit: u64,
+    pub allowlist_root: Option<[u8; 32]>,
+    pub allowlist_start: i64,
+    pub public_start: i64,
+    pub timestamp: i64,
+}
+
+pub fn process_create_drop(ctx: Context<CreateDrop>, config: DropConfig) -> Result<()> {
+    require!(config.max_supply > 0, ErrorCode::InvalidDropConfig);
+    require!(config.public_start >= config.allowlist_start, ErrorCode::InvalidDropConfig);
+    require!(config.name.len() <= Drop::MAX_NAME_LEN, ErrorCode::InvalidDropConfig);
+    require!(config.uri.len() <= Drop::MAX_URI_LEN, ErrorCode::InvalidDropConfig);
+
+    let drop = &mut ctx.accounts.drop;
+    let clock = Clock::get()?;
+
+    drop.creator = ctx.accounts.creator.key();
+    drop.collection = ctx.accounts.collection.key();
+    drop.name = config.name.clone();
+    drop.uri = config.uri.clone();
+    drop.price = config.price;
+    drop.max_supply = config.max_supply;
+    drop.minted = 0;
+    drop.wallet_limit = config.wallet_limit;
+    drop.allowlist_root = config.allowlist_root;
+    drop.allowlist_start = config.allowlist_start;
+    drop.public_start = config.public_start;
+    drop.bump = ctx.bumps.drop;
+
+    // Create the collection with the drop PDA as update authority
+    CreateCollectionV2CpiBuilder::new(&ctx.accounts.mpl_core_program.to_account_info())
+        .collection(&ctx.accounts.collection.to_account_info())
+        .update_authority(Some(&ctx.accounts.drop.to_account_info()))
+        .payer(&ctx.accounts.creator.to_account_info())
+        .system_program(&ctx.accounts.system_program.to_account_info())
+        .name(config.name)
+        .uri(format!("{}/collection.json", config.uri))
+        .invoke()?;
+
+    msg!(
+        "Drop created: {} for collection: {} ({} assets at {} lamports)",
+        ctx.accounts.drop.key(),
+        ctx.accounts.collection.key(),
+        config.max_supply,
+        config.price
+    );
+
+    let seq = ctx.accounts.market.next_event_seq()?;
+    emit_cpi!(DropCreated {
+        seq,
+        market: ctx.accounts.market.key(),
+        drop: ctx.accounts.drop.key(),
+        creator: ctx.accounts.creator.key(),
+        collection: ctx.accounts.collection.key(),
+        price: config.price,
+        max_supply: config.max_supply,
+        wallet_limit: config.wallet_limit,
+        allowlist_root: config.allowlist_root,
+        allowlist_start: config.allowlist_start,
+        public_start: config.public_start,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::{LoanOffer, Market, ErrorCode};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(seed: u64)]
+pub struct CreateLoanOffer<'info> {
+    /// The lender funding the loan
+    #[account(mut)]
+    pub lender: Signer<'info>,
+
+    /// The loan offer escrow - PDA with seeds [b"loan_offer", lender, seed]
+    #[account(
+        init,
+        payer = lender,
+        space = LoanOffer::DISCRIMINATOR.len() + LoanOffer::INIT_SPACE,
+        seeds = [b"loan_offer", lender.key().as_ref(), seed.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub offer: Account<'info, LoanOffer>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Market account (event sequence number)
+    #[account(
+        mut,
+        seeds = [b"market"],
+        bump = market.bump,
+    )]
+    pub market: Account<'info, Market>,
+}
+
+#[event]
+pub struct LoanOfferCreated {
+    pub seq: u64,
+    pub market: Pubkey,
+    pub offer: Pubkey,
+    pub lender: Pubkey,
+    pub collection: Pubkey,
+    pub amount: u64,
+    pub apr_bps: u64,
+    pub duration: i64,
+    pub timestamp: i64,
+}
+
+pub fn process_create_loan_offer(
+    ctx: Context<CreateLoanOffer>,
+    seed: u64,
+    collection: Pubkey,
+    amount: u64,
+    apr_bps: u64,
+    duration: i64,
+) -> Result<()> {
+    require!(amount > 0, ErrorCode::InvalidLoanTerms);
+    require!(duration > 0, ErrorCode::InvalidLoanTerms);
+    require!(apr_bps <= LoanOffer::MAX_APR_BPS, ErrorCode::InvalidLoanTerms);
+
+    let clock = Clock::get()?;
+
+    let offer = &mut ctx.accounts.offer;
+    offer.lender = ctx.accounts.lender.key();
+    offer.collection = collection;
+    offer.seed = seed;
+    offer.amount = amount;
+    offer.apr_bps = apr_bps;
+    offer.duration = duration;
+    offer.created_at = clock.unix_timestamp;
+    offer.bump = ctx.bumps.offer;
+
+    // Escrow the principal in the offer PDA
+    transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.lender.to_account_info(),
+                to: ctx.accounts.offer.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    msg!(
+        "Loan offer created: {} lending {} lamports at {} bps APR for {}s against collection {}",
+        ctx.accounts.offer.key(),
+        amount,
+        apr_bps,
+        duration,
+        collection
+    );
+
+    let seq = ctx.accounts.market.next_event_seq()?;
+    emit_cpi!(LoanOfferCreated {
+        seq,
+        market: ctx.accounts.market.key(),
+        offer: ctx.accounts.offer.key(),
+        lender: ctx.accounts.lender.key(),
+        collection,
+        amount,
+        apr_bps,
+        duration,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
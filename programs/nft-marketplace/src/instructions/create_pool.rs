@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+use crate::{fees, BondingCurve, Market, Pool, ErrorCode};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(seed: u64)]
+pub struct CreatePool<'info> {
+    /// The liquidity provider opening the pool
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// The pool - PDA with seeds [b"pool", owner, seed]
+    #[account(
+        init,
+        payer = owner,
+        space = Pool::DISCRIMINATOR.len() + Pool::INIT_SPACE,
+        seeds = [b"pool", owner.key().as_ref(), seed.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// The collection the pool trades
+    /// CHECK: Validated as mpl-core collection
+    pub collection: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Market account (event sequence number)
+    #[account(
+        mut,
+        seeds = [b"market"],
+        bump = market.bump,
+    )]
+    pub market: Account<'info, Market>,
+}
+
+#[event]
+pub struct PoolCreated {
+    pub seq: u64,
+    pub market: Pubkey,
+    pub pool: Pubkey,
+    pub owner: Pubkey,
+    pub collection: Pubkey,
+    pub curve: BondingCurve,
+    pub spot_price: u64,
+    pub delta: u64,
+    pub spread_bps: u16,
+    pub timestamp: i64,
+}
+
+pub fn process_create_pool(
+    ctx: Context<CreatePool>,
+    seed: u64,
+    curve: BondingCurve,
+    spot_price: u64,
+    delta: u64,
+    spread_bps: u16,
+) -> Result<()> {
+    require_keys_eq!(*ctx.accounts.collection.owner, mpl_core::ID, ErrorCode::InvalidAsset);
+    require!(spot_price > 0, ErrorCode::InvalidPoolConfig);
+    // A full spread would make the pool bid nothing
+    require!((spread_bps as u128) < fees::BPS_DENOMINATOR, ErrorCode::InvalidPoolConfig);
+    if curve == BondingCurve::Exponential {
+        require!(delta <= Pool::MAX_EXPONENTIAL_DELTA_BPS, ErrorCode::InvalidPoolConfig);
+    }
+
+    let clock = Clock::get()?;
+
+    let pool = &mut ctx.accounts.pool;
+    pool.owner = ctx.accounts.owner.key();
+    pool.collection = ctx.accounts.collection.key();
+    pool.seed = seed;
+    pool.curve = curve;
+    pool.spot_price = spot_price;
+    pool.delta = delta;
+    pool.spread_bps = spread_bps;
+    pool.sol_balance = 0;
+    pool.nft_count = 0;
+    pool.created_at = clock.unix_timestamp;
+    pool.bump = ctx.bumps.pool;
+
+    msg!(
+        "Pool created: {} for collection {} at spot price {} lamports",
+        ctx.accounts.pool.key(),
+        ctx.accounts.collection.key(),
+        spot_price
+    );
+
+    let seq = ctx.accounts.market.next_event_seq()?;
+    emit_cpi!(PoolCreated {
+        seq,
+        market: ctx.accounts.market.key(),
+        pool: ctx.accounts.pool.key(),
+        owner: ctx.accounts.owner.key(),
+        collection: ctx.accounts.collection.key(),
+        curve,
+        spot_price,
+        delta,
+        spread_bps,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
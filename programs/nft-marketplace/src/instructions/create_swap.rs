@@ -0,0 +1,136 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use mpl_core::instructions::TransferV1CpiBuilder;
+use crate::asset_pairs::asset_pairs;
+use crate::{Market, SwapOffer, ErrorCode};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(seed: u64)]
+pub struct CreateSwap<'info> {
+    /// Party A, depositing the offered assets
+    #[account(mut)]
+    pub maker: Signer<'info>,
+
+    /// The swap escrow - PDA with seeds [b"swap", maker, seed]
+    #[account(
+        init,
+        payer = maker,
+        space = SwapOffer::DISCRIMINATOR.len() + SwapOffer::INIT_SPACE,
+        seeds = [b"swap", maker.key().as_ref(), seed.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub swap: Account<'info, SwapOffer>,
+
+    /// MPL Core program for NFT transfers
+    /// CHECK: Pinned to the mpl-core program id
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Market account (event sequence number)
+    #[account(
+        mut,
+        seeds = [b"market"],
+        bump = market.bump,
+    )]
+    pub market: Account<'info, Market>,
+    // remaining_accounts (after the event CPI accounts): (asset, collection)
+    // pairs for the offered assets
+}
+
+#[event]
+pub struct SwapCreated {
+    pub seq: u64,
+    pub market: Pubkey,
+    pub swap: Pubkey,
+    pub maker: Pubkey,
+    pub taker: Pubkey,
+    pub offered_assets: Vec<Pubkey>,
+    pub requested_assets: Vec<Pubkey>,
+    pub offered_lamports: u64,
+    pub timestamp: i64,
+}
+
+pub fn process_create_swap<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CreateSwap<'info>>,
+    seed: u64,
+    taker: Pubkey,
+    requested_assets: Vec<Pubkey>,
+    offered_lamports: u64,
+) -> Result<()> {
+    let offered = asset_pairs(ctx.remaining_accounts)?;
+    let offered_assets: Vec<Pubkey> = offered.iter().map(|pair| pair.asset.key()).collect();
+    let clock = Clock::get()?;
+
+    require!(
+        !offered_assets.is_empty() || offered_lamports > 0,
+        ErrorCode::InvalidSwap
+    );
+    require!(!requested_assets.is_empty(), ErrorCode::InvalidSwap);
+    require!(
+        offered_assets.len() <= SwapOffer::MAX_ASSETS && requested_assets.len() <= SwapOffer::MAX_ASSETS,
+        ErrorCode::TooManyAssets
+    );
+    require!(taker != ctx.accounts.maker.key(), ErrorCode::InvalidSwap);
+
+    let swap = &mut ctx.accounts.swap;
+    swap.maker = ctx.accounts.maker.key();
+    swap.taker = taker;
+    swap.seed = seed;
+    swap.offered_assets = offered_assets.clone();
+    swap.requested_assets = requested_assets.clone();
+    swap.offered_lamports = offered_lamports;
+    swap.created_at = clock.unix_timestamp;
+    swap.bump = ctx.bumps.swap;
+
+    // Transfer offered NFTs from maker to swap PDA (escrow)
+    for pair in &offered {
+        TransferV1CpiBuilder::new(&ctx.accounts.mpl_core_program.to_account_info())
+            .asset(pair.asset)
+            .collection(pair.collection)
+            .payer(&ctx.accounts.maker.to_account_info())
+            .authority(Some(&ctx.accounts.maker.to_account_info()))
+            .new_owner(&ctx.accounts.swap.to_account_info())
+            .invoke()?;
+    }
+
+    // Escrow offered lamports in the swap PDA on top of its rent
+    if offered_lamports > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.maker.to_account_info(),
+                    to: ctx.accounts.swap.to_account_info(),
+                },
+            ),
+            offered_lamports,
+        )?;
+    }
+
+    msg!(
+        "Swap created: {} offering {} assets + {} lamports for {} assets from {}",
+        ctx.accounts.swap.key(),
+        offered_assets.len(),
+        offered_lamports,
+        requested_assets.len(),
+        taker
+    );
+
+    let seq = ctx.accounts.market.next_event_seq()?;
+    emit_cpi!(SwapCreated {
+        seq,
+        market: ctx.accounts.market.key(),
+        swap: ctx.accounts.swap.key(),
+        maker: ctx.accounts.maker.key(),
+        taker,
+        offered_assets,
+        requested_assets,
+        offered_lamports,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,107 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::{Market, TraitBid, TraitFilter, ErrorCode};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(seed: u64)]
+pub struct CreateTraitBid<'info> {
+    /// The collector placing the bid
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+
+    /// The bid escrow - PDA with seeds [b"trait_bid", bidder, seed]
+    #[account(
+        init,
+        payer = bidder,
+        space = TraitBid::DISCRIMINATOR.len() + TraitBid::INIT_SPACE,
+        seeds = [b"trait_bid", bidder.key().as_ref(), seed.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub bid: Account<'info, TraitBid>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Market account (event sequence number)
+    #[account(
+        mut,
+        seeds = [b"market"],
+        bump = market.bump,
+    )]
+    pub market: Account<'info, Market>,
+}
+
+#[event]
+pub struct TraitBidCreated {
+    pub seq: u64,
+    pub market: Pubkey,
+    pub bid: Pubkey,
+    pub bidder: Pubkey,
+    pub collection: Pubkey,
+    pub traits: Vec<TraitFilter>,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+pub fn process_create_trait_bid(
+    ctx: Context<CreateTraitBid>,
+    seed: u64,
+    collection: Pubkey,
+    traits: Vec<TraitFilter>,
+    amount: u64,
+) -> Result<()> {
+    require!(amount > 0, ErrorCode::InvalidTraitBid);
+    require!(
+        !traits.is_empty() && traits.len() <= TraitBid::MAX_TRAITS,
+        ErrorCode::InvalidTraitBid
+    );
+    require!(
+        traits.iter().all(|t| t.key.len() <= TraitBid::MAX_KEY_LEN && t.value.len() <= TraitBid::MAX_VALUE_LEN),
+        ErrorCode::InvalidTraitBid
+    );
+
+    let clock = Clock::get()?;
+
+    let bid = &mut ctx.accounts.bid;
+    bid.bidder = ctx.accounts.bidder.key();
+    bid.collection = collection;
+    bid.seed = seed;
+    bid.traits = traits.clone();
+    bid.amount = amount;
+    bid.created_at = clock.unix_timestamp;
+    bid.bump = ctx.bumps.bid;
+
+    // Escrow the bid in the bid PDA
+    transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.bidder.to_account_info(),
+                to: ctx.accounts.bid.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    msg!(
+        "Trait bid created: {} bidding {} lamports for collection {} with {} traits",
+        ctx.accounts.bid.key(),
+        amount,
+        collection,
+        traits.len()
+    );
+
+    let seq = ctx.accounts.market.next_event_seq()?;
+    emit_cpi!(TraitBidCreated {
+        seq,
+        market: ctx.accounts.market.key(),
+        bid: ctx.accounts.bid.key(),
+        bidder: ctx.accounts.bidder.key(),
+        collection,
+        traits,
+        amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
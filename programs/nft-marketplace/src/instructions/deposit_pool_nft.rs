@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+use mpl_core::accounts::BaseAssetV1;
+use mpl_core::instructions::TransferV1CpiBuilder;
+use mpl_core::types::UpdateAuthority;
+use crate::{Market, Pool, ErrorCode};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DepositPoolNft<'info> {
+    /// The pool owner stocking the pool
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// The pool escrowing the NFT
+    #[account(
+        mut,
+        seeds = [b"pool", pool.owner.as_ref(), pool.seed.to_le_bytes().as_ref()],
+        bump = pool.bump,
+        has_one = owner,
+        has_one = collection,
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// The NFT being deposited
+    /// CHECK: Checked to belong to pool.collection
+    #[account(mut)]
+    pub asset: UncheckedAccount<'info>,
+
+    /// The collection the NFT belongs to
+    /// CHECK: Validated against pool.collection
+    pub collection: UncheckedAccount<'info>,
+
+    /// MPL Core program for NFT transfer
+    /// CHECK: Pinned to the mpl-core program id
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Market account (event sequence number)
+    #[account(
+        mut,
+        seeds = [b"market"],
+        bump = market.bump,
+    )]
+    pub market: Account<'info, Market>,
+}
+
+#[event]
+pub struct PoolNftDeposited {
+    pub seq: u64,
+    pub market: Pubkey,
+    pub pool: Pubkey,
+    pub owner: Pubkey,
+    pub asset: Pubkey,
+    pub nft_count: u32,
+    pub timestamp: i64,
+}
+
+pub fn process_deposit_pool_nft(ctx: Context<DepositPoolNft>) -> Result<()> {
+    // Asset must belong to the pool's collection
+    require_keys_eq!(*ctx.accounts.asset.owner, mpl_core::ID, ErrorCode::InvalidAsset);
+    let asset = BaseAssetV1::from_bytes(&ctx.accounts.asset.data.borrow())
+        .map_err(|_| ErrorCode::InvalidAsset)?;
+    require!(
+        asset.update_authority == UpdateAuthority::Collection(ctx.accounts.pool.collection),
+        ErrorCode::InvalidAsset
+    );
+
+    // Transfer NFT from owner to the pool PDA (escrow)
+    TransferV1CpiBuilder::new(&ctx.accounts.mpl_core_program.to_account_info())
+        .asset(&ctx.accounts.asset.to_account_info())
+        .collection(Some(&ctx.accounts.collection.to_account_info()))
+        .payer(&ctx.accounts.owner.to_account_info())
+        .authority(Some(&ctx.accounts.owner.to_account_info()))
+        .new_owner(&ctx.accounts.pool.to_account_info())
+        .invoke()?;
+
+    let pool = &mut ctx.accounts.pool;
+    pool.nft_count = pool.nft_count.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+
+    msg!("Pool {} stocked with asset {} ({} held)", pool.key(), ctx.accounts.asset.key(), pool.nft_count);
+
+    let seq = ctx.accounts.market.next_event_seq()?;
+    emit_cpi!(PoolNftDeposited {
+        seq,
+        market: ctx.accounts.market.key(),
+        pool: ctx.accounts.pool.key(),
+        owner: ctx.accounts.owner.key(),
+        asset: ctx.accounts.asset.key(),
+        nft_count: ctx.accounts.pool.nft_count,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
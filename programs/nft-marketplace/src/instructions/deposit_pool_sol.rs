@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::{Market, Pool, ErrorCode};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DepositPoolSol<'info> {
+    /// The pool owner funding the pool
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// The pool receiving the lamports
+    #[account(
+        mut,
+        seeds = [b"pool", pool.owner.as_ref(), pool.seed.to_le_bytes().as_ref()],
+        bump = pool.bump,
+        has_one = owner,
+    )]
+    pub pool: Account<'info, Pool>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Market account (event sequence number)
+    #[account(
+        mut,
+        seeds = [b"market"],
+        bump = market.bump,
+    )]
+    pub market: Account<'info, Market>,
+}
+
+#[event]
+pub struct PoolSolDeposited {
+    pub seq: u64,
+    pub market: Pubkey,
+    pub pool: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub sol_balance: u64,
+    pub timestamp: i64,
+}
+
+pub fn process_deposit_pool_sol(ctx: Context<DepositPoolSol>, amount: u64) -> Result<()> {
+    require!(amount > 0, ErrorCode::InvalidPoolConfig);
+
+    transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.owner.to_account_info(),
+                to: ctx.accounts.pool.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    let pool = &mut ctx.accounts.pool;
+    pool.sol_balance = pool.sol_balance.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+
+    msg!("Pool {} funded with {} lamports ({} available)", pool.key(), amount, pool.sol_balance);
+
+    let seq = ctx.accounts.market.next_event_seq()?;
+    emit_cpi!(PoolSolDeposited {
+        seq,
+        market: ctx.accounts.market.key(),
+        pool: ctx.accounts.pool.key(),
+        owner: ctx.accounts.owner.key(),
+        amount,
+        sol_balance: ctx.accounts.pool.sol_balance,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
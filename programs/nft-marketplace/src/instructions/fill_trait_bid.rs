@@ -0,0 +1,164 @@
+use anchor_lang::prelude::*;
+use mpl_core::accounts::BaseAssetV1;
+use mpl_core::fetch_plugin;
+use mpl_core::instructions::TransferV1CpiBuilder;
+use mpl_core::types::{Attributes, PluginType, UpdateAuthority};
+use crate::{fees, Market, TraitBid, ErrorCode};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct FillTraitBid<'info> {
+    /// The owner selling a matching NFT into the bid
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    /// The bidder receiving the NFT and the bid rent
+    /// CHECK: Validated against bid.bidder
+    #[account(mut)]
+    pub bidder: UncheckedAccount<'info>,
+
+    /// The bid being filled
+    #[account(
+        mut,
+        seeds = [b"trait_bid", bid.bidder.as_ref(), bid.seed.to_le_bytes().as_ref()],
+        bump = bid.bump,
+        has_one = bidder,
+        has_one = collection,
+        close = bidder  // Return rent to bidder
+    )]
+    pub bid: Account<'info, TraitBid>,
+
+    /// The NFT being sold
+    /// CHECK: Checked to belong to bid.collection and carry the traits
+    #[account(mut)]
+    pub asset: UncheckedAccount<'info>,
+
+    /// The collection the NFT belongs to
+    /// CHECK: Validated against bid.collection
+    pub collection: UncheckedAccount<'info>,
+
+    /// Fee recipient receiving marketplace fee
+    /// CHECK: Validated against market.fee_recipient
+    #[account(mut)]
+    pub fee_recipient: UncheckedAccount<'info>,
+
+    /// MPL Core program for NFT transfer
+    /// CHECK: Pinned to the mpl-core program id
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Market account for fee configuration and event sequence
+    #[account(
+        mut,
+        seeds = [b"market"],
+        bump = market.bump,
+    )]
+    pub market: Account<'info, Market>,
+}
+
+#[event]
+pub struct TraitBidFilled {
+    pub seq: u64,
+    pub market: Pubkey,
+    pub bid: Pubkey,
+    pub seller: Pubkey,
+    pub bidder: Pubkey,
+    pub collection: Pubkey,
+    pub asset: Pubkey,
+    pub fee_recipient: Pubkey,
+    pub price: u64,
+    pub fee_amount: u64,
+    pub timestamp: i64,
+}
+
+pub fn process_fill_trait_bid(ctx: Context<FillTraitBid>) -> Result<()> {
+    let bid = &ctx.accounts.bid;
+    let market = &ctx.accounts.market;
+    let clock = Clock::get()?;
+
+    require!(bid.bidder != ctx.accounts.seller.key(), ErrorCode::SelfTrade);
+    require!(
+        market.fee_recipient == ctx.accounts.fee_recipient.key(),
+        ErrorCode::InvalidFeeRecipient
+    );
+
+    // Asset must belong to the bid's collection
+    require_keys_eq!(*ctx.accounts.asset.owner, mpl_core::ID, ErrorCode::InvalidAsset);
+    let asset = BaseAssetV1::from_bytes(&ctx.accounts.asset.data.borrow())
+        .map_err(|_| ErrorCode::InvalidAsset)?;
+    require!(
+        asset.update_authority == UpdateAuthority::Collection(bid.collection),
+        ErrorCode::InvalidAsset
+    );
+
+    // Every required trait must be on the asset's Attributes plugin
+    let (_, attributes, _) = fetch_plugin::<BaseAssetV1, Attributes>(
+        &ctx.accounts.asset.to_account_info(),
+        PluginType::Attributes,
+    )
+    .map_err(|_| ErrorCode::TraitMismatch)?;
+    require!(
+        bid.traits.iter().all(|required| {
+            attributes
+                .attribute_list
+                .iter()
+                .any(|attribute| attribute.key == required.key && attribute.value == required.value)
+        }),
+        ErrorCode::TraitMismatch
+    );
+
+    // Marketplace fee comes out of the escrowed bid
+    let price = bid.amount;
+    let fee_amount = fees::compute_fee_from_proceeds(
+        price,
+        market.taker_fee_bps,
+        market.fee_rounding,
+        market.min_fee,
+    )?;
+    let seller_amount = price
+        .checked_sub(fee_amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    // Transfer NFT from seller to bidder
+    TransferV1CpiBuilder::new(&ctx.accounts.mpl_core_program.to_account_info())
+        .asset(&ctx.accounts.asset.to_account_info())
+        .collection(Some(&ctx.accounts.collection.to_account_info()))
+        .payer(&ctx.accounts.seller.to_account_info())
+        .authority(Some(&ctx.accounts.seller.to_account_info()))
+        .new_owner(&ctx.accounts.bidder.to_account_info())
+        .invoke()?;
+
+    // Release the escrow (bid PDA is program owned)
+    ctx.accounts.bid.sub_lamports(price)?;
+    ctx.accounts.seller.add_lamports(seller_amount)?;
+    ctx.accounts.fee_recipient.add_lamports(fee_amount)?;
+
+    ctx.accounts.market.record_sale(price, fee_amount)?;
+
+    msg!(
+        "Trait bid filled: {} - asset {} sold to {} for {} lamports",
+        ctx.accounts.bid.key(),
+        ctx.accounts.asset.key(),
+        ctx.accounts.bidder.key(),
+        price
+    );
+
+    let seq = ctx.accounts.market.next_event_seq()?;
+    emit_cpi!(TraitBidFilled {
+        seq,
+        market: ctx.accounts.market.key(),
+        bid: ctx.accounts.bid.key(),
+        seller: ctx.accounts.seller.key(),
+        bidder: ctx.accounts.bidder.key(),
+        collection: ctx.accounts.collection.key(),
+        asset: ctx.accounts.asset.key(),
+        fee_recipient: ctx.accounts.fee_recipient.key(),
+        price,
+        fee_amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,177 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{mint_to, Mint, MintTo, Token, TokenAccount};
+use mpl_core::accounts::BaseAssetV1;
+use mpl_core::instructions::TransferV1CpiBuilder;
+use mpl_core::types::UpdateAuthority;
+use crate::{FractionVault, Market, VaultState, ErrorCode};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct Fractionalize<'info> {
+    /// The curator depositing their NFT
+    #[account(mut)]
+    pub curator: Signer<'info>,
+
+    /// The vault - PDA with seeds [b"vault", asset]
+    #[account(
+        init,
+        payer = curator,
+        space = FractionVault::DISCRIMINATOR.len() + FractionVault::INIT_SPACE,
+        seeds = [b"vault", asset.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, FractionVault>,
+
+    /// Fraction mint - PDA with seeds [b"fraction_mint", vault]
+    #[account(
+        init,
+        payer = curator,
+        seeds = [b"fraction_mint", vault.key().as_ref()],
+        bump,
+        mint::decimals = 0,
+        mint::authority = vault,
+    )]
+    pub fraction_mint: Account<'info, Mint>,
+
+    /// Curator's fraction token account
+    #[account(
+        init_if_needed,
+        payer = curator,
+        associated_token::mint = fraction_mint,
+        associated_token::authority = curator,
+    )]
+    pub curator_token_account: Account<'info, TokenAccount>,
+
+    /// The NFT asset being fractionalized
+    /// CHECK: Checked to be an mpl-core asset of the collection
+    #[account(mut)]
+    pub asset: UncheckedAccount<'info>,
+
+    /// The collection the NFT belongs to
+    /// CHECK: Checked to be the asset's collection
+    pub collection: UncheckedAccount<'info>,
+
+    /// MPL Core program for NFT transfer
+    /// CHECK: Pinned to the mpl-core program id
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+
+    /// Market account (event sequence number)
+    #[account(
+        mut,
+        seeds = [b"market"],
+        bump = market.bump,
+    )]
+    pub market: Account<'info, Market>,
+}
+
+#[event]
+pub struct VaultCreated {
+    pub seq: u64,
+    pub market: Pubkey,
+    pub vault: Pubkey,
+    pub curator: Pubkey,
+    pub collection: Pubkey,
+    pub asset: Pubkey,
+    pub fraction_mint: Pubkey,
+    pub supply: u64,
+    pub reserve_price: u64,
+    pub auction_duration: i64,
+    pub timestamp: i64,
+}
+
+pub fn process_fractionalize(
+    ctx: Context<Fractionalize>,
+    supply: u64,
+    reserve_price: u64,
+    auction_duration: i64,
+) -> Result<()> {
+    require!(
+        supply > 0 && reserve_price > 0 && auction_duration > 0,
+        ErrorCode::InvalidVaultConfig
+    );
+
+    // Asset must be an mpl-core asset of the given collection
+    require_keys_eq!(*ctx.accounts.asset.owner, mpl_core::ID, ErrorCode::InvalidAsset);
+    let asset = BaseAssetV1::from_bytes(&ctx.accounts.asset.data.borrow())
+        .map_err(|_| ErrorCode::InvalidAsset)?;
+    require!(
+        asset.update_authority == UpdateAuthority::Collection(ctx.accounts.collection.key()),
+        ErrorCode::InvalidAsset
+    );
+
+    let clock = Clock::get()?;
+
+    let vault = &mut ctx.accounts.vault;
+    vault.curator = ctx.accounts.curator.key();
+    vault.collection = ctx.accounts.collection.key();
+    vault.asset = ctx.accounts.asset.key();
+    vault.fraction_mint = ctx.accounts.fraction_mint.key();
+    vault.supply = supply;
+    vault.reserve_price = reserve_price;
+    vault.auction_duration = auction_duration;
+    vault.state = VaultState::Active;
+    vault.bidder = None;
+    vault.winning_bid = 0;
+    vault.auction_ends_at = 0;
+    vault.proceeds = 0;
+    vault.created_at = clock.unix_timestamp;
+    vault.bump = ctx.bumps.vault;
+
+    // Escrow the NFT in the vault PDA
+    TransferV1CpiBuilder::new(&ctx.accounts.mpl_core_program.to_account_info())
+        .asset(&ctx.accounts.asset.to_account_info())
+        .collection(Some(&ctx.accounts.collection.to_account_info()))
+        .payer(&ctx.accounts.curator.to_account_info())
+        .authority(Some(&ctx.accounts.curator.to_account_info()))
+        .new_owner(&ctx.accounts.vault.to_account_info())
+        .invoke()?;
+
+    // Mint the whole fraction supply to the curator (vault PDA is mint authority)
+    let asset_key = ctx.accounts.asset.key();
+    let seeds = &[b"vault", asset_key.as_ref(), &[ctx.accounts.vault.bump]];
+    let signer = &[&seeds[..]];
+
+    mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.fraction_mint.to_account_info(),
+                to: ctx.accounts.curator_token_account.to_account_info(),
+                authority: ctx.accounts.vault.to_account_info(),
+            },
+            signer,
+        ),
+        supply,
+    )?;
+
+    msg!(
+        "Vault created: {} - asset {} split into {} fractions (reserve {} lamports)",
+        ctx.accounts.vault.key(),
+        asset_key,
+        supply,
+        reserve_price
+    );
+
+    let seq = ctx.accounts.market.next_event_seq()?;
+    emit_cpi!(VaultCreated {
+        seq,
+        market: ctx.accounts.market.key(),
+        vault: ctx.accounts.vault.key(),
+        curator: ctx.accounts.curator.key(),
+        collection: ctx.accounts.collection.key(),
+        asset: asset_key,
+        fraction_mint: ctx.accounts.fraction_mint.key(),
+        supply,
+        reserve_price,
+        auction_duration,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
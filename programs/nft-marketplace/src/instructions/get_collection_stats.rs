@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use crate::CollectionStats;
+
+#[derive(Accounts)]
+pub struct GetCollectionStats<'info> {
+    #[account(
+        seeds = [b"collection_stats", collection_stats.collection.as_ref()],
+        bump = collection_stats.bump,
+    )]
+    pub collection_stats: Account<'info, CollectionStats>,
+}
+
+// Returned via return data so other programs can read it over CPI
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CollectionStatsView {
+    pub collection: Pubkey,
+    pub last_price: u64,
+    pub volume: u128,
+    pub sale_count: u64,
+    pub price_cumulative: u128,      // TWAP accumulator extrapolated to `timestamp`
+    pub first_sale_at: i64,
+    pub last_sale_at: i64,
+    pub timestamp: i64,
+}
+
+pub fn process_get_collection_stats(ctx: Context<GetCollectionStats>) -> Result<CollectionStatsView> {
+    let stats = &ctx.accounts.collection_stats;
+    let now = Clock::get()?.unix_timestamp;
+
+    Ok(CollectionStatsView {
+        collection: stats.collection,
+        last_price: stats.last_price,
+        volume: stats.volume,
+        sale_count: stats.sale_count,
+        price_cumulative: stats.price_cumulative_at(now)?,
+        first_sale_at: stats.first_sale_at,
+        last_sale_at: stats.last_sale_at,
+        timestamp: now,
+    })
+}
@@ -0,0 +1,107 @@
+use anchor_lang::prelude::*;
+use mpl_core::instructions::TransferV1CpiBuilder;
+use crate::{Market, Rental, ErrorCode};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ListRental<'info> {
+    /// The owner renting out their NFT
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// The rental - PDA with seeds [b"rental", asset]
+    #[account(
+        init,
+        payer = owner,
+        space = Rental::DISCRIMINATOR.len() + Rental::INIT_SPACE,
+        seeds = [b"rental", asset.key().as_ref()],
+        bump
+    )]
+    pub rental: Account<'info, Rental>,
+
+    /// The NFT asset being rented out
+    /// CHECK: Validated as mpl-core asset from the collection
+    #[account(mut)]
+    pub asset: UncheckedAccount<'info>,
+
+    /// The collection the NFT belongs to
+    /// CHECK: Validated as mpl-core collection
+    pub collection: UncheckedAccount<'info>,
+
+    /// MPL Core program for NFT transfer
+    /// CHECK: Pinned to the mpl-core program id
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Market account (event sequence number)
+    #[account(
+        mut,
+        seeds = [b"market"],
+        bump = market.bump,
+    )]
+    pub market: Account<'info, Market>,
+}
+
+#[event]
+pub struct RentalListed {
+    pub seq: u64,
+    pub market: Pubkey,
+    pub rental: Pubkey,
+    pub owner: Pubkey,
+    pub collection: Pubkey,
+    pub asset: Pubkey,
+    pub daily_price: u64,
+    pub max_days: u16,
+    pub timestamp: i64,
+}
+
+pub fn process_list_rental(ctx: Context<ListRental>, daily_price: u64, max_days: u16) -> Result<()> {
+    require!(daily_price > 0 && max_days > 0, ErrorCode::InvalidRentalTerms);
+
+    let clock = Clock::get()?;
+
+    let rental = &mut ctx.accounts.rental;
+    rental.owner = ctx.accounts.owner.key();
+    rental.collection = ctx.accounts.collection.key();
+    rental.asset = ctx.accounts.asset.key();
+    rental.daily_price = daily_price;
+    rental.max_days = max_days;
+    rental.renter = None;
+    rental.rented_until = 0;
+    rental.created_at = clock.unix_timestamp;
+    rental.bump = ctx.bumps.rental;
+
+    // Escrow the NFT in the rental PDA for the lifetime of the listing
+    TransferV1CpiBuilder::new(&ctx.accounts.mpl_core_program.to_account_info())
+        .asset(&ctx.accounts.asset.to_account_info())
+        .collection(Some(&ctx.accounts.collection.to_account_info()))
+        .payer(&ctx.accounts.owner.to_account_info())
+        .authority(Some(&ctx.accounts.owner.to_account_info()))
+        .new_owner(&ctx.accounts.rental.to_account_info())
+        .invoke()?;
+
+    msg!(
+        "Rental listed: {} for asset: {} at {} lamports/day (max {} days)",
+        ctx.accounts.rental.key(),
+        ctx.accounts.asset.key(),
+        daily_price,
+        max_days
+    );
+
+    let seq = ctx.accounts.market.next_event_seq()?;
+    emit_cpi!(RentalListed {
+        seq,
+        market: ctx.accounts.market.key(),
+        rental: ctx.accounts.rental.key(),
+        owner: ctx.accounts.owner.key(),
+        collection: ctx.accounts.collection.key(),
+        asset: ctx.accounts.asset.key(),
+        daily_price,
+        max_days,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,172 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use mpl_core::instructions::TransferV1CpiBuilder;
+use crate::asset_pairs::expected_asset_pairs;
+use crate::{fees, BundleListing, Market, ErrorCode};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MatchBundleListing<'info> {
+    /// The buyer purchasing the bundle
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    /// The seller receiving payment and the bundle rent
+    /// CHECK: Validated against bundle.seller
+    #[account(mut)]
+    pub seller: UncheckedAccount<'info>,
+
+    /// The bundle being purchased
+    #[account(
+        mut,
+        seeds = [b"bundle", bundle.seller.as_ref(), bundle.seed.to_le_bytes().as_ref()],
+        bump = bundle.bump,
+        has_one = seller,
+        close = seller  // Return rent to seller
+    )]
+    pub bundle: Account<'info, BundleListing>,
+
+    /// Fee recipient receiving marketplace fee
+    /// CHECK: Validated against market.fee_recipient
+    #[account(mut)]
+    pub fee_recipient: UncheckedAccount<'info>,
+
+    /// MPL Core program for NFT transfers
+    /// CHECK: Pinned to the mpl-core program id
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Market account for fee configuration and event sequence
+    #[account(
+        mut,
+        seeds = [b"market"],
+        bump = market.bump,
+    )]
+    pub market: Account<'info, Market>,
+    // remaining_accounts (after the event CPI accounts): (asset, collection)
+    // pairs for the bundled assets, in listing order
+}
+
+#[event]
+pub struct BundleSold {
+    pub seq: u64,
+    pub market: Pubkey,
+    pub bundle: Pubkey,
+    pub seller: Pubkey,
+    pub buyer: Pubkey,
+    pub assets: Vec<Pubkey>,
+    pub fee_recipient: Pubkey,
+    pub price: u64,
+    pub fee_amount: u64,
+    pub timestamp: i64,
+}
+
+pub fn process_match_bundle_listing<'info>(
+    ctx: Context<'_, '_, 'info, 'info, MatchBundleListing<'info>>,
+    max_total: u64,
+) -> Result<()> {
+    let bundle = &ctx.accounts.bundle;
+    let market = &ctx.accounts.market;
+    let clock = Clock::get()?;
+
+    let pairs = expected_asset_pairs(ctx.remaining_accounts, &bundle.assets)?;
+
+    require!(
+        bundle.seller != ctx.accounts.buyer.key(),
+        ErrorCode::SelfTrade
+    );
+    require!(
+        market.fee_recipient == ctx.accounts.fee_recipient.key(),
+        ErrorCode::InvalidFeeRecipient
+    );
+
+    // Buyer pays price + taker fee, seller receives the full price
+    let price = bundle.price;
+    let fee_amount = fees::compute_fee(
+        price,
+        market.taker_fee_bps,
+        market.fee_rounding,
+        market.min_fee,
+    )?;
+    let total_amount = price
+        .checked_add(fee_amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+    // Buyer's cap guards against the fee tier or policy changing before the fill lands
+    require!(total_amount <= max_total, ErrorCode::SlippageExceeded);
+
+    if price > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.buyer.to_account_info(),
+                    to: ctx.accounts.seller.to_account_info(),
+                },
+            ),
+            price,
+        )?;
+    }
+
+    if fee_amount > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.buyer.to_account_info(),
+                    to: ctx.accounts.fee_recipient.to_account_info(),
+                },
+            ),
+            fee_amount,
+        )?;
+    }
+
+    // Prepare bundle PDA signer seeds
+    let seed_bytes = bundle.seed.to_le_bytes();
+    let seeds = &[
+        b"bundle",
+        bundle.seller.as_ref(),
+        seed_bytes.as_ref(),
+        &[bundle.bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    // Transfer every NFT from bundle PDA to buyer
+    for pair in &pairs {
+        TransferV1CpiBuilder::new(&ctx.accounts.mpl_core_program.to_account_info())
+            .asset(pair.asset)
+            .collection(pair.collection)
+            .payer(&ctx.accounts.buyer.to_account_info())
+            .authority(Some(&ctx.accounts.bundle.to_account_info()))  // PDA is authority
+            .new_owner(&ctx.accounts.buyer.to_account_info())
+            .invoke_signed(signer)?;  // Sign with PDA seeds
+    }
+
+    // Marketplace health counters
+    ctx.accounts.market.record_sale(price, fee_amount)?;
+
+    msg!(
+        "Bundle sold: {} ({} assets) to buyer {} for {} lamports",
+        ctx.accounts.bundle.key(),
+        pairs.len(),
+        ctx.accounts.buyer.key(),
+        price
+    );
+
+    let seq = ctx.accounts.market.next_event_seq()?;
+    emit_cpi!(BundleSold {
+        seq,
+        market: ctx.accounts.market.key(),
+        bundle: ctx.accounts.bundle.key(),
+        seller: ctx.accounts.seller.key(),
+        buyer: ctx.accounts.buyer.key(),
+        assets: ctx.accounts.bundle.assets.clone(),
+        fee_recipient: ctx.accounts.fee_recipient.key(),
+        price,
+        fee_amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -1,14 +1,21 @@
 use anchor_lang::prelude::*;
 use anchor_lang::system_program::{transfer, Transfer};
-use mpl_core::instructions::TransferV1CpiBuilder;
-use crate::{Listing, Market, ErrorCode};
+use mpl_core::instructions::{RemovePluginV1CpiBuilder, TransferV1CpiBuilder, UpdatePluginV1CpiBuilder};
+use mpl_core::types::{FreezeDelegate, Plugin, PluginType};
+use crate::instructions::add_listing::ListingCreated;
+use crate::{fees, merkle, oracle, CollectionStats, Listing, ListingMode, Market, RelayerApproval, RewardConfig, TraderStats, ErrorCode};
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct MatchListing<'info> {
-    /// The buyer purchasing the NFT
+    /// The buyer purchasing the NFT (pays the price)
     #[account(mut)]
     pub buyer: Signer<'info>,
 
+    /// Pays rent for new accounts - the buyer, or an approved relayer sponsoring the purchase
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
     /// The seller receiving payment (no signature needed - NFT is in escrow)
     /// CHECK: Validated against listing.seller
     #[account(mut)]
@@ -27,8 +34,9 @@ pub struct MatchListing<'info> {
     )]
     pub listing: Account<'info, Listing>,
 
-    /// Market account for fee configuration
+    /// Market account for fee configuration and event sequence
     #[account(
+        mut,
         seeds = [b"market"],
         bump = market.bump,
     )]
@@ -49,24 +57,110 @@ pub struct MatchListing<'info> {
     pub collection: UncheckedAccount<'info>,
 
     /// MPL Core program for NFT transfer
-    /// CHECK: mpl-core program
+    /// CHECK: Pinned to the mpl-core program id
+    #[account(address = mpl_core::ID)]
     pub mpl_core_program: UncheckedAccount<'info>,
 
     pub system_program: Program<'info, System>,
+
+    /// Sale stats / TWAP oracle for the collection
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = CollectionStats::DISCRIMINATOR.len() + CollectionStats::INIT_SPACE,
+        seeds = [b"collection_stats", listing.collection.as_ref()],
+        bump
+    )]
+    pub collection_stats: Account<'info, CollectionStats>,
+
+    /// Buyer reputation / trade counters
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = TraderStats::DISCRIMINATOR.len() + TraderStats::INIT_SPACE,
+        seeds = [b"trader_stats", buyer.key().as_ref()],
+        bump
+    )]
+    pub buyer_stats: Account<'info, TraderStats>,
+
+    /// Seller reputation / trade counters
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = TraderStats::DISCRIMINATOR.len() + TraderStats::INIT_SPACE,
+        seeds = [b"trader_stats", listing.seller.as_ref()],
+        bump
+    )]
+    pub seller_stats: Account<'info, TraderStats>,
+
+    /// Optional reward program - PDA with seeds [b"reward_config", market]
+    /// CHECK: Skipped if not initialized, otherwise owner checked and deserialized
+    #[account(
+        seeds = [b"reward_config", market.key().as_ref()],
+        bump
+    )]
+    pub reward_config: UncheckedAccount<'info>,
+
+    /// Pyth SOL/USD price account, required for USD-priced listings
+    /// CHECK: Validated against market.sol_usd_feed and parsed by oracle::load_price
+    pub price_feed: Option<UncheckedAccount<'info>>,
+
+    /// Per-collection price bounds for oracle prices and the relist, may not exist
+    /// CHECK: PDA with seeds [b"collection_config", collection], read only if initialized
+    #[account(
+        seeds = [b"collection_config", listing.collection.as_ref()],
+        bump
+    )]
+    pub collection_config: UncheckedAccount<'info>,
+
+    /// Buyer's new listing when relisting on purchase - PDA with seeds
+    /// [b"listing", buyer, collection, asset]
+    #[account(
+        init,
+        payer = payer,
+        space = Listing::DISCRIMINATOR.len() + Listing::INIT_SPACE,
+        seeds = [
+            b"listing",
+            buyer.key().as_ref(),
+            listing.collection.as_ref(),
+            listing.asset.as_ref()
+        ],
+        bump
+    )]
+    pub relist: Option<Box<Account<'info, Listing>>>,
+
+    /// Relayer allowlist entry - PDA with seeds [b"relayer", market, payer],
+    /// required when the payer is not the buyer
+    #[account(
+        seeds = [b"relayer", market.key().as_ref(), payer.key().as_ref()],
+        bump = relayer_approval.bump,
+    )]
+    pub relayer_approval: Option<Account<'info, RelayerApproval>>,
 }
 
 #[event]
 pub struct ListingSold {
+    pub seq: u64,
+    pub market: Pubkey,
     pub listing: Pubkey,
     pub seller: Pubkey,
     pub buyer: Pubkey,
+    pub collection: Pubkey,
     pub asset: Pubkey,
+    pub mode: ListingMode,
+    pub fee_recipient: Pubkey,
     pub price: u64,
-    pub fee_amount: u64,
+    pub fee_amount: u64,       // Taker fee paid by the buyer
+    pub maker_fee_amount: u64, // Maker fee deducted from seller proceeds
     pub timestamp: i64,
 }
 
-pub fn process_match_listing(ctx: Context<MatchListing>) -> Result<()> {
+pub fn process_match_listing(
+    ctx: Context<MatchListing>,
+    proof: Vec<[u8; 32]>,
+    relist_price: Option<u64>,
+    max_total: u64,
+) -> Result<()> {
     // Save values and accounts we need before mutating listing
     let listing_key = ctx.accounts.listing.key();
     let listing_price = ctx.accounts.listing.price;
@@ -74,6 +168,7 @@ pub fn process_match_listing(ctx: Context<MatchListing>) -> Result<()> {
     let listing_seller = ctx.accounts.listing.seller;
     let listing_collection = ctx.accounts.listing.collection;
     let listing_asset = ctx.accounts.listing.asset;
+    let listing_mode = ctx.accounts.listing.mode;
     let listing_account_info = ctx.accounts.listing.to_account_info();
 
     let listing = &mut ctx.accounts.listing;
@@ -89,6 +184,17 @@ pub fn process_match_listing(ctx: Context<MatchListing>) -> Result<()> {
         ErrorCode::InvalidAsset
     );
 
+    // Prevent wash trades (and aliasing buyer/seller stats)
+    require!(
+        listing.seller != ctx.accounts.buyer.key(),
+        ErrorCode::SelfTrade
+    );
+
+    // Sponsored purchase: only allowlisted relayers may pay on a buyer's behalf
+    if ctx.accounts.payer.key() != ctx.accounts.buyer.key() {
+        require!(ctx.accounts.relayer_approval.is_some(), ErrorCode::RelayerNotApproved);
+    }
+
     // Validate asset matches listing
     require!(
         listing.asset == ctx.accounts.asset.key(),
@@ -107,37 +213,110 @@ pub fn process_match_listing(ctx: Context<MatchListing>) -> Result<()> {
         ErrorCode::InvalidFeeRecipient
     );
 
+    // Private sale: only the reserved buyer can execute
+    if let Some(reserved_buyer) = listing.reserved_buyer {
+        require!(
+            reserved_buyer == ctx.accounts.buyer.key(),
+            ErrorCode::ReservedForOtherBuyer
+        );
+    }
+
+    // Gated sale: the listing's own allowlist takes precedence over market early access
+    let allowlist_root = match listing.allowlist_root {
+        Some(root) => Some(root),
+        None if clock.unix_timestamp < market.early_access_ends_at => market.allowlist_root,
+        None => None,
+    };
+    if let Some(root) = allowlist_root {
+        require!(
+            merkle::verify(&proof, root, merkle::wallet_leaf(&ctx.accounts.buyer.key())),
+            ErrorCode::NotAllowlisted
+        );
+    }
+
+    // Relisting needs both the new price and the new listing account
+    require!(
+        relist_price.is_some() == ctx.accounts.relist.is_some(),
+        ErrorCode::InvalidRelist
+    );
+    if let Some(relist_price) = relist_price {
+        market.check_price(relist_price, &ctx.accounts.collection_config)?;
+    }
+
+    // USD-priced listing: lamport price comes from the oracle at fill time,
+    // the listed lamport price acts as a floor for the seller
+    let listing_price = match listing.usd_pricing {
+        Some(pricing) => {
+            let price_feed = ctx.accounts.price_feed.as_ref().ok_or(ErrorCode::InvalidPriceFeed)?;
+            require_keys_neq!(market.sol_usd_feed, Pubkey::default(), ErrorCode::InvalidPriceFeed);
+            require_keys_eq!(price_feed.key(), market.sol_usd_feed, ErrorCode::InvalidPriceFeed);
+            let oracle_price = oracle::load_price(price_feed, clock.unix_timestamp)?;
+            let lamports = oracle::lamports_for_usd(pricing.usd_price, &oracle_price)?;
+            require!(lamports >= listing_price, ErrorCode::PriceBelowFloor);
+            // Oracle price is held to the same bounds as a listed lamport price
+            market.check_price(lamports, &ctx.accounts.collection_config)?;
+            lamports
+        }
+        None => listing_price,
+    };
+
+    // Fee tiers by lifetime volume before this trade: the buyer's tier sets the
+    // taker fee, the seller's tier sets the maker fee (no tier = base taker fee)
+    let taker_fee_bps = market
+        .fee_tier(ctx.accounts.buyer_stats.volume())
+        .map_or(market.taker_fee_bps, |tier| tier.taker_fee_bps);
+    let maker_fee_bps = market
+        .fee_tier(ctx.accounts.seller_stats.volume())
+        .map_or(0, |tier| tier.maker_fee_bps);
+
     // Calculate fee amount (taker fee)
-    let fee_amount = (listing_price as u128)
-        .checked_mul(market.taker_fee_bps as u128)
-        .unwrap()
-        .checked_div(10000)
-        .unwrap() as u64;
+    let fee_amount = fees::compute_fee(
+        listing_price,
+        taker_fee_bps,
+        market.fee_rounding,
+        market.min_fee,
+    )?;
+
+    // Maker fee comes out of the seller's proceeds
+    let maker_fee_amount = fees::compute_fee(listing_price, maker_fee_bps, market.fee_rounding, 0)?;
+    let seller_amount = listing_price
+        .checked_sub(maker_fee_amount)
+        .ok_or(ErrorCode::MathOverflow)?;
 
     // Total amount buyer needs to pay
-    let total_amount = listing_price.checked_add(fee_amount).unwrap();
+    let total_amount = listing_price
+        .checked_add(fee_amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+    // Buyer's cap guards against the price moving (oracle, seller edits) before the fill lands
+    require!(total_amount <= max_total, ErrorCode::SlippageExceeded);
+    let market_fee_amount = fee_amount
+        .checked_add(maker_fee_amount)
+        .ok_or(ErrorCode::MathOverflow)?;
 
     msg!(
-        "Purchase: price={} lamports, fee={} lamports, total={} lamports",
+        "Purchase: price={} lamports, fee={} lamports, maker fee={} lamports, total={} lamports",
         listing_price,
         fee_amount,
+        maker_fee_amount,
         total_amount
     );
 
     // Transfer payment from buyer to seller
-    transfer(
-        CpiContext::new(
-            ctx.accounts.system_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.buyer.to_account_info(),
-                to: ctx.accounts.seller.to_account_info(),
-            },
-        ),
-        listing_price,
-    )?;
+    if seller_amount > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.buyer.to_account_info(),
+                    to: ctx.accounts.seller.to_account_info(),
+                },
+            ),
+            seller_amount,
+        )?;
+    }
 
-    // Transfer fee from buyer to fee_recipient
-    if fee_amount > 0 {
+    // Transfer taker + maker fees from buyer to fee_recipient
+    if market_fee_amount > 0 {
         transfer(
             CpiContext::new(
                 ctx.accounts.system_program.to_account_info(),
@@ -146,7 +325,7 @@ pub fn process_match_listing(ctx: Context<MatchListing>) -> Result<()> {
                     to: ctx.accounts.fee_recipient.to_account_info(),
                 },
             ),
-            fee_amount,
+            market_fee_amount,
         )?;
     }
 
@@ -160,20 +339,115 @@ pub fn process_match_listing(ctx: Context<MatchListing>) -> Result<()> {
     ];
     let signer = &[&seeds[..]];
 
-    // Transfer NFT from listing PDA to buyer using PDA as authority
+    // Delegated listing: thaw the NFT in the seller's wallet (PDA is freeze delegate)
+    if listing_mode == ListingMode::Delegated {
+        UpdatePluginV1CpiBuilder::new(&ctx.accounts.mpl_core_program.to_account_info())
+            .asset(&ctx.accounts.asset.to_account_info())
+            .collection(Some(&ctx.accounts.collection.to_account_info()))
+            .payer(&ctx.accounts.payer.to_account_info())
+            .authority(Some(&listing_account_info))  // PDA is authority
+            .system_program(&ctx.accounts.system_program.to_account_info())
+            .plugin(Plugin::FreezeDelegate(FreezeDelegate { frozen: false }))
+            .invoke_signed(signer)?;  // Sign with PDA seeds
+    }
+
+    // Transfer NFT to buyer (or straight into the buyer's relist escrow) using PDA
+    // as authority (owner in escrow mode, transfer delegate in delegated mode)
+    let new_owner = match &ctx.accounts.relist {
+        Some(relist) => relist.to_account_info(),
+        None => ctx.accounts.buyer.to_account_info(),
+    };
     TransferV1CpiBuilder::new(&ctx.accounts.mpl_core_program.to_account_info())
         .asset(&ctx.accounts.asset.to_account_info())
         .collection(Some(&ctx.accounts.collection.to_account_info()))
-        .payer(&ctx.accounts.buyer.to_account_info())
+        .payer(&ctx.accounts.payer.to_account_info())
         .authority(Some(&listing_account_info))  // PDA is authority
-        .new_owner(&ctx.accounts.buyer.to_account_info())
+        .new_owner(&new_owner)
         .invoke_signed(signer)?;  // Sign with PDA seeds
 
+    // Delegated listing: the new owner drops both delegates the listing PDA held
+    // (the buyer signs, a relist escrow signs with its own PDA seeds)
+    if listing_mode == ListingMode::Delegated {
+        let buyer_key = ctx.accounts.buyer.key();
+        let relist_bump = [ctx.bumps.relist.unwrap_or_default()];
+        let relist_seeds = &[
+            b"listing",
+            buyer_key.as_ref(),
+            listing_collection.as_ref(),
+            listing_asset.as_ref(),
+            &relist_bump,
+        ];
+        let owner_signer: &[&[&[u8]]] = if ctx.accounts.relist.is_some() {
+            &[&relist_seeds[..]]
+        } else {
+            &[]
+        };
+        for plugin_type in [PluginType::FreezeDelegate, PluginType::TransferDelegate] {
+            RemovePluginV1CpiBuilder::new(&ctx.accounts.mpl_core_program.to_account_info())
+                .asset(&ctx.accounts.asset.to_account_info())
+                .collection(Some(&ctx.accounts.collection.to_account_info()))
+                .payer(&ctx.accounts.payer.to_account_info())
+                .authority(Some(&new_owner))  // New owner removes owner-managed plugins
+                .system_program(&ctx.accounts.system_program.to_account_info())
+                .plugin_type(plugin_type)
+                .invoke_signed(owner_signer)?;
+        }
+    }
+
     // Mark listing as inactive
     listing.is_active = false;
 
+    // Update collection price oracle
+    let collection_stats = &mut ctx.accounts.collection_stats;
+    collection_stats.collection = listing_collection;
+    collection_stats.bump = ctx.bumps.collection_stats;
+    collection_stats.record_sale(listing_price, clock.unix_timestamp)?;
+
+    // Update trader reputation
+    let buyer_stats = &mut ctx.accounts.buyer_stats;
+    buyer_stats.wallet = ctx.accounts.buyer.key();
+    buyer_stats.bump = ctx.bumps.buyer_stats;
+    buyer_stats.record_trade(listing_price, 0, clock.unix_timestamp)?;
+
+    let seller_stats = &mut ctx.accounts.seller_stats;
+    seller_stats.wallet = listing_seller;
+    seller_stats.bump = ctx.bumps.seller_stats;
+    seller_stats.record_trade(0, listing_price, clock.unix_timestamp)?;
+
+    // Accrue volume rewards to both sides if the market runs a reward program
+    if !ctx.accounts.reward_config.data_is_empty() {
+        require_keys_eq!(*ctx.accounts.reward_config.owner, crate::ID);
+        let reward_config = RewardConfig::try_deserialize(&mut &ctx.accounts.reward_config.data.borrow()[..])?;
+        if reward_config.enabled {
+            let reward = reward_config.reward_for(listing_price)?;
+            ctx.accounts.buyer_stats.accrue_rewards(reward)?;
+            ctx.accounts.seller_stats.accrue_rewards(reward)?;
+        }
+    }
+
+    // Marketplace health counters
+    ctx.accounts.market.record_sale(listing_price, market_fee_amount)?;
+
+    // Relist on purchase: the NFT already sits in the buyer's new listing PDA
+    if let (Some(relist), Some(relist_price)) = (ctx.accounts.relist.as_mut(), relist_price) {
+        relist.seller = ctx.accounts.buyer.key();
+        relist.collection = listing_collection;
+        relist.asset = ctx.accounts.asset.key();
+        relist.price = relist_price;
+        relist.is_active = true;
+        relist.created_at = clock.unix_timestamp;
+        relist.bump = ctx.bumps.relist.ok_or(ErrorCode::InvalidRelist)?;
+        relist.allowlist_root = None;
+        relist.reserved_buyer = None;
+        relist.mode = ListingMode::Escrow;
+        relist.version = Listing::VERSION;
+        relist.installment_plan = None;
+        relist.usd_pricing = None;
+        ctx.accounts.market.record_listing()?;
+    }
+
     msg!(
-        "NFT sold: asset {} transferred from escrow to buyer {} for {} lamports (seller: {})",
+        "NFT sold: asset {} transferred to buyer {} for {} lamports (seller: {})",
         ctx.accounts.asset.key(),
         ctx.accounts.buyer.key(),
         listing_price,
@@ -181,15 +455,46 @@ pub fn process_match_listing(ctx: Context<MatchListing>) -> Result<()> {
     );
 
     // Emit sale event
-    emit!(ListingSold {
+    let seq = ctx.accounts.market.next_event_seq()?;
+    emit_cpi!(ListingSold {
+        seq,
+        market: ctx.accounts.market.key(),
         listing: listing_key,
         seller: ctx.accounts.seller.key(),
         buyer: ctx.accounts.buyer.key(),
+        collection: listing_collection,
         asset: ctx.accounts.asset.key(),
+        mode: listing_mode,
+        fee_recipient: ctx.accounts.fee_recipient.key(),
         price: listing_price,
         fee_amount,
+        maker_fee_amount,
         timestamp: clock.unix_timestamp,
     });
 
+    if let Some(relist_price) = relist_price {
+        msg!(
+            "Asset {} relisted by buyer {} at {} lamports",
+            ctx.accounts.asset.key(),
+            ctx.accounts.buyer.key(),
+            relist_price
+        );
+
+        let seq = ctx.accounts.market.next_event_seq()?;
+        emit_cpi!(ListingCreated {
+            seq,
+            market: ctx.accounts.market.key(),
+            listing: ctx.accounts.relist.as_ref().map(|relist| relist.key()).ok_or(ErrorCode::InvalidRelist)?,
+            seller: ctx.accounts.buyer.key(),
+            collection: listing_collection,
+            asset: ctx.accounts.asset.key(),
+            price: relist_price,
+            allowlist_root: None,
+            reserved_buyer: None,
+            mode: ListingMode::Escrow,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
     Ok(())
 }
@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+use crate::instructions::migrate_market::{check_legacy_account, grow_account};
+use crate::{Listing, Market, ErrorCode};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MigrateListing<'info> {
+    /// Anyone can migrate a listing, they pay the extra rent
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Listing account in any previous layout
+    /// CHECK: Legacy layouts don't deserialize, owner/discriminator checked manually
+    #[account(mut)]
+    pub listing: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Market account (event sequence number), must be migrated first
+    #[account(
+        mut,
+        seeds = [b"market"],
+        bump = market.bump,
+    )]
+    pub market: Account<'info, Market>,
+}
+
+#[event]
+pub struct ListingMigrated {
+    pub seq: u64,
+    pub market: Pubkey,
+    pub listing: Pubkey,
+    pub payer: Pubkey,
+    pub from_version: u8,
+    pub to_version: u8,
+    pub timestamp: i64,
+}
+
+pub fn process_migrate_listing(ctx: Context<MigrateListing>) -> Result<()> {
+    let listing_info = ctx.accounts.listing.to_account_info();
+    check_legacy_account(&listing_info, Listing::DISCRIMINATOR)?;
+
+    grow_account(
+        &listing_info,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        Listing::DISCRIMINATOR.len() + Listing::INIT_SPACE,
+    )?;
+
+    // v1 fields missing from the old layout default to no gating, escrow mode
+    let mut listing = Listing::try_deserialize(&mut &listing_info.data.borrow()[..])?;
+    require!(listing.version < Listing::VERSION, ErrorCode::AlreadyMigrated);
+    let from_version = listing.version.max(1); // legacy v1 accounts read back version 0
+
+    listing.version = Listing::VERSION;
+    listing.try_serialize(&mut &mut listing_info.try_borrow_mut_data()?[..])?;
+
+    msg!("Listing migrated: v{} -> v{}", from_version, Listing::VERSION);
+
+    let seq = ctx.accounts.market.next_event_seq()?;
+    emit_cpi!(ListingMigrated {
+        seq,
+        market: ctx.accounts.market.key(),
+        listing: listing_info.key(),
+        payer: ctx.accounts.payer.key(),
+        from_version,
+        to_version: Listing::VERSION,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,119 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::{Market, ErrorCode};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MigrateMarket<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Market account in any previous layout
+    /// CHECK: Legacy layouts don't deserialize, owner/discriminator/admin checked manually
+    #[account(
+        mut,
+        seeds = [b"market"],
+        bump
+    )]
+    pub market: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct MarketMigrated {
+    pub seq: u64,
+    pub market: Pubkey,
+    pub from_version: u8,
+    pub to_version: u8,
+    pub timestamp: i64,
+}
+
+// Program-owned account with the expected discriminator (layout may be stale)
+pub(crate) fn check_legacy_account(account: &AccountInfo, discriminator: &[u8]) -> Result<()> {
+    require_keys_eq!(*account.owner, crate::ID);
+    require!(
+        account.data.borrow().starts_with(discriminator),
+        anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch
+    );
+    Ok(())
+}
+
+// Realloc to `new_len`, topping up rent from `payer`. New bytes are zeroed so
+// fields missing from the old layout read back as 0 / None / first enum variant
+pub(crate) fn grow_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    new_len: usize,
+) -> Result<()> {
+    let old_len = account.data_len();
+    if old_len >= new_len {
+        return Ok(());
+    }
+
+    let top_up = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(account.lamports());
+    if top_up > 0 {
+        transfer(
+            CpiContext::new(
+                system_program.clone(),
+                Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            top_up,
+        )?;
+    }
+
+    account.resize(new_len)?;
+    account.try_borrow_mut_data()?[old_len..].fill(0);
+    Ok(())
+}
+
+pub fn process_migrate_market(ctx: Context<MigrateMarket>) -> Result<()> {
+    let market_info = ctx.accounts.market.to_account_info();
+    check_legacy_account(&market_info, Market::DISCRIMINATOR)?;
+
+    // admin is the first field in every layout
+    let admin = Pubkey::try_from(&market_info.data.borrow()[8..40])
+        .map_err(|_| ErrorCode::Unauthorized)?;
+    require_keys_eq!(admin, ctx.accounts.admin.key(), ErrorCode::Unauthorized);
+
+    grow_account(
+        &market_info,
+        &ctx.accounts.admin.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        Market::DISCRIMINATOR.len() + Market::INIT_SPACE,
+    )?;
+
+    let mut market = Market::try_deserialize(&mut &market_info.data.borrow()[..])?;
+    require!(market.version < Market::VERSION, ErrorCode::AlreadyMigrated);
+    let from_version = market.version.max(1); // legacy v1 accounts read back version 0
+
+    // v1 had no price bounds: match init_market (a zero max would reject every
+    // listing, a zero min would accept 0-lamport listings)
+    if market.min_price == 0 {
+        market.min_price = 1;
+    }
+    if market.max_price == 0 {
+        market.max_price = u64::MAX;
+    }
+    market.version = Market::VERSION;
+    let seq = market.next_event_seq()?;
+    market.try_serialize(&mut &mut market_info.try_borrow_mut_data()?[..])?;
+
+    msg!("Market migrated: v{} -> v{}", from_version, Market::VERSION);
+
+    emit_cpi!(MarketMigrated {
+        seq,
+        market: market_info.key(),
+        from_version,
+        to_version: Market::VERSION,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,212 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use mpl_core::instructions::CreateV2CpiBuilder;
+use crate::{fees, merkle, Drop, DropMintRecord, Market, ErrorCode};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MintDrop<'info> {
+    /// The buyer minting from the drop
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    /// The new asset account (mpl-core asset)
+    #[account(mut)]
+    pub asset: Signer<'info>,
+
+    /// The drop being minted from
+    #[account(
+        mut,
+        seeds = [b"drop", drop.collection.as_ref()],
+        bump = drop.bump,
+    )]
+    pub drop: Account<'info, Drop>,
+
+    /// Per-wallet mint counter - PDA with seeds [b"drop_mint", drop, buyer]
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = DropMintRecord::DISCRIMINATOR.len() + DropMintRecord::INIT_SPACE,
+        seeds = [b"drop_mint", drop.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub mint_record: Account<'info, DropMintRecord>,
+
+    /// Market account for fee configuration and event sequence
+    #[account(
+        mut,
+        seeds = [b"market"],
+        bump = market.bump,
+    )]
+    pub market: Account<'info, Market>,
+
+    /// The creator receiving proceeds
+    /// CHECK: Validated against drop.creator
+    #[account(mut)]
+    pub creator: UncheckedAccount<'info>,
+
+    /// Fee recipient receiving marketplace fee
+    /// CHECK: Validated against market.fee_recipient
+    #[account(mut)]
+    pub fee_recipient: UncheckedAccount<'info>,
+
+    /// The collection being minted into
+    /// CHECK: Validated against drop.collection
+    #[account(mut)]
+    pub collection: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// MPL Core program
+    /// CHECK: Pinned to the mpl-core program id
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: UncheckedAccount<'info>,
+}
+
+#[event]
+pub struct DropMinted {
+    pub seq: u64,
+    pub market: Pubkey,
+    pub drop: Pubkey,
+    pub creator: Pubkey,
+    pub collection: Pubkey,
+    pub asset: Pubkey,
+    pub buyer: Pubkey,
+    pub fee_recipient: Pubkey,
+    pub index: u64,
+    pub price: u64,
+    pub fee_amount: u64,
+    pub timestamp: i64,
+}
+
+pub fn process_mint_drop(ctx: Context<MintDrop>, proof: Vec<[u8; 32]>) -> Result<()> {
+    let drop = &ctx.accounts.drop;
+    let market = &ctx.accounts.market;
+    let buyer_key = ctx.accounts.buyer.key();
+    let clock = Clock::get()?;
+
+    // Validate accounts match drop and market
+    require!(
+        drop.creator == ctx.accounts.creator.key(),
+        ErrorCode::InvalidDropConfig
+    );
+    require!(
+        drop.collection == ctx.accounts.collection.key(),
+        ErrorCode::InvalidAsset
+    );
+    require!(
+        market.fee_recipient == ctx.accounts.fee_recipient.key(),
+        ErrorCode::InvalidFeeRecipient
+    );
+
+    // Phases: allowlist from allowlist_start, public from public_start
+    if clock.unix_timestamp < drop.public_start {
+        let root = drop.allowlist_root.ok_or(ErrorCode::DropNotStarted)?;
+        require!(
+            clock.unix_timestamp >= drop.allowlist_start,
+            ErrorCode::DropNotStarted
+        );
+        require!(
+            merkle::verify(&proof, root, merkle::wallet_leaf(&buyer_key)),
+            ErrorCode::NotAllowlisted
+        );
+    }
+
+    require!(drop.minted < drop.max_supply, ErrorCode::DropSoldOut);
+    require!(
+        drop.wallet_limit == 0 || ctx.accounts.mint_record.minted < drop.wallet_limit,
+        ErrorCode::WalletMintLimitReached
+    );
+
+    // Split price between creator and marketplace
+    let price = drop.price;
+    let fee_amount = fees::compute_fee_from_proceeds(
+        price,
+        market.taker_fee_bps,
+        market.fee_rounding,
+        market.min_fee,
+    )?;
+    let creator_amount = price
+        .checked_sub(fee_amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    if creator_amount > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.buyer.to_account_info(),
+                    to: ctx.accounts.creator.to_account_info(),
+                },
+            ),
+            creator_amount,
+        )?;
+    }
+
+    if fee_amount > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.buyer.to_account_info(),
+                    to: ctx.accounts.fee_recipient.to_account_info(),
+                },
+            ),
+            fee_amount,
+        )?;
+    }
+
+    let index = drop.minted.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+    let collection_key = drop.collection;
+    let seeds = &[b"drop", collection_key.as_ref(), &[drop.bump]];
+    let signer = &[&seeds[..]];
+
+    // Mint the asset into the collection using the drop PDA as authority
+    CreateV2CpiBuilder::new(&ctx.accounts.mpl_core_program.to_account_info())
+        .asset(&ctx.accounts.asset.to_account_info())
+        .collection(Some(&ctx.accounts.collection.to_account_info()))
+        .authority(Some(&ctx.accounts.drop.to_account_info()))  // PDA is authority
+        .payer(&ctx.accounts.buyer.to_account_info())
+        .owner(Some(&ctx.accounts.buyer.to_account_info()))
+        .system_program(&ctx.accounts.system_program.to_account_info())
+        .name(format!("{} #{}", drop.name, index))
+        .uri(format!("{}/{}.json", drop.uri, index))
+        .invoke_signed(signer)?;  // Sign with PDA seeds
+
+    let drop_key = ctx.accounts.drop.key();
+    ctx.accounts.drop.minted = index;
+
+    let mint_record = &mut ctx.accounts.mint_record;
+    mint_record.drop = drop_key;
+    mint_record.wallet = buyer_key;
+    mint_record.minted = mint_record.minted
+        .checked_add(1)
+        .ok_or(ErrorCode::MathOverflow)?;
+    mint_record.bump = ctx.bumps.mint_record;
+
+    msg!(
+        "Drop mint #{}: asset {} to buyer {} for {} lamports",
+        index,
+        ctx.accounts.asset.key(),
+        buyer_key,
+        price
+    );
+
+    let seq = ctx.accounts.market.next_event_seq()?;
+    emit_cpi!(DropMinted {
+        seq,
+        market: ctx.accounts.market.key(),
+        drop: drop_key,
+        creator: ctx.accounts.creator.key(),
+        collection: collection_key,
+        asset: ctx.accounts.asset.key(),
+        buyer: buyer_key,
+        fee_recipient: ctx.accounts.fee_recipient.key(),
+        index,
+        price,
+        fee_amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -2,8 +2,7 @@
 // pub use init_market::*;
 // pub mod init_market;
 
-// pub use update_market::*;
-// pub mod update_market;
+pub mod update_market;
 
 pub use create_collection::*;
 pub mod create_collection;
@@ -16,3 +15,154 @@ pub mod match_listing;
 
 pub use remove_listing::*;
 pub mod remove_listing;
+
+pub use create_drop::*;
+pub mod create_drop;
+
+pub use mint_drop::*;
+pub mod mint_drop;
+
+pub use create_swap::*;
+pub mod create_swap;
+
+pub use accept_swap::*;
+pub mod accept_swap;
+
+pub use cancel_swap::*;
+pub mod cancel_swap;
+
+pub mod set_market_allowlist;
+
+pub mod set_price_bounds;
+
+pub mod set_fee_policy;
+
+pub mod set_fee_tiers;
+
+pub use set_collection_price_bounds::*;
+pub mod set_collection_price_bounds;
+
+pub use get_collection_stats::*;
+pub mod get_collection_stats;
+
+pub use set_reward_config::*;
+pub mod set_reward_config;
+
+pub use claim_rewards::*;
+pub mod claim_rewards;
+
+pub use migrate_market::*;
+pub mod migrate_market;
+
+pub use migrate_listing::*;
+pub mod migrate_listing;
+
+pub use close_inactive_listing::*;
+pub mod close_inactive_listing;
+
+pub use close_inactive_listings::*;
+pub mod close_inactive_listings;
+
+pub use create_loan_offer::*;
+pub mod create_loan_offer;
+
+pub use cancel_loan_offer::*;
+pub mod cancel_loan_offer;
+
+pub use accept_loan_offer::*;
+pub mod accept_loan_offer;
+
+pub use repay_loan::*;
+pub mod repay_loan;
+
+pub use foreclose_loan::*;
+pub mod foreclose_loan;
+
+pub use list_rental::*;
+pub mod list_rental;
+
+pub use rent_asset::*;
+pub mod rent_asset;
+
+pub use reclaim_rental::*;
+pub mod reclaim_rental;
+
+pub use remove_rental::*;
+pub mod remove_rental;
+
+pub use set_installment_plan::*;
+pub mod set_installment_plan;
+
+pub use start_installment_purchase::*;
+pub mod start_installment_purchase;
+
+pub use pay_installment::*;
+pub mod pay_installment;
+
+pub use reclaim_installment::*;
+pub mod reclaim_installment;
+
+pub use add_bundle_listing::*;
+pub mod add_bundle_listing;
+
+pub use match_bundle_listing::*;
+pub mod match_bundle_listing;
+
+pub use remove_bundle_listing::*;
+pub mod remove_bundle_listing;
+
+pub use fractionalize::*;
+pub mod fractionalize;
+
+pub use place_buyout_bid::*;
+pub mod place_buyout_bid;
+
+pub use settle_buyout::*;
+pub mod settle_buyout;
+
+pub use redeem_fractions::*;
+pub mod redeem_fractions;
+
+pub use create_trait_bid::*;
+pub mod create_trait_bid;
+
+pub use cancel_trait_bid::*;
+pub mod cancel_trait_bid;
+
+pub use fill_trait_bid::*;
+pub mod fill_trait_bid;
+
+pub use set_usd_price::*;
+pub mod set_usd_price;
+
+pub mod set_price_feed;
+
+pub use create_pool::*;
+pub mod create_pool;
+
+pub use deposit_pool_sol::*;
+pub mod deposit_pool_sol;
+
+pub use withdraw_pool_sol::*;
+pub mod withdraw_pool_sol;
+
+pub use deposit_pool_nft::*;
+pub mod deposit_pool_nft;
+
+pub use withdraw_pool_nft::*;
+pub mod withdraw_pool_nft;
+
+pub use buy_from_pool::*;
+pub mod buy_from_pool;
+
+pub use sell_to_pool::*;
+pub mod sell_to_pool;
+
+pub use close_pool::*;
+pub mod close_pool;
+
+pub use add_relayer::*;
+pub mod add_relayer;
+
+pub use remove_relayer::*;
+pub mod remove_relayer;
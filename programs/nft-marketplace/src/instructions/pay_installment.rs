@@ -0,0 +1,184 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use mpl_core::instructions::TransferV1CpiBuilder;
+use crate::{fees, InstallmentPurchase, Market, ErrorCode};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct PayInstallment<'info> {
+    /// The buyer paying the installment
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    /// The seller receiving the installment
+    /// CHECK: Validated against purchase.seller
+    #[account(mut)]
+    pub seller: UncheckedAccount<'info>,
+
+    /// The purchase being paid off
+    #[account(
+        mut,
+        seeds = [b"installment", purchase.listing.as_ref()],
+        bump = purchase.bump,
+        has_one = buyer,
+        has_one = seller,
+        has_one = asset,
+        has_one = collection,
+    )]
+    pub purchase: Account<'info, InstallmentPurchase>,
+
+    /// The escrowed NFT, released on the last installment
+    /// CHECK: Validated against purchase.asset
+    #[account(mut)]
+    pub asset: UncheckedAccount<'info>,
+
+    /// The collection the NFT belongs to
+    /// CHECK: Validated against purchase.collection
+    pub collection: UncheckedAccount<'info>,
+
+    /// Fee recipient receiving marketplace fee
+    /// CHECK: Validated against market.fee_recipient
+    #[account(mut)]
+    pub fee_recipient: UncheckedAccount<'info>,
+
+    /// MPL Core program for NFT transfer
+    /// CHECK: Pinned to the mpl-core program id
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Market account for fee configuration and event sequence
+    #[account(
+        mut,
+        seeds = [b"market"],
+        bump = market.bump,
+    )]
+    pub market: Account<'info, Market>,
+}
+
+#[event]
+pub struct InstallmentPaid {
+    pub seq: u64,
+    pub market: Pubkey,
+    pub purchase: Pubkey,
+    pub seller: Pubkey,
+    pub buyer: Pubkey,
+    pub collection: Pubkey,
+    pub asset: Pubkey,
+    pub fee_recipient: Pubkey,
+    pub amount: u64,
+    pub fee_amount: u64,
+    pub installments_paid: u8,
+    pub remaining: u64,
+    pub completed: bool,
+    pub next_due_at: i64,
+    pub timestamp: i64,
+}
+
+pub fn process_pay_installment(ctx: Context<PayInstallment>) -> Result<()> {
+    let purchase = &ctx.accounts.purchase;
+    let market = &ctx.accounts.market;
+    let clock = Clock::get()?;
+
+    require!(
+        market.fee_recipient == ctx.accounts.fee_recipient.key(),
+        ErrorCode::InvalidFeeRecipient
+    );
+    require!(clock.unix_timestamp <= purchase.next_due_at, ErrorCode::InstallmentOverdue);
+
+    let amount = purchase.next_installment()?;
+    let fee_amount = fees::compute_fee(
+        amount,
+        market.taker_fee_bps,
+        market.fee_rounding,
+        market.min_fee,
+    )?;
+
+    transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.buyer.to_account_info(),
+                to: ctx.accounts.seller.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    if fee_amount > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.buyer.to_account_info(),
+                    to: ctx.accounts.fee_recipient.to_account_info(),
+                },
+            ),
+            fee_amount,
+        )?;
+    }
+
+    let purchase = &mut ctx.accounts.purchase;
+    purchase.paid = purchase.paid.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+    purchase.fees_paid = purchase.fees_paid.checked_add(fee_amount).ok_or(ErrorCode::MathOverflow)?;
+    purchase.installments_paid = purchase.installments_paid.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+    purchase.next_due_at = purchase.next_due_at
+        .checked_add(purchase.interval)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    let completed = purchase.installments_paid == purchase.installment_count;
+    let remaining = purchase.price.checked_sub(purchase.paid).ok_or(ErrorCode::MathOverflow)?;
+    let installments_paid = purchase.installments_paid;
+    let next_due_at = purchase.next_due_at;
+
+    if completed {
+        // Paid off: release the NFT using the purchase PDA as authority
+        let listing_key = purchase.listing;
+        let seeds = &[b"installment", listing_key.as_ref(), &[purchase.bump]];
+        let signer = &[&seeds[..]];
+
+        TransferV1CpiBuilder::new(&ctx.accounts.mpl_core_program.to_account_info())
+            .asset(&ctx.accounts.asset.to_account_info())
+            .collection(Some(&ctx.accounts.collection.to_account_info()))
+            .payer(&ctx.accounts.buyer.to_account_info())
+            .authority(Some(&ctx.accounts.purchase.to_account_info()))  // PDA is authority
+            .new_owner(&ctx.accounts.buyer.to_account_info())
+            .invoke_signed(signer)?;  // Sign with PDA seeds
+
+        let (price, fees_paid) = (ctx.accounts.purchase.price, ctx.accounts.purchase.fees_paid);
+        ctx.accounts.market.record_sale(price, fees_paid)?;
+
+        // Buyer paid the purchase rent
+        ctx.accounts.purchase.close(ctx.accounts.buyer.to_account_info())?;
+    }
+
+    msg!(
+        "Installment {} paid: {} lamports, {} remaining{}",
+        installments_paid,
+        amount,
+        remaining,
+        if completed { " - NFT released to buyer" } else { "" }
+    );
+
+    let seq = ctx.accounts.market.next_event_seq()?;
+    emit_cpi!(InstallmentPaid {
+        seq,
+        market: ctx.accounts.market.key(),
+        purchase: ctx.accounts.purchase.key(),
+        seller: ctx.accounts.seller.key(),
+        buyer: ctx.accounts.buyer.key(),
+        collection: ctx.accounts.collection.key(),
+        asset: ctx.accounts.asset.key(),
+        fee_recipient: ctx.accounts.fee_recipient.key(),
+        amount,
+        fee_amount,
+        installments_paid,
+        remaining,
+        completed,
+        next_due_at,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
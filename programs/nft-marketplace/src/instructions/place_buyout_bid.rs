@@ -0,0 +1,128 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::{fees, FeeRounding, FractionVault, Market, VaultState, ErrorCode};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct PlaceBuyoutBid<'info> {
+    /// The bidder escrowing lamports
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+
+    /// The outbid bidder being refunded (omit for the opening bid)
+    /// CHECK: Validated against vault.bidder
+    #[account(mut)]
+    pub previous_bidder: Option<UncheckedAccount<'info>>,
+
+    /// The vault being bought out
+    #[account(
+        mut,
+        seeds = [b"vault", vault.asset.as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, FractionVault>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Market account (event sequence number)
+    #[account(
+        mut,
+        seeds = [b"market"],
+        bump = market.bump,
+    )]
+    pub market: Account<'info, Market>,
+}
+
+#[event]
+pub struct BuyoutBidPlaced {
+    pub seq: u64,
+    pub market: Pubkey,
+    pub vault: Pubkey,
+    pub asset: Pubkey,
+    pub bidder: Pubkey,
+    pub previous_bidder: Option<Pubkey>,
+    pub bid: u64,
+    pub auction_ends_at: i64,
+    pub timestamp: i64,
+}
+
+pub fn process_place_buyout_bid(ctx: Context<PlaceBuyoutBid>, bid: u64) -> Result<()> {
+    let vault = &ctx.accounts.vault;
+    let clock = Clock::get()?;
+
+    let previous_bidder = match vault.state {
+        // Opening bid starts the auction clock
+        VaultState::Active => {
+            require!(bid >= vault.reserve_price, ErrorCode::BidTooLow);
+            None
+        }
+        VaultState::Auction => {
+            require!(clock.unix_timestamp < vault.auction_ends_at, ErrorCode::AuctionEnded);
+            let increment = fees::compute_fee(
+                vault.winning_bid,
+                FractionVault::MIN_BID_INCREMENT_BPS,
+                FeeRounding::Ceil,
+                1,
+            )?;
+            let min_bid = vault.winning_bid
+                .checked_add(increment)
+                .ok_or(ErrorCode::MathOverflow)?;
+            require!(bid >= min_bid, ErrorCode::BidTooLow);
+
+            // Refund the outbid bidder from the vault escrow
+            let previous = ctx.accounts.previous_bidder.as_ref().ok_or(ErrorCode::InvalidVaultState)?;
+            require!(Some(previous.key()) == vault.bidder, ErrorCode::InvalidVaultState);
+            let refund = vault.winning_bid;
+            ctx.accounts.vault.sub_lamports(refund)?;
+            previous.add_lamports(refund)?;
+            Some(previous.key())
+        }
+        VaultState::Sold => return err!(ErrorCode::InvalidVaultState),
+    };
+
+    // Escrow the new bid in the vault PDA on top of its rent
+    transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.bidder.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+            },
+        ),
+        bid,
+    )?;
+
+    let vault = &mut ctx.accounts.vault;
+    if vault.state == VaultState::Active {
+        vault.state = VaultState::Auction;
+        vault.auction_ends_at = clock.unix_timestamp
+            .checked_add(vault.auction_duration)
+            .ok_or(ErrorCode::MathOverflow)?;
+    }
+    vault.bidder = Some(ctx.accounts.bidder.key());
+    vault.winning_bid = bid;
+    let auction_ends_at = vault.auction_ends_at;
+
+    msg!(
+        "Buyout bid: {} lamports on vault {} by {} (ends at {})",
+        bid,
+        vault.key(),
+        ctx.accounts.bidder.key(),
+        auction_ends_at
+    );
+
+    let seq = ctx.accounts.market.next_event_seq()?;
+    emit_cpi!(BuyoutBidPlaced {
+        seq,
+        market: ctx.accounts.market.key(),
+        vault: ctx.accounts.vault.key(),
+        asset: ctx.accounts.vault.asset,
+        bidder: ctx.accounts.bidder.key(),
+        previous_bidder,
+        bid,
+        auction_ends_at,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,130 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use mpl_core::instructions::TransferV1CpiBuilder;
+use crate::{InstallmentPurchase, Market, ErrorCode};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ReclaimInstallment<'info> {
+    /// The seller taking the NFT back
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    /// The defaulted buyer receiving the refund
+    /// CHECK: Validated against purchase.buyer
+    #[account(mut)]
+    pub buyer: UncheckedAccount<'info>,
+
+    /// The defaulted purchase
+    #[account(
+        mut,
+        seeds = [b"installment", purchase.listing.as_ref()],
+        bump = purchase.bump,
+        has_one = buyer,
+        has_one = seller,
+        has_one = asset,
+        has_one = collection,
+        close = buyer  // Buyer paid the purchase rent
+    )]
+    pub purchase: Account<'info, InstallmentPurchase>,
+
+    /// The escrowed NFT being returned
+    /// CHECK: Validated against purchase.asset
+    #[account(mut)]
+    pub asset: UncheckedAccount<'info>,
+
+    /// The collection the NFT belongs to
+    /// CHECK: Validated against purchase.collection
+    pub collection: UncheckedAccount<'info>,
+
+    /// MPL Core program for NFT transfer
+    /// CHECK: Pinned to the mpl-core program id
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Market account (event sequence number)
+    #[account(
+        mut,
+        seeds = [b"market"],
+        bump = market.bump,
+    )]
+    pub market: Account<'info, Market>,
+}
+
+#[event]
+pub struct InstallmentDefaulted {
+    pub seq: u64,
+    pub market: Pubkey,
+    pub purchase: Pubkey,
+    pub seller: Pubkey,
+    pub buyer: Pubkey,
+    pub collection: Pubkey,
+    pub asset: Pubkey,
+    pub paid: u64,
+    pub refund: u64,
+    pub timestamp: i64,
+}
+
+pub fn process_reclaim_installment(ctx: Context<ReclaimInstallment>) -> Result<()> {
+    let purchase = &ctx.accounts.purchase;
+    let clock = Clock::get()?;
+
+    require!(clock.unix_timestamp > purchase.next_due_at, ErrorCode::InstallmentNotOverdue);
+
+    // Seller keeps what was paid minus the configured refund
+    let paid = purchase.paid;
+    let refund = purchase.default_refund()?;
+    let kept = paid.checked_sub(refund).ok_or(ErrorCode::MathOverflow)?;
+    if refund > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.seller.to_account_info(),
+                    to: ctx.accounts.buyer.to_account_info(),
+                },
+            ),
+            refund,
+        )?;
+    }
+
+    // Return the NFT using the purchase PDA as authority
+    let listing_key = purchase.listing;
+    let seeds = &[b"installment", listing_key.as_ref(), &[purchase.bump]];
+    let signer = &[&seeds[..]];
+
+    TransferV1CpiBuilder::new(&ctx.accounts.mpl_core_program.to_account_info())
+        .asset(&ctx.accounts.asset.to_account_info())
+        .collection(Some(&ctx.accounts.collection.to_account_info()))
+        .payer(&ctx.accounts.seller.to_account_info())
+        .authority(Some(&ctx.accounts.purchase.to_account_info()))  // PDA is authority
+        .new_owner(&ctx.accounts.seller.to_account_info())
+        .invoke_signed(signer)?;  // Sign with PDA seeds
+
+    ctx.accounts.market.record_delisting();
+
+    msg!(
+        "Installment purchase defaulted: {} - seller kept {} lamports, refunded {}",
+        ctx.accounts.purchase.key(),
+        kept,
+        refund
+    );
+
+    let seq = ctx.accounts.market.next_event_seq()?;
+    emit_cpi!(InstallmentDefaulted {
+        seq,
+        market: ctx.accounts.market.key(),
+        purchase: ctx.accounts.purchase.key(),
+        seller: ctx.accounts.seller.key(),
+        buyer: ctx.accounts.buyer.key(),
+        collection: ctx.accounts.collection.key(),
+        asset: ctx.accounts.asset.key(),
+        paid,
+        refund,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use crate::{Market, Rental, ErrorCode};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ReclaimRental<'info> {
+    /// Anyone can end an expired rental
+    pub caller: Signer<'info>,
+
+    /// The rental whose period has ended
+    #[account(
+        mut,
+        seeds = [b"rental", rental.asset.as_ref()],
+        bump = rental.bump,
+    )]
+    pub rental: Account<'info, Rental>,
+
+    /// Market account (event sequence number)
+    #[account(
+        mut,
+        seeds = [b"market"],
+        bump = market.bump,
+    )]
+    pub market: Account<'info, Market>,
+}
+
+#[event]
+pub struct RentalReclaimed {
+    pub seq: u64,
+    pub market: Pubkey,
+    pub rental: Pubkey,
+    pub owner: Pubkey,
+    pub renter: Pubkey,
+    pub caller: Pubkey,
+    pub asset: Pubkey,
+    pub timestamp: i64,
+}
+
+pub fn process_reclaim_rental(ctx: Context<ReclaimRental>) -> Result<()> {
+    let rental = &mut ctx.accounts.rental;
+    let clock = Clock::get()?;
+
+    let renter = rental.renter.ok_or(ErrorCode::NotRented)?;
+    require!(clock.unix_timestamp >= rental.rented_until, ErrorCode::RentalNotExpired);
+
+    // Usage reverts to the owner, the asset stays listed for the next renter
+    rental.renter = None;
+    rental.rented_until = 0;
+
+    msg!(
+        "Rental reclaimed: {} - renter {} removed",
+        rental.key(),
+        renter
+    );
+
+    let seq = ctx.accounts.market.next_event_seq()?;
+    emit_cpi!(RentalReclaimed {
+        seq,
+        market: ctx.accounts.market.key(),
+        rental: ctx.accounts.rental.key(),
+        owner: ctx.accounts.rental.owner,
+        renter,
+        caller: ctx.accounts.caller.key(),
+        asset: ctx.accounts.rental.asset,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
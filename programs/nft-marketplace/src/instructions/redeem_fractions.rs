@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{burn, Burn, Mint, Token, TokenAccount};
+use crate::{FractionVault, Market, VaultState, ErrorCode};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RedeemFractions<'info> {
+    /// The fraction holder redeeming
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    /// The settled vault holding the proceeds
+    #[account(
+        mut,
+        seeds = [b"vault", vault.asset.as_ref()],
+        bump = vault.bump,
+        has_one = fraction_mint,
+    )]
+    pub vault: Account<'info, FractionVault>,
+
+    #[account(mut)]
+    pub fraction_mint: Account<'info, Mint>,
+
+    /// Holder's fraction token account, burned in full
+    #[account(
+        mut,
+        token::mint = fraction_mint,
+        token::authority = holder,
+    )]
+    pub holder_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+
+    /// Market account (event sequence number)
+    #[account(
+        mut,
+        seeds = [b"market"],
+        bump = market.bump,
+    )]
+    pub market: Account<'info, Market>,
+}
+
+#[event]
+pub struct FractionsRedeemed {
+    pub seq: u64,
+    pub market: Pubkey,
+    pub vault: Pubkey,
+    pub holder: Pubkey,
+    pub fractions: u64,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+pub fn process_redeem_fractions(ctx: Context<RedeemFractions>) -> Result<()> {
+    let vault = &ctx.accounts.vault;
+    require!(vault.state == VaultState::Sold, ErrorCode::InvalidVaultState);
+
+    let fractions = ctx.accounts.holder_token_account.amount;
+    require!(fractions > 0, ErrorCode::NothingToRedeem);
+
+    // Pro rata against the fractions still outstanding, so rounding dust goes to the last holder
+    let amount = vault.redemption_for(fractions, ctx.accounts.fraction_mint.supply)?;
+
+    burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.fraction_mint.to_account_info(),
+                from: ctx.accounts.holder_token_account.to_account_info(),
+                authority: ctx.accounts.holder.to_account_info(),
+            },
+        ),
+        fractions,
+    )?;
+
+    if amount > 0 {
+        ctx.accounts.vault.sub_lamports(amount)?;
+        ctx.accounts.holder.add_lamports(amount)?;
+    }
+    let vault = &mut ctx.accounts.vault;
+    vault.proceeds = vault.proceeds.checked_sub(amount).ok_or(ErrorCode::MathOverflow)?;
+
+    msg!(
+        "Fractions redeemed: {} fractions of vault {} for {} lamports",
+        fractions,
+        ctx.accounts.vault.key(),
+        amount
+    );
+
+    let seq = ctx.accounts.market.next_event_seq()?;
+    emit_cpi!(FractionsRedeemed {
+        seq,
+        market: ctx.accounts.market.key(),
+        vault: ctx.accounts.vault.key(),
+        holder: ctx.accounts.holder.key(),
+        fractions,
+        amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,101 @@
+use anchor_lang::prelude::*;
+use mpl_core::instructions::TransferV1CpiBuilder;
+use crate::asset_pairs::expected_asset_pairs;
+use crate::{BundleListing, Market};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RemoveBundleListing<'info> {
+    /// The seller delisting the bundle
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    /// The bundle being removed
+    #[account(
+        mut,
+        seeds = [b"bundle", bundle.seller.as_ref(), bundle.seed.to_le_bytes().as_ref()],
+        bump = bundle.bump,
+        has_one = seller,
+        close = seller  // Return rent to seller
+    )]
+    pub bundle: Account<'info, BundleListing>,
+
+    /// MPL Core program for NFT transfers
+    /// CHECK: Pinned to the mpl-core program id
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Market account (event sequence number)
+    #[account(
+        mut,
+        seeds = [b"market"],
+        bump = market.bump,
+    )]
+    pub market: Account<'info, Market>,
+    // remaining_accounts (after the event CPI accounts): (asset, collection)
+    // pairs for the bundled assets, in listing order
+}
+
+#[event]
+pub struct BundleCanceled {
+    pub seq: u64,
+    pub market: Pubkey,
+    pub bundle: Pubkey,
+    pub seller: Pubkey,
+    pub assets: Vec<Pubkey>,
+    pub price: u64,
+    pub timestamp: i64,
+}
+
+pub fn process_remove_bundle_listing<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RemoveBundleListing<'info>>,
+) -> Result<()> {
+    let bundle = &ctx.accounts.bundle;
+    let clock = Clock::get()?;
+
+    let pairs = expected_asset_pairs(ctx.remaining_accounts, &bundle.assets)?;
+
+    // Prepare bundle PDA signer seeds
+    let seed_bytes = bundle.seed.to_le_bytes();
+    let seeds = &[
+        b"bundle",
+        bundle.seller.as_ref(),
+        seed_bytes.as_ref(),
+        &[bundle.bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    // Return every NFT from bundle PDA to seller
+    for pair in &pairs {
+        TransferV1CpiBuilder::new(&ctx.accounts.mpl_core_program.to_account_info())
+            .asset(pair.asset)
+            .collection(pair.collection)
+            .payer(&ctx.accounts.seller.to_account_info())
+            .authority(Some(&ctx.accounts.bundle.to_account_info()))  // PDA is authority
+            .new_owner(&ctx.accounts.seller.to_account_info())
+            .invoke_signed(signer)?;  // Sign with PDA seeds
+    }
+
+    ctx.accounts.market.record_delisting();
+
+    msg!(
+        "Bundle removed: {} - {} assets returned to seller",
+        ctx.accounts.bundle.key(),
+        pairs.len()
+    );
+
+    let seq = ctx.accounts.market.next_event_seq()?;
+    emit_cpi!(BundleCanceled {
+        seq,
+        market: ctx.accounts.market.key(),
+        bundle: ctx.accounts.bundle.key(),
+        seller: ctx.accounts.seller.key(),
+        assets: ctx.accounts.bundle.assets.clone(),
+        price: ctx.accounts.bundle.price,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -1,8 +1,10 @@
 use anchor_lang::prelude::*;
-use mpl_core::instructions::TransferV1CpiBuilder;
-use crate::Listing;
+use mpl_core::instructions::{RemovePluginV1CpiBuilder, TransferV1CpiBuilder, UpdatePluginV1CpiBuilder};
+use mpl_core::types::{FreezeDelegate, Plugin, PluginType};
+use crate::{Listing, ListingMode, Market};
 use crate::ErrorCode;
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct RemoveListing<'info> {
     /// The seller canceling their listing
@@ -34,17 +36,31 @@ pub struct RemoveListing<'info> {
     pub asset: UncheckedAccount<'info>,
 
     /// MPL Core program for NFT transfer
-    /// CHECK: mpl-core program
+    /// CHECK: Pinned to the mpl-core program id
+    #[account(address = mpl_core::ID)]
     pub mpl_core_program: UncheckedAccount<'info>,
 
     pub system_program: Program<'info, System>,
+
+    /// Market account (event sequence number)
+    #[account(
+        mut,
+        seeds = [b"market"],
+        bump = market.bump,
+    )]
+    pub market: Account<'info, Market>,
 }
 
 #[event]
 pub struct ListingCanceled {
+    pub seq: u64,
+    pub market: Pubkey,
     pub listing: Pubkey,
     pub seller: Pubkey,
+    pub collection: Pubkey,
     pub asset: Pubkey,
+    pub price: u64,
+    pub mode: ListingMode,
     pub timestamp: i64,
 }
 
@@ -77,27 +93,61 @@ pub fn process_remove_listing(ctx: Context<RemoveListing>) -> Result<()> {
     ];
     let signer = &[&seeds[..]];
 
-    // Transfer NFT from listing PDA back to seller
-    TransferV1CpiBuilder::new(&ctx.accounts.mpl_core_program.to_account_info())
-        .asset(&ctx.accounts.asset.to_account_info())
-        .collection(Some(&ctx.accounts.collection.to_account_info()))
-        .payer(&ctx.accounts.seller.to_account_info())
-        .authority(Some(&ctx.accounts.listing.to_account_info()))  // PDA is authority
-        .new_owner(&ctx.accounts.seller.to_account_info())
-        .invoke_signed(signer)?;  // Sign with PDA seeds
+    match listing.mode {
+        ListingMode::Escrow => {
+            // Transfer NFT from listing PDA back to seller
+            TransferV1CpiBuilder::new(&ctx.accounts.mpl_core_program.to_account_info())
+                .asset(&ctx.accounts.asset.to_account_info())
+                .collection(Some(&ctx.accounts.collection.to_account_info()))
+                .payer(&ctx.accounts.seller.to_account_info())
+                .authority(Some(&ctx.accounts.listing.to_account_info()))  // PDA is authority
+                .new_owner(&ctx.accounts.seller.to_account_info())
+                .invoke_signed(signer)?;  // Sign with PDA seeds
+        }
+        ListingMode::Delegated => {
+            // Thaw the NFT (PDA is freeze delegate), then seller as owner drops both delegates
+            UpdatePluginV1CpiBuilder::new(&ctx.accounts.mpl_core_program.to_account_info())
+                .asset(&ctx.accounts.asset.to_account_info())
+                .collection(Some(&ctx.accounts.collection.to_account_info()))
+                .payer(&ctx.accounts.seller.to_account_info())
+                .authority(Some(&ctx.accounts.listing.to_account_info()))  // PDA is authority
+                .system_program(&ctx.accounts.system_program.to_account_info())
+                .plugin(Plugin::FreezeDelegate(FreezeDelegate { frozen: false }))
+                .invoke_signed(signer)?;  // Sign with PDA seeds
+
+            for plugin_type in [PluginType::FreezeDelegate, PluginType::TransferDelegate] {
+                RemovePluginV1CpiBuilder::new(&ctx.accounts.mpl_core_program.to_account_info())
+                    .asset(&ctx.accounts.asset.to_account_info())
+                    .collection(Some(&ctx.accounts.collection.to_account_info()))
+                    .payer(&ctx.accounts.seller.to_account_info())
+                    .authority(Some(&ctx.accounts.seller.to_account_info()))
+                    .system_program(&ctx.accounts.system_program.to_account_info())
+                    .plugin_type(plugin_type)
+                    .invoke()?;
+            }
+        }
+    }
 
     msg!(
-        "Listing canceled: {} - NFT {} returned to seller {}",
+        "Listing canceled: {} - NFT {} released to seller {}",
         ctx.accounts.listing.key(),
         ctx.accounts.asset.key(),
         ctx.accounts.seller.key()
     );
 
+    ctx.accounts.market.record_delisting();
+
     // Emit cancellation event
-    emit!(ListingCanceled {
+    let seq = ctx.accounts.market.next_event_seq()?;
+    emit_cpi!(ListingCanceled {
+        seq,
+        market: ctx.accounts.market.key(),
         listing: ctx.accounts.listing.key(),
         seller: ctx.accounts.seller.key(),
+        collection: ctx.accounts.collection.key(),
         asset: ctx.accounts.asset.key(),
+        price: ctx.accounts.listing.price,
+        mode: ctx.accounts.listing.mode,
         timestamp: clock.unix_timestamp,
     });
 
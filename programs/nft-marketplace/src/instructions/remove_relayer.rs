@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use crate::{Market, RelayerApproval};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RemoveRelayer<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"market"],
+        bump = market.bump,
+        has_one = admin
+    )]
+    pub market: Account<'info, Market>,
+
+    /// Allowlist entry being revoked
+    #[account(
+        mut,
+        seeds = [b"relayer", market.key().as_ref(), relayer_approval.relayer.as_ref()],
+        bump = relayer_approval.bump,
+        close = admin  // Return rent to admin
+    )]
+    pub relayer_approval: Account<'info, RelayerApproval>,
+}
+
+#[event]
+pub struct RelayerRemoved {
+    pub seq: u64,
+    pub market: Pubkey,
+    pub relayer: Pubkey,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+pub fn process_remove_relayer(ctx: Context<RemoveRelayer>) -> Result<()> {
+    msg!("Relayer {} may no longer sponsor purchases", ctx.accounts.relayer_approval.relayer);
+
+    let seq = ctx.accounts.market.next_event_seq()?;
+    emit_cpi!(RelayerRemoved {
+        seq,
+        market: ctx.accounts.market.key(),
+        relayer: ctx.accounts.relayer_approval.relayer,
+        admin: ctx.accounts.admin.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+use mpl_core::instructions::TransferV1CpiBuilder;
+use crate::{Market, Rental, ErrorCode};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RemoveRental<'info> {
+    /// The owner taking their NFT back
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// The rental being removed
+    #[account(
+        mut,
+        seeds = [b"rental", rental.asset.as_ref()],
+        bump = rental.bump,
+        has_one = owner,
+        has_one = asset,
+        has_one = collection,
+        close = owner  // Return rent to owner
+    )]
+    pub rental: Account<'info, Rental>,
+
+    /// The escrowed NFT being returned
+    /// CHECK: Validated against rental.asset
+    #[account(mut)]
+    pub asset: UncheckedAccount<'info>,
+
+    /// The collection the NFT belongs to
+    /// CHECK: Validated against rental.collection
+    pub collection: UncheckedAccount<'info>,
+
+    /// MPL Core program for NFT transfer
+    /// CHECK: Pinned to the mpl-core program id
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Market account (event sequence number)
+    #[account(
+        mut,
+        seeds = [b"market"],
+        bump = market.bump,
+    )]
+    pub market: Account<'info, Market>,
+}
+
+#[event]
+pub struct RentalRemoved {
+    pub seq: u64,
+    pub market: Pubkey,
+    pub rental: Pubkey,
+    pub owner: Pubkey,
+    pub collection: Pubkey,
+    pub asset: Pubkey,
+    pub timestamp: i64,
+}
+
+pub fn process_remove_rental(ctx: Context<RemoveRental>) -> Result<()> {
+    let rental = &ctx.accounts.rental;
+    let clock = Clock::get()?;
+
+    // An expired rental doesn't need to be reclaimed first
+    require!(!rental.is_rented(clock.unix_timestamp), ErrorCode::AssetRented);
+
+    // Return the NFT using the rental PDA as authority
+    let asset_key = rental.asset;
+    let seeds = &[b"rental", asset_key.as_ref(), &[rental.bump]];
+    let signer = &[&seeds[..]];
+
+    TransferV1CpiBuilder::new(&ctx.accounts.mpl_core_program.to_account_info())
+        .asset(&ctx.accounts.asset.to_account_info())
+        .collection(Some(&ctx.accounts.collection.to_account_info()))
+        .payer(&ctx.accounts.owner.to_account_info())
+        .authority(Some(&ctx.accounts.rental.to_account_info()))  // PDA is authority
+        .new_owner(&ctx.accounts.owner.to_account_info())
+        .invoke_signed(signer)?;  // Sign with PDA seeds
+
+    msg!(
+        "Rental removed: {} - asset {} returned to owner",
+        ctx.accounts.rental.key(),
+        asset_key
+    );
+
+    let seq = ctx.accounts.market.next_event_seq()?;
+    emit_cpi!(RentalRemoved {
+        seq,
+        market: ctx.accounts.market.key(),
+        rental: ctx.accounts.rental.key(),
+        owner: ctx.accounts.owner.key(),
+        collection: ctx.accounts.collection.key(),
+        asset: asset_key,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
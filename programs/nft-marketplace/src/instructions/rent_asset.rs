@@ -0,0 +1,147 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::{fees, Market, Rental, ErrorCode};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RentAsset<'info> {
+    /// The renter paying for the rental period
+    #[account(mut)]
+    pub renter: Signer<'info>,
+
+    /// The asset owner receiving the rent
+    /// CHECK: Validated against rental.owner
+    #[account(mut)]
+    pub owner: UncheckedAccount<'info>,
+
+    /// The rental being booked
+    #[account(
+        mut,
+        seeds = [b"rental", rental.asset.as_ref()],
+        bump = rental.bump,
+        has_one = owner,
+    )]
+    pub rental: Account<'info, Rental>,
+
+    /// Fee recipient receiving marketplace fee
+    /// CHECK: Validated against market.fee_recipient
+    #[account(mut)]
+    pub fee_recipient: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Market account for fee configuration and event sequence
+    #[account(
+        mut,
+        seeds = [b"market"],
+        bump = market.bump,
+    )]
+    pub market: Account<'info, Market>,
+}
+
+#[event]
+pub struct AssetRented {
+    pub seq: u64,
+    pub market: Pubkey,
+    pub rental: Pubkey,
+    pub owner: Pubkey,
+    pub renter: Pubkey,
+    pub collection: Pubkey,
+    pub asset: Pubkey,
+    pub fee_recipient: Pubkey,
+    pub days: u16,
+    pub total_price: u64,
+    pub fee_amount: u64,
+    pub rented_until: i64,
+    pub timestamp: i64,
+}
+
+pub fn process_rent_asset(ctx: Context<RentAsset>, days: u16) -> Result<()> {
+    let rental = &ctx.accounts.rental;
+    let market = &ctx.accounts.market;
+    let renter_key = ctx.accounts.renter.key();
+    let clock = Clock::get()?;
+
+    require!(
+        market.fee_recipient == ctx.accounts.fee_recipient.key(),
+        ErrorCode::InvalidFeeRecipient
+    );
+    require_keys_neq!(renter_key, rental.owner, ErrorCode::SelfTrade);
+    require!(!rental.is_rented(clock.unix_timestamp), ErrorCode::AssetRented);
+    require!(days > 0 && days <= rental.max_days, ErrorCode::InvalidRentalTerms);
+
+    // Whole period is paid upfront, split between owner and marketplace
+    let total_price = rental.daily_price
+        .checked_mul(days as u64)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let fee_amount = fees::compute_fee_from_proceeds(
+        total_price,
+        market.taker_fee_bps,
+        market.fee_rounding,
+        market.min_fee,
+    )?;
+    let owner_amount = total_price
+        .checked_sub(fee_amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let rented_until = clock.unix_timestamp
+        .checked_add(days as i64 * Rental::SECONDS_PER_DAY)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    if owner_amount > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.renter.to_account_info(),
+                    to: ctx.accounts.owner.to_account_info(),
+                },
+            ),
+            owner_amount,
+        )?;
+    }
+
+    if fee_amount > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.renter.to_account_info(),
+                    to: ctx.accounts.fee_recipient.to_account_info(),
+                },
+            ),
+            fee_amount,
+        )?;
+    }
+
+    let rental = &mut ctx.accounts.rental;
+    rental.renter = Some(renter_key);
+    rental.rented_until = rented_until;
+
+    msg!(
+        "Asset rented: {} by {} for {} days ({} lamports) until {}",
+        rental.asset,
+        renter_key,
+        days,
+        total_price,
+        rented_until
+    );
+
+    let seq = ctx.accounts.market.next_event_seq()?;
+    emit_cpi!(AssetRented {
+        seq,
+        market: ctx.accounts.market.key(),
+        rental: ctx.accounts.rental.key(),
+        owner: ctx.accounts.owner.key(),
+        renter: renter_key,
+        collection: ctx.accounts.rental.collection,
+        asset: ctx.accounts.rental.asset,
+        fee_recipient: ctx.accounts.fee_recipient.key(),
+        days,
+        total_price,
+        fee_amount,
+        rented_until,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
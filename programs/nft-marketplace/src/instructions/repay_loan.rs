@@ -0,0 +1,129 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use mpl_core::instructions::TransferV1CpiBuilder;
+use crate::{Loan, Market, ErrorCode};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RepayLoan<'info> {
+    /// The borrower repaying and reclaiming their NFT
+    #[account(mut)]
+    pub borrower: Signer<'info>,
+
+    /// The lender receiving principal + interest
+    /// CHECK: Validated against loan.lender
+    #[account(mut)]
+    pub lender: UncheckedAccount<'info>,
+
+    /// The loan being repaid
+    #[account(
+        mut,
+        seeds = [b"loan", loan.asset.as_ref()],
+        bump = loan.bump,
+        has_one = borrower,
+        has_one = lender,
+        has_one = asset,
+        has_one = collection,
+        close = borrower  // Borrower paid the loan rent
+    )]
+    pub loan: Account<'info, Loan>,
+
+    /// The collateral being returned
+    /// CHECK: Validated against loan.asset
+    #[account(mut)]
+    pub asset: UncheckedAccount<'info>,
+
+    /// The collection the collateral belongs to
+    /// CHECK: Validated against loan.collection
+    pub collection: UncheckedAccount<'info>,
+
+    /// MPL Core program for NFT transfer
+    /// CHECK: Pinned to the mpl-core program id
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Market account (event sequence number)
+    #[account(
+        mut,
+        seeds = [b"market"],
+        bump = market.bump,
+    )]
+    pub market: Account<'info, Market>,
+}
+
+#[event]
+pub struct LoanRepaid {
+    pub seq: u64,
+    pub market: Pubkey,
+    pub loan: Pubkey,
+    pub lender: Pubkey,
+    pub borrower: Pubkey,
+    pub collection: Pubkey,
+    pub asset: Pubkey,
+    pub principal: u64,
+    pub interest: u64,
+    pub timestamp: i64,
+}
+
+pub fn process_repay_loan(ctx: Context<RepayLoan>) -> Result<()> {
+    let loan = &ctx.accounts.loan;
+    let clock = Clock::get()?;
+
+    require!(clock.unix_timestamp <= loan.due_at, ErrorCode::LoanExpired);
+
+    let principal = loan.principal;
+    let interest = loan.interest_due(clock.unix_timestamp)?;
+    let total_amount = principal
+        .checked_add(interest)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    // Pay back principal + interest
+    transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.borrower.to_account_info(),
+                to: ctx.accounts.lender.to_account_info(),
+            },
+        ),
+        total_amount,
+    )?;
+
+    // Return the collateral using the loan PDA as authority
+    let asset_key = loan.asset;
+    let seeds = &[b"loan", asset_key.as_ref(), &[loan.bump]];
+    let signer = &[&seeds[..]];
+
+    TransferV1CpiBuilder::new(&ctx.accounts.mpl_core_program.to_account_info())
+        .asset(&ctx.accounts.asset.to_account_info())
+        .collection(Some(&ctx.accounts.collection.to_account_info()))
+        .payer(&ctx.accounts.borrower.to_account_info())
+        .authority(Some(&ctx.accounts.loan.to_account_info()))  // PDA is authority
+        .new_owner(&ctx.accounts.borrower.to_account_info())
+        .invoke_signed(signer)?;  // Sign with PDA seeds
+
+    msg!(
+        "Loan repaid: {} - principal {} + interest {} lamports",
+        ctx.accounts.loan.key(),
+        principal,
+        interest
+    );
+
+    let seq = ctx.accounts.market.next_event_seq()?;
+    emit_cpi!(LoanRepaid {
+        seq,
+        market: ctx.accounts.market.key(),
+        loan: ctx.accounts.loan.key(),
+        lender: ctx.accounts.lender.key(),
+        borrower: ctx.accounts.borrower.key(),
+        collection: ctx.accounts.collection.key(),
+        asset: asset_key,
+        principal,
+        interest,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,149 @@
+use anchor_lang::prelude::*;
+use mpl_core::accounts::BaseAssetV1;
+use mpl_core::instructions::TransferV1CpiBuilder;
+use mpl_core::types::UpdateAuthority;
+use crate::{fees, Market, Pool, ErrorCode};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SellToPool<'info> {
+    /// The trader selling an NFT
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    /// The pool buying the NFT
+    #[account(
+        mut,
+        seeds = [b"pool", pool.owner.as_ref(), pool.seed.to_le_bytes().as_ref()],
+        bump = pool.bump,
+        has_one = collection,
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// The NFT being sold
+    /// CHECK: Checked to belong to pool.collection
+    #[account(mut)]
+    pub asset: UncheckedAccount<'info>,
+
+    /// The collection the NFT belongs to
+    /// CHECK: Validated against pool.collection
+    pub collection: UncheckedAccount<'info>,
+
+    /// Fee recipient receiving marketplace fee
+    /// CHECK: Validated against market.fee_recipient
+    #[account(mut)]
+    pub fee_recipient: UncheckedAccount<'info>,
+
+    /// MPL Core program for NFT transfer
+    /// CHECK: Pinned to the mpl-core program id
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Market account for fee configuration and event sequence
+    #[account(
+        mut,
+        seeds = [b"market"],
+        bump = market.bump,
+    )]
+    pub market: Account<'info, Market>,
+}
+
+#[event]
+pub struct PoolNftSold {
+    pub seq: u64,
+    pub market: Pubkey,
+    pub pool: Pubkey,
+    pub seller: Pubkey,
+    pub asset: Pubkey,
+    pub fee_recipient: Pubkey,
+    pub price: u64,
+    pub fee_amount: u64,       // Taker fee deducted from seller proceeds
+    pub spot_price: u64,
+    pub timestamp: i64,
+}
+
+pub fn process_sell_to_pool(ctx: Context<SellToPool>, min_price: u64) -> Result<()> {
+    let pool = &ctx.accounts.pool;
+    let market = &ctx.accounts.market;
+    let clock = Clock::get()?;
+
+    require!(pool.owner != ctx.accounts.seller.key(), ErrorCode::SelfTrade);
+    require!(
+        market.fee_recipient == ctx.accounts.fee_recipient.key(),
+        ErrorCode::InvalidFeeRecipient
+    );
+
+    // Asset must belong to the pool's collection
+    require_keys_eq!(*ctx.accounts.asset.owner, mpl_core::ID, ErrorCode::InvalidAsset);
+    let asset = BaseAssetV1::from_bytes(&ctx.accounts.asset.data.borrow())
+        .map_err(|_| ErrorCode::InvalidAsset)?;
+    require!(
+        asset.update_authority == UpdateAuthority::Collection(pool.collection),
+        ErrorCode::InvalidAsset
+    );
+
+    // Pool pays one step down the curve less its spread, the curve then moves down
+    let price = pool.bid_price()?;
+    require!(price >= min_price, ErrorCode::SlippageExceeded);
+    require!(price <= pool.sol_balance, ErrorCode::InsufficientFunds);
+    let next_spot_price = pool.price_after_sell()?;
+
+    // Taker fee comes out of the seller's proceeds
+    let fee_amount = fees::compute_fee_from_proceeds(
+        price,
+        market.taker_fee_bps,
+        market.fee_rounding,
+        market.min_fee,
+    )?;
+    let seller_amount = price
+        .checked_sub(fee_amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    // Transfer NFT from seller into the pool PDA (escrow)
+    TransferV1CpiBuilder::new(&ctx.accounts.mpl_core_program.to_account_info())
+        .asset(&ctx.accounts.asset.to_account_info())
+        .collection(Some(&ctx.accounts.collection.to_account_info()))
+        .payer(&ctx.accounts.seller.to_account_info())
+        .authority(Some(&ctx.accounts.seller.to_account_info()))
+        .new_owner(&ctx.accounts.pool.to_account_info())
+        .invoke()?;
+
+    // Pay out of the pool (pool PDA is program owned)
+    ctx.accounts.pool.sub_lamports(price)?;
+    ctx.accounts.seller.add_lamports(seller_amount)?;
+    ctx.accounts.fee_recipient.add_lamports(fee_amount)?;
+
+    let pool = &mut ctx.accounts.pool;
+    pool.spot_price = next_spot_price;
+    pool.sol_balance = pool.sol_balance.checked_sub(price).ok_or(ErrorCode::MathOverflow)?;
+    pool.nft_count = pool.nft_count.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+
+    ctx.accounts.market.record_volume(price, fee_amount)?;
+
+    msg!(
+        "Pool purchase: asset {} sold to pool {} by {} for {} lamports (next spot price {})",
+        ctx.accounts.asset.key(),
+        ctx.accounts.pool.key(),
+        ctx.accounts.seller.key(),
+        price,
+        next_spot_price
+    );
+
+    let seq = ctx.accounts.market.next_event_seq()?;
+    emit_cpi!(PoolNftSold {
+        seq,
+        market: ctx.accounts.market.key(),
+        pool: ctx.accounts.pool.key(),
+        seller: ctx.accounts.seller.key(),
+        asset: ctx.accounts.asset.key(),
+        fee_recipient: ctx.accounts.fee_recipient.key(),
+        price,
+        fee_amount,
+        spot_price: next_spot_price,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+use crate::{CollectionConfig, Market, ErrorCode};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetCollectionPriceBounds<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"market"],
+        bump = market.bump,
+        has_one = admin
+    )]
+    pub market: Account<'info, Market>,
+
+    /// The collection the bounds apply to
+    /// CHECK: Only used as a PDA seed
+    pub collection: UncheckedAccount<'info>,
+
+    /// Per-collection overrides - PDA with seeds [b"collection_config", collection]
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = CollectionConfig::DISCRIMINATOR.len() + CollectionConfig::INIT_SPACE,
+        seeds = [b"collection_config", collection.key().as_ref()],
+        bump
+    )]
+    pub collection_config: Account<'info, CollectionConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct CollectionPriceBoundsUpdated {
+    pub seq: u64,
+    pub market: Pubkey,
+    pub collection: Pubkey,
+    pub admin: Pubkey,
+    pub min_price: u64,
+    pub max_price: u64,
+    pub timestamp: i64,
+}
+
+pub fn process_set_collection_price_bounds(
+    ctx: Context<SetCollectionPriceBounds>,
+    min_price: u64,
+    max_price: u64,
+) -> Result<()> {
+    require!(min_price <= max_price, ErrorCode::InvalidPriceBounds);
+
+    let collection_config = &mut ctx.accounts.collection_config;
+    collection_config.collection = ctx.accounts.collection.key();
+    collection_config.min_price = min_price;
+    collection_config.max_price = max_price;
+    collection_config.bump = ctx.bumps.collection_config;
+
+    msg!(
+        "Collection {} price bounds: {} - {} lamports",
+        ctx.accounts.collection.key(),
+        min_price,
+        max_price
+    );
+
+    let seq = ctx.accounts.market.next_event_seq()?;
+    emit_cpi!(CollectionPriceBoundsUpdated {
+        seq,
+        market: ctx.accounts.market.key(),
+        collection: ctx.accounts.collection.key(),
+        admin: ctx.accounts.admin.key(),
+        min_price,
+        max_price,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
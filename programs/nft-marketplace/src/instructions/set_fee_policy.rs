@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::{FeeRounding, UpdateMarket};
+
+#[event]
+pub struct FeePolicyUpdated {
+    pub seq: u64,
+    pub market: Pubkey,
+    pub admin: Pubkey,
+    pub fee_rounding: FeeRounding,
+    pub min_fee: u64,
+    pub timestamp: i64,
+}
+
+pub fn process_set_fee_policy(
+    ctx: Context<UpdateMarket>,
+    fee_rounding: FeeRounding,
+    min_fee: u64,
+) -> Result<()> {
+    // min_fee may exceed small prices: fees taken from proceeds are capped at the amount
+    let market = &mut ctx.accounts.market;
+    market.fee_rounding = fee_rounding;
+    market.min_fee = min_fee;
+
+    msg!("Market fee policy: {:?} rounding, min fee {} lamports", fee_rounding, min_fee);
+
+    let seq = ctx.accounts.market.next_event_seq()?;
+    emit_cpi!(FeePolicyUpdated {
+        seq,
+        market: ctx.accounts.market.key(),
+        admin: ctx.accounts.admin.key(),
+        fee_rounding,
+        min_fee,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
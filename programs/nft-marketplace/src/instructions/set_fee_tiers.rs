@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use crate::{FeeTier, Market, UpdateMarket, ErrorCode};
+
+#[event]
+pub struct FeeTiersUpdated {
+    pub seq: u64,
+    pub market: Pubkey,
+    pub admin: Pubkey,
+    pub fee_tiers: Vec<FeeTier>,
+    pub timestamp: i64,
+}
+
+pub fn process_set_fee_tiers(ctx: Context<UpdateMarket>, fee_tiers: Vec<FeeTier>) -> Result<()> {
+    require!(fee_tiers.len() <= Market::MAX_FEE_TIERS, ErrorCode::InvalidFeeTiers);
+    require!(
+        fee_tiers.windows(2).all(|pair| pair[0].volume_threshold < pair[1].volume_threshold),
+        ErrorCode::InvalidFeeTiers
+    );
+    for tier in fee_tiers.iter() {
+        require!(tier.taker_fee_bps <= 10000, ErrorCode::FeeTooHigh);
+        require!(tier.maker_fee_bps <= 10000, ErrorCode::FeeTooHigh);
+    }
+
+    let market = &mut ctx.accounts.market;
+    market.fee_tiers = fee_tiers.clone();
+
+    msg!("Market fee tiers: {} tiers", fee_tiers.len());
+
+    let seq = ctx.accounts.market.next_event_seq()?;
+    emit_cpi!(FeeTiersUpdated {
+        seq,
+        market: ctx.accounts.market.key(),
+        admin: ctx.accounts.admin.key(),
+        fee_tiers,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
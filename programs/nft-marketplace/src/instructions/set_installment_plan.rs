@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+use crate::{fees, InstallmentPlan, Listing, ListingMode, Market, ErrorCode};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetInstallmentPlan<'info> {
+    /// The seller configuring their listing
+    pub seller: Signer<'info>,
+
+    /// The listing offering the plan
+    #[account(
+        mut,
+        seeds = [
+            b"listing",
+            listing.seller.as_ref(),
+            listing.collection.as_ref(),
+            listing.asset.as_ref()
+        ],
+        bump = listing.bump,
+        has_one = seller,
+    )]
+    pub listing: Account<'info, Listing>,
+
+    /// Market account (event sequence number)
+    #[account(
+        mut,
+        seeds = [b"market"],
+        bump = market.bump,
+    )]
+    pub market: Account<'info, Market>,
+}
+
+#[event]
+pub struct InstallmentPlanSet {
+    pub seq: u64,
+    pub market: Pubkey,
+    pub listing: Pubkey,
+    pub seller: Pubkey,
+    pub asset: Pubkey,
+    pub plan: Option<InstallmentPlan>,
+    pub timestamp: i64,
+}
+
+pub fn process_set_installment_plan(ctx: Context<SetInstallmentPlan>, plan: Option<InstallmentPlan>) -> Result<()> {
+    let listing = &mut ctx.accounts.listing;
+    require!(listing.is_active, ErrorCode::ListingNotActive);
+
+    if let Some(plan) = plan {
+        // NFT must already sit in the listing PDA so it can move to the purchase escrow
+        require!(listing.mode == ListingMode::Escrow, ErrorCode::InvalidInstallmentPlan);
+        // Installments are scheduled against a fixed lamport price
+        require!(listing.usd_pricing.is_none(), ErrorCode::InvalidInstallmentPlan);
+        require!(
+            plan.installment_count > 0
+                && plan.interval > 0
+                && plan.refund_bps as u128 <= fees::BPS_DENOMINATOR
+                && plan.down_payment < listing.price,
+            ErrorCode::InvalidInstallmentPlan
+        );
+        // Every installment has to be at least one lamport
+        let financed = listing.price
+            .checked_sub(plan.down_payment)
+            .ok_or(ErrorCode::MathOverflow)?;
+        require!(financed >= plan.installment_count as u64, ErrorCode::InvalidInstallmentPlan);
+    }
+
+    listing.installment_plan = plan;
+
+    msg!(
+        "Installment plan {} on listing {}",
+        if plan.is_some() { "set" } else { "removed" },
+        listing.key()
+    );
+
+    let seq = ctx.accounts.market.next_event_seq()?;
+    emit_cpi!(InstallmentPlanSet {
+        seq,
+        market: ctx.accounts.market.key(),
+        listing: ctx.accounts.listing.key(),
+        seller: ctx.accounts.seller.key(),
+        asset: ctx.accounts.listing.asset,
+        plan,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::UpdateMarket;
+
+#[event]
+pub struct MarketAllowlistUpdated {
+    pub seq: u64,
+    pub market: Pubkey,
+    pub admin: Pubkey,
+    pub allowlist_root: Option<[u8; 32]>,
+    pub early_access_ends_at: i64,
+    pub timestamp: i64,
+}
+
+pub fn process_set_market_allowlist(
+    ctx: Context<UpdateMarket>,
+    allowlist_root: Option<[u8; 32]>,
+    early_access_ends_at: i64,
+) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    market.allowlist_root = allowlist_root;
+    market.early_access_ends_at = early_access_ends_at;
+
+    let seq = ctx.accounts.market.next_event_seq()?;
+    emit_cpi!(MarketAllowlistUpdated {
+        seq,
+        market: ctx.accounts.market.key(),
+        admin: ctx.accounts.admin.key(),
+        allowlist_root,
+        early_access_ends_at,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
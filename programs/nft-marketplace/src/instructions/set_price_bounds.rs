@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+use crate::{UpdateMarket, ErrorCode};
+
+#[event]
+pub struct PriceBoundsUpdated {
+    pub seq: u64,
+    pub market: Pubkey,
+    pub admin: Pubkey,
+    pub min_price: u64,
+    pub max_price: u64,
+    pub timestamp: i64,
+}
+
+pub fn process_set_price_bounds(
+    ctx: Context<UpdateMarket>,
+    min_price: u64,
+    max_price: u64,
+) -> Result<()> {
+    require!(min_price <= max_price, ErrorCode::InvalidPriceBounds);
+
+    let market = &mut ctx.accounts.market;
+    market.min_price = min_price;
+    market.max_price = max_price;
+
+    msg!("Market price bounds: {} - {} lamports", min_price, max_price);
+
+    let seq = ctx.accounts.market.next_event_seq()?;
+    emit_cpi!(PriceBoundsUpdated {
+        seq,
+        market: ctx.accounts.market.key(),
+        admin: ctx.accounts.admin.key(),
+        min_price,
+        max_price,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+use crate::UpdateMarket;
+
+#[event]
+pub struct PriceFeedUpdated {
+    pub seq: u64,
+    pub market: Pubkey,
+    pub admin: Pubkey,
+    pub sol_usd_feed: Pubkey,
+    pub timestamp: i64,
+}
+
+pub fn process_set_price_feed(ctx: Context<UpdateMarket>, sol_usd_feed: Pubkey) -> Result<()> {
+    ctx.accounts.market.sol_usd_feed = sol_usd_feed;
+
+    msg!("Market SOL/USD feed: {}", sol_usd_feed);
+
+    let seq = ctx.accounts.market.next_event_seq()?;
+    emit_cpi!(PriceFeedUpdated {
+        seq,
+        market: ctx.accounts.market.key(),
+        admin: ctx.accounts.admin.key(),
+        sol_usd_feed,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use crate::{Market, RewardConfig, ErrorCode};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetRewardConfig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"market"],
+        bump = market.bump,
+        has_one = admin
+    )]
+    pub market: Account<'info, Market>,
+
+    /// Reward program settings - PDA with seeds [b"reward_config", market]
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = RewardConfig::DISCRIMINATOR.len() + RewardConfig::INIT_SPACE,
+        seeds = [b"reward_config", market.key().as_ref()],
+        bump
+    )]
+    pub reward_config: Account<'info, RewardConfig>,
+
+    /// SPL mint paid out as rewards
+    pub reward_mint: Account<'info, Mint>,
+
+    /// Vault funded by the admin when the program is not the mint authority
+    #[account(
+        init_if_needed,
+        payer = admin,
+        associated_token::mint = reward_mint,
+        associated_token::authority = reward_config,
+    )]
+    pub reward_vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct RewardConfigUpdated {
+    pub seq: u64,
+    pub market: Pubkey,
+    pub admin: Pubkey,
+    pub reward_mint: Pubkey,
+    pub emission_rate: u64,
+    pub enabled: bool,
+    pub timestamp: i64,
+}
+
+pub fn process_set_reward_config(
+    ctx: Context<SetRewardConfig>,
+    emission_rate: u64,
+    enabled: bool,
+) -> Result<()> {
+    let reward_config = &mut ctx.accounts.reward_config;
+
+    // Accrued balances are denominated in the original mint
+    require!(
+        reward_config.reward_mint == Pubkey::default()
+            || reward_config.reward_mint == ctx.accounts.reward_mint.key(),
+        ErrorCode::InvalidRewardMint
+    );
+
+    reward_config.market = ctx.accounts.market.key();
+    reward_config.reward_mint = ctx.accounts.reward_mint.key();
+    reward_config.emission_rate = emission_rate;
+    reward_config.enabled = enabled;
+    reward_config.bump = ctx.bumps.reward_config;
+
+    msg!(
+        "Rewards: mint {} at {} per {} lamports (enabled: {})",
+        ctx.accounts.reward_mint.key(),
+        emission_rate,
+        RewardConfig::RATE_SCALE,
+        enabled
+    );
+
+    let seq = ctx.accounts.market.next_event_seq()?;
+    emit_cpi!(RewardConfigUpdated {
+        seq,
+        market: ctx.accounts.market.key(),
+        admin: ctx.accounts.admin.key(),
+        reward_mint: ctx.accounts.reward_mint.key(),
+        emission_rate,
+        enabled,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+use crate::{oracle, Listing, Market, UsdPricing, ErrorCode};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetUsdPrice<'info> {
+    /// The seller configuring their listing
+    pub seller: Signer<'info>,
+
+    /// The listing being priced
+    #[account(
+        mut,
+        seeds = [
+            b"listing",
+            listing.seller.as_ref(),
+            listing.collection.as_ref(),
+            listing.asset.as_ref()
+        ],
+        bump = listing.bump,
+        has_one = seller,
+    )]
+    pub listing: Account<'info, Listing>,
+
+    /// Market account (event sequence number)
+    #[account(
+        mut,
+        seeds = [b"market"],
+        bump = market.bump,
+    )]
+    pub market: Account<'info, Market>,
+}
+
+#[event]
+pub struct ListingUsdPriceSet {
+    pub seq: u64,
+    pub market: Pubkey,
+    pub listing: Pubkey,
+    pub seller: Pubkey,
+    pub asset: Pubkey,
+    pub usd_pricing: Option<UsdPricing>,
+    pub floor_price: u64,
+    pub timestamp: i64,
+}
+
+pub fn process_set_usd_price(ctx: Context<SetUsdPrice>, usd_pricing: Option<UsdPricing>) -> Result<()> {
+    let listing = &mut ctx.accounts.listing;
+    require!(listing.is_active, ErrorCode::ListingNotActive);
+
+    if let Some(pricing) = usd_pricing {
+        require!(pricing.usd_price > 0, ErrorCode::InvalidPriceFeed);
+        // USD pricing is only available once the admin has pinned a SOL/USD feed
+        require_keys_neq!(ctx.accounts.market.sol_usd_feed, Pubkey::default(), ErrorCode::InvalidPriceFeed);
+        // Installments are scheduled against a fixed lamport price
+        require!(listing.installment_plan.is_none(), ErrorCode::InvalidInstallmentPlan);
+    }
+
+    listing.usd_pricing = usd_pricing;
+
+    msg!(
+        "Listing {} priced {} (floor {} lamports)",
+        listing.key(),
+        match usd_pricing {
+            Some(pricing) => format!("at {} USD/10^{}", pricing.usd_price, oracle::USD_DECIMALS),
+            None => "in lamports".to_string(),
+        },
+        listing.price
+    );
+
+    let seq = ctx.accounts.market.next_event_seq()?;
+    emit_cpi!(ListingUsdPriceSet {
+        seq,
+        market: ctx.accounts.market.key(),
+        listing: ctx.accounts.listing.key(),
+        seller: ctx.accounts.seller.key(),
+        asset: ctx.accounts.listing.asset,
+        usd_pricing,
+        floor_price: ctx.accounts.listing.price,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,147 @@
+use anchor_lang::prelude::*;
+use mpl_core::instructions::TransferV1CpiBuilder;
+use crate::{fees, FractionVault, Market, VaultState, ErrorCode};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SettleBuyout<'info> {
+    /// Anyone can settle an ended auction
+    #[account(mut)]
+    pub caller: Signer<'info>,
+
+    /// The winning bidder receiving the NFT
+    /// CHECK: Validated against vault.bidder
+    pub bidder: UncheckedAccount<'info>,
+
+    /// The vault being settled
+    #[account(
+        mut,
+        seeds = [b"vault", vault.asset.as_ref()],
+        bump = vault.bump,
+        has_one = asset,
+        has_one = collection,
+    )]
+    pub vault: Account<'info, FractionVault>,
+
+    /// The escrowed NFT
+    /// CHECK: Validated against vault.asset
+    #[account(mut)]
+    pub asset: UncheckedAccount<'info>,
+
+    /// The collection the NFT belongs to
+    /// CHECK: Validated against vault.collection
+    pub collection: UncheckedAccount<'info>,
+
+    /// Fee recipient receiving marketplace fee
+    /// CHECK: Validated against market.fee_recipient
+    #[account(mut)]
+    pub fee_recipient: UncheckedAccount<'info>,
+
+    /// MPL Core program for NFT transfer
+    /// CHECK: Pinned to the mpl-core program id
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Market account for fee configuration and event sequence
+    #[account(
+        mut,
+        seeds = [b"market"],
+        bump = market.bump,
+    )]
+    pub market: Account<'info, Market>,
+}
+
+#[event]
+pub struct BuyoutSettled {
+    pub seq: u64,
+    pub market: Pubkey,
+    pub vault: Pubkey,
+    pub collection: Pubkey,
+    pub asset: Pubkey,
+    pub bidder: Pubkey,
+    pub fee_recipient: Pubkey,
+    pub price: u64,
+    pub fee_amount: u64,
+    pub proceeds: u64,
+    pub timestamp: i64,
+}
+
+pub fn process_settle_buyout(ctx: Context<SettleBuyout>) -> Result<()> {
+    let vault = &ctx.accounts.vault;
+    let market = &ctx.accounts.market;
+    let clock = Clock::get()?;
+
+    require!(vault.state == VaultState::Auction, ErrorCode::InvalidVaultState);
+    require!(clock.unix_timestamp >= vault.auction_ends_at, ErrorCode::AuctionNotEnded);
+    require!(
+        vault.bidder == Some(ctx.accounts.bidder.key()),
+        ErrorCode::InvalidVaultState
+    );
+    require!(
+        market.fee_recipient == ctx.accounts.fee_recipient.key(),
+        ErrorCode::InvalidFeeRecipient
+    );
+
+    // Marketplace fee comes out of the winning bid, the rest is left for holders
+    let price = vault.winning_bid;
+    let fee_amount = fees::compute_fee_from_proceeds(
+        price,
+        market.taker_fee_bps,
+        market.fee_rounding,
+        market.min_fee,
+    )?;
+    let proceeds = price
+        .checked_sub(fee_amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    if fee_amount > 0 {
+        ctx.accounts.vault.sub_lamports(fee_amount)?;
+        ctx.accounts.fee_recipient.add_lamports(fee_amount)?;
+    }
+
+    // Hand the NFT to the winning bidder using the vault PDA as authority
+    let asset_key = vault.asset;
+    let seeds = &[b"vault", asset_key.as_ref(), &[vault.bump]];
+    let signer = &[&seeds[..]];
+
+    TransferV1CpiBuilder::new(&ctx.accounts.mpl_core_program.to_account_info())
+        .asset(&ctx.accounts.asset.to_account_info())
+        .collection(Some(&ctx.accounts.collection.to_account_info()))
+        .payer(&ctx.accounts.caller.to_account_info())
+        .authority(Some(&ctx.accounts.vault.to_account_info()))  // PDA is authority
+        .new_owner(&ctx.accounts.bidder.to_account_info())
+        .invoke_signed(signer)?;  // Sign with PDA seeds
+
+    let vault = &mut ctx.accounts.vault;
+    vault.state = VaultState::Sold;
+    vault.proceeds = proceeds;
+
+    ctx.accounts.market.record_sale(price, fee_amount)?;
+
+    msg!(
+        "Buyout settled: vault {} - asset {} to {} for {} lamports",
+        ctx.accounts.vault.key(),
+        asset_key,
+        ctx.accounts.bidder.key(),
+        price
+    );
+
+    let seq = ctx.accounts.market.next_event_seq()?;
+    emit_cpi!(BuyoutSettled {
+        seq,
+        market: ctx.accounts.market.key(),
+        vault: ctx.accounts.vault.key(),
+        collection: ctx.accounts.collection.key(),
+        asset: asset_key,
+        bidder: ctx.accounts.bidder.key(),
+        fee_recipient: ctx.accounts.fee_recipient.key(),
+        price,
+        fee_amount,
+        proceeds,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,234 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use mpl_core::instructions::TransferV1CpiBuilder;
+use crate::{fees, merkle, InstallmentPurchase, Listing, Market, ErrorCode};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct StartInstallmentPurchase<'info> {
+    /// The buyer paying the down payment
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    /// The seller receiving the down payment
+    /// CHECK: Validated against listing.seller
+    #[account(mut)]
+    pub seller: UncheckedAccount<'info>,
+
+    /// The listing being bought in installments
+    #[account(
+        mut,
+        seeds = [
+            b"listing",
+            listing.seller.as_ref(),
+            listing.collection.as_ref(),
+            listing.asset.as_ref()
+        ],
+        bump = listing.bump,
+        has_one = seller,
+        has_one = asset,
+        has_one = collection,
+    )]
+    pub listing: Account<'info, Listing>,
+
+    /// The purchase - PDA with seeds [b"installment", listing]
+    #[account(
+        init,
+        payer = buyer,
+        space = InstallmentPurchase::DISCRIMINATOR.len() + InstallmentPurchase::INIT_SPACE,
+        seeds = [b"installment", listing.key().as_ref()],
+        bump
+    )]
+    pub purchase: Account<'info, InstallmentPurchase>,
+
+    /// The NFT asset moving from the listing to the purchase escrow
+    /// CHECK: Validated against listing.asset
+    #[account(mut)]
+    pub asset: UncheckedAccount<'info>,
+
+    /// The collection the NFT belongs to
+    /// CHECK: Validated against listing.collection
+    pub collection: UncheckedAccount<'info>,
+
+    /// Fee recipient receiving marketplace fee
+    /// CHECK: Validated against market.fee_recipient
+    #[account(mut)]
+    pub fee_recipient: UncheckedAccount<'info>,
+
+    /// MPL Core program for NFT transfer
+    /// CHECK: Pinned to the mpl-core program id
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Market account for fee configuration and event sequence
+    #[account(
+        mut,
+        seeds = [b"market"],
+        bump = market.bump,
+    )]
+    pub market: Account<'info, Market>,
+}
+
+#[event]
+pub struct InstallmentPurchaseStarted {
+    pub seq: u64,
+    pub market: Pubkey,
+    pub purchase: Pubkey,
+    pub listing: Pubkey,
+    pub seller: Pubkey,
+    pub buyer: Pubkey,
+    pub collection: Pubkey,
+    pub asset: Pubkey,
+    pub fee_recipient: Pubkey,
+    pub price: u64,
+    pub down_payment: u64,
+    pub fee_amount: u64,
+    pub installment_count: u8,
+    pub next_due_at: i64,
+    pub timestamp: i64,
+}
+
+pub fn process_start_installment_purchase(ctx: Context<StartInstallmentPurchase>, proof: Vec<[u8; 32]>) -> Result<()> {
+    let listing = &ctx.accounts.listing;
+    let market = &ctx.accounts.market;
+    let buyer_key = ctx.accounts.buyer.key();
+    let clock = Clock::get()?;
+
+    require!(listing.is_active, ErrorCode::ListingNotActive);
+    let plan = listing.installment_plan.ok_or(ErrorCode::NoInstallmentPlan)?;
+
+    require!(listing.seller != buyer_key, ErrorCode::SelfTrade);
+    require!(
+        market.fee_recipient == ctx.accounts.fee_recipient.key(),
+        ErrorCode::InvalidFeeRecipient
+    );
+
+    // Same private sale / allowlist gating as match_listing
+    if let Some(reserved_buyer) = listing.reserved_buyer {
+        require!(reserved_buyer == buyer_key, ErrorCode::ReservedForOtherBuyer);
+    }
+    let allowlist_root = match listing.allowlist_root {
+        Some(root) => Some(root),
+        None if clock.unix_timestamp < market.early_access_ends_at => market.allowlist_root,
+        None => None,
+    };
+    if let Some(root) = allowlist_root {
+        require!(
+            merkle::verify(&proof, root, merkle::wallet_leaf(&buyer_key)),
+            ErrorCode::NotAllowlisted
+        );
+    }
+
+    // Seller gets every payment in full, the buyer pays the taker fee on top
+    let down_payment = plan.down_payment;
+    let fee_amount = fees::compute_fee(
+        down_payment,
+        market.taker_fee_bps,
+        market.fee_rounding,
+        market.min_fee,
+    )?;
+    let installment_amount = listing.price
+        .checked_sub(down_payment)
+        .ok_or(ErrorCode::MathOverflow)?
+        / plan.installment_count as u64;
+    let next_due_at = clock.unix_timestamp
+        .checked_add(plan.interval)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    if down_payment > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.buyer.to_account_info(),
+                    to: ctx.accounts.seller.to_account_info(),
+                },
+            ),
+            down_payment,
+        )?;
+    }
+
+    if fee_amount > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.buyer.to_account_info(),
+                    to: ctx.accounts.fee_recipient.to_account_info(),
+                },
+            ),
+            fee_amount,
+        )?;
+    }
+
+    let purchase = &mut ctx.accounts.purchase;
+    purchase.listing = listing.key();
+    purchase.seller = listing.seller;
+    purchase.buyer = buyer_key;
+    purchase.collection = listing.collection;
+    purchase.asset = listing.asset;
+    purchase.price = listing.price;
+    purchase.paid = down_payment;
+    purchase.fees_paid = fee_amount;
+    purchase.installment_amount = installment_amount;
+    purchase.installment_count = plan.installment_count;
+    purchase.installments_paid = 0;
+    purchase.interval = plan.interval;
+    purchase.next_due_at = next_due_at;
+    purchase.refund_bps = plan.refund_bps;
+    purchase.started_at = clock.unix_timestamp;
+    purchase.bump = ctx.bumps.purchase;
+
+    // Move the NFT from the listing escrow to the purchase escrow
+    let seeds = &[
+        b"listing",
+        listing.seller.as_ref(),
+        listing.collection.as_ref(),
+        listing.asset.as_ref(),
+        &[listing.bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    TransferV1CpiBuilder::new(&ctx.accounts.mpl_core_program.to_account_info())
+        .asset(&ctx.accounts.asset.to_account_info())
+        .collection(Some(&ctx.accounts.collection.to_account_info()))
+        .payer(&ctx.accounts.buyer.to_account_info())
+        .authority(Some(&ctx.accounts.listing.to_account_info()))  // PDA is authority
+        .new_owner(&ctx.accounts.purchase.to_account_info())
+        .invoke_signed(signer)?;  // Sign with PDA seeds
+
+    // Listing is no longer for sale; it still counts as active on the market
+    // until the purchase completes or defaults
+    ctx.accounts.listing.is_active = false;
+
+    msg!(
+        "Installment purchase started: {} - down payment {} lamports, {} installments from {}",
+        ctx.accounts.purchase.key(),
+        down_payment,
+        plan.installment_count,
+        next_due_at
+    );
+
+    let seq = ctx.accounts.market.next_event_seq()?;
+    emit_cpi!(InstallmentPurchaseStarted {
+        seq,
+        market: ctx.accounts.market.key(),
+        purchase: ctx.accounts.purchase.key(),
+        listing: ctx.accounts.listing.key(),
+        seller: ctx.accounts.seller.key(),
+        buyer: buyer_key,
+        collection: ctx.accounts.collection.key(),
+        asset: ctx.accounts.asset.key(),
+        fee_recipient: ctx.accounts.fee_recipient.key(),
+        price: ctx.accounts.purchase.price,
+        down_payment,
+        fee_amount,
+        installment_count: plan.installment_count,
+        next_due_at,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use crate::{UpdateMarket, ErrorCode};
+
+#[event]
+pub struct MarketUpdated {
+    pub seq: u64,
+    pub market: Pubkey,
+    pub admin: Pubkey,
+    pub fee_recipient: Pubkey,
+    pub taker_fee_bps: u64,
+    pub timestamp: i64,
+}
+
+pub fn process_update_market(
+    ctx: Context<UpdateMarket>,
+    fee_recipient: Pubkey,
+    taker_fee_bps: u64,
+) -> Result<()> {
+    require!(taker_fee_bps <= 10000, ErrorCode::FeeTooHigh); // basis points n/10_000 = percentage
+
+    let market = &mut ctx.accounts.market;
+    market.fee_recipient = fee_recipient;
+    market.taker_fee_bps = taker_fee_bps;
+
+    let seq = ctx.accounts.market.next_event_seq()?;
+    emit_cpi!(MarketUpdated {
+        seq,
+        market: ctx.accounts.market.key(),
+        admin: ctx.accounts.admin.key(),
+        fee_recipient,
+        taker_fee_bps,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+use mpl_core::instructions::TransferV1CpiBuilder;
+use crate::{Market, Pool, ErrorCode};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct WithdrawPoolNft<'info> {
+    /// The pool owner taking an NFT back
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// The pool releasing the NFT
+    #[account(
+        mut,
+        seeds = [b"pool", pool.owner.as_ref(), pool.seed.to_le_bytes().as_ref()],
+        bump = pool.bump,
+        has_one = owner,
+        has_one = collection,
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// The NFT being withdrawn
+    /// CHECK: Ownership by the pool PDA is enforced by mpl-core on transfer
+    #[account(mut)]
+    pub asset: UncheckedAccount<'info>,
+
+    /// The collection the NFT belongs to
+    /// CHECK: Validated against pool.collection
+    pub collection: UncheckedAccount<'info>,
+
+    /// MPL Core program for NFT transfer
+    /// CHECK: Pinned to the mpl-core program id
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Market account (event sequence number)
+    #[account(
+        mut,
+        seeds = [b"market"],
+        bump = market.bump,
+    )]
+    pub market: Account<'info, Market>,
+}
+
+#[event]
+pub struct PoolNftWithdrawn {
+    pub seq: u64,
+    pub market: Pubkey,
+    pub pool: Pubkey,
+    pub owner: Pubkey,
+    pub asset: Pubkey,
+    pub nft_count: u32,
+    pub timestamp: i64,
+}
+
+pub fn process_withdraw_pool_nft(ctx: Context<WithdrawPoolNft>) -> Result<()> {
+    let pool = &ctx.accounts.pool;
+
+    // Prepare pool PDA signer seeds
+    let seed_bytes = pool.seed.to_le_bytes();
+    let seeds = &[b"pool", pool.owner.as_ref(), seed_bytes.as_ref(), &[pool.bump]];
+    let signer = &[&seeds[..]];
+
+    // Transfer NFT back to the owner using the pool PDA as authority
+    TransferV1CpiBuilder::new(&ctx.accounts.mpl_core_program.to_account_info())
+        .asset(&ctx.accounts.asset.to_account_info())
+        .collection(Some(&ctx.accounts.collection.to_account_info()))
+        .payer(&ctx.accounts.owner.to_account_info())
+        .authority(Some(&ctx.accounts.pool.to_account_info()))
+        .new_owner(&ctx.accounts.owner.to_account_info())
+        .invoke_signed(signer)?;
+
+    let pool = &mut ctx.accounts.pool;
+    pool.nft_count = pool.nft_count.checked_sub(1).ok_or(ErrorCode::MathOverflow)?;
+
+    msg!("Pool {} released asset {} ({} held)", pool.key(), ctx.accounts.asset.key(), pool.nft_count);
+
+    let seq = ctx.accounts.market.next_event_seq()?;
+    emit_cpi!(PoolNftWithdrawn {
+        seq,
+        market: ctx.accounts.market.key(),
+        pool: ctx.accounts.pool.key(),
+        owner: ctx.accounts.owner.key(),
+        asset: ctx.accounts.asset.key(),
+        nft_count: ctx.accounts.pool.nft_count,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use crate::{Market, Pool, ErrorCode};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct WithdrawPoolSol<'info> {
+    /// The pool owner pulling liquidity
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// The pool paying out the lamports
+    #[account(
+        mut,
+        seeds = [b"pool", pool.owner.as_ref(), pool.seed.to_le_bytes().as_ref()],
+        bump = pool.bump,
+        has_one = owner,
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// Market account (event sequence number)
+    #[account(
+        mut,
+        seeds = [b"market"],
+        bump = market.bump,
+    )]
+    pub market: Account<'info, Market>,
+}
+
+#[event]
+pub struct PoolSolWithdrawn {
+    pub seq: u64,
+    pub market: Pubkey,
+    pub pool: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub sol_balance: u64,
+    pub timestamp: i64,
+}
+
+pub fn process_withdraw_pool_sol(ctx: Context<WithdrawPoolSol>, amount: u64) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    require!(amount > 0, ErrorCode::InvalidPoolConfig);
+    // Only the tracked balance can leave, the rent stays until the pool is closed
+    pool.sol_balance = pool.sol_balance.checked_sub(amount).ok_or(ErrorCode::InsufficientFunds)?;
+
+    // Pool PDA is program owned
+    ctx.accounts.pool.sub_lamports(amount)?;
+    ctx.accounts.owner.add_lamports(amount)?;
+
+    msg!(
+        "Pool {} withdrew {} lamports ({} available)",
+        ctx.accounts.pool.key(),
+        amount,
+        ctx.accounts.pool.sol_balance
+    );
+
+    let seq = ctx.accounts.market.next_event_seq()?;
+    emit_cpi!(PoolSolWithdrawn {
+        seq,
+        market: ctx.accounts.market.key(),
+        pool: ctx.accounts.pool.key(),
+        owner: ctx.accounts.owner.key(),
+        amount,
+        sol_balance: ctx.accounts.pool.sol_balance,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
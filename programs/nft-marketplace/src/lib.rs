@@ -1,7 +1,10 @@
 use anchor_lang::prelude::*;
 use instructions::*;
 mod instructions;
-
+mod merkle;
+mod asset_pairs;
+pub mod fees;
+pub mod oracle;
 
 mod error;
 use error::ErrorCode;
@@ -23,6 +26,21 @@ pub mod nft_marketplace {
             market.admin = ctx.accounts.admin.key();
             market.fee_recipient = fee_recipient.key();
             market.taker_fee_bps = taker_fee_bps;
+            market.allowlist_root = None;
+            market.early_access_ends_at = 0;
+            market.fee_rounding = FeeRounding::Floor;
+            market.min_fee = 0;
+            market.min_price = 1;
+            market.max_price = u64::MAX;
+            market.fee_tiers = Vec::new();
+            market.version = Market::VERSION;
+            market.event_seq = 0;
+            market.active_listings = 0;
+            market.total_listings = 0;
+            market.total_sales = 0;
+            market.total_volume = 0;
+            market.total_fees = 0;
+            market.sol_usd_feed = Pubkey::default();
             market.bump = ctx.bumps.market;
             Ok(())
         }
@@ -33,13 +51,64 @@ pub mod nft_marketplace {
         fee_recipient: Pubkey,
         taker_fee_bps: u64
     ) -> Result<()> {
-        require!(taker_fee_bps <= 10000, ErrorCode::FeeTooHigh); // basis points n/10_000 = percentage
+        instructions::update_market::process_update_market(ctx, fee_recipient, taker_fee_bps)
+    }
 
-        let market = &mut ctx.accounts.market;
-        market.fee_recipient = fee_recipient;
-        market.taker_fee_bps = taker_fee_bps;
+    // only admin - gate all purchases behind an allowlist until early_access_ends_at
+    pub fn set_market_allowlist(
+        ctx: Context<UpdateMarket>,
+        allowlist_root: Option<[u8; 32]>,
+        early_access_ends_at: i64
+    ) -> Result<()> {
+        instructions::set_market_allowlist::process_set_market_allowlist(ctx, allowlist_root, early_access_ends_at)
+    }
 
-        Ok(())
+    // only admin - how fractional lamports are rounded and the smallest fee charged
+    pub fn set_fee_policy(
+        ctx: Context<UpdateMarket>,
+        fee_rounding: FeeRounding,
+        min_fee: u64
+    ) -> Result<()> {
+        instructions::set_fee_policy::process_set_fee_policy(ctx, fee_rounding, min_fee)
+    }
+
+    // only admin - volume based fee discounts (replaces the whole table, empty disables)
+    pub fn set_fee_tiers(ctx: Context<UpdateMarket>, fee_tiers: Vec<FeeTier>) -> Result<()> {
+        instructions::set_fee_tiers::process_set_fee_tiers(ctx, fee_tiers)
+    }
+
+    // only admin - SPL reward emissions per lamport of traded volume
+    pub fn set_reward_config(ctx: Context<SetRewardConfig>, emission_rate: u64, enabled: bool) -> Result<()> {
+        instructions::set_reward_config::process_set_reward_config(ctx, emission_rate, enabled)
+    }
+
+    // only admin - Pyth SOL/USD feed that settles USD-priced listings
+    pub fn set_price_feed(ctx: Context<UpdateMarket>, sol_usd_feed: Pubkey) -> Result<()> {
+        instructions::set_price_feed::process_set_price_feed(ctx, sol_usd_feed)
+    }
+
+    // only admin - market-wide listing price bounds
+    pub fn set_price_bounds(ctx: Context<UpdateMarket>, min_price: u64, max_price: u64) -> Result<()> {
+        instructions::set_price_bounds::process_set_price_bounds(ctx, min_price, max_price)
+    }
+
+    // only admin - per-collection listing price bounds (override market-wide)
+    pub fn set_collection_price_bounds(
+        ctx: Context<SetCollectionPriceBounds>,
+        min_price: u64,
+        max_price: u64
+    ) -> Result<()> {
+        instructions::set_collection_price_bounds::process_set_collection_price_bounds(ctx, min_price, max_price)
+    }
+
+    // only admin - allow a relayer to pay fees and rent for buyers' purchases
+    pub fn add_relayer(ctx: Context<AddRelayer>) -> Result<()> {
+        instructions::add_relayer::process_add_relayer(ctx)
+    }
+
+    // only admin - revoke a relayer
+    pub fn remove_relayer(ctx: Context<RemoveRelayer>) -> Result<()> {
+        instructions::remove_relayer::process_remove_relayer(ctx)
     }
 
     // Create an NFT collection
@@ -47,9 +116,15 @@ pub mod nft_marketplace {
         instructions::create_collection::process_create_collection(ctx, name, uri)
     }
 
-    // List an NFT for sale
-    pub fn add_listing(ctx: Context<AddListing>, price: u64) -> Result<()> {
-        instructions::add_listing::process_add_listing(ctx, price)
+    // List an NFT for sale (optionally gated to an allowlist or reserved for one buyer)
+    pub fn add_listing(
+        ctx: Context<AddListing>,
+        price: u64,
+        allowlist_root: Option<[u8; 32]>,
+        reserved_buyer: Option<Pubkey>,
+        mode: ListingMode
+    ) -> Result<()> {
+        instructions::add_listing::process_add_listing(ctx, price, allowlist_root, reserved_buyer, mode)
     }
 
     // Remove a listing and return NFT to seller
@@ -57,9 +132,271 @@ pub mod nft_marketplace {
         instructions::remove_listing::process_remove_listing(ctx)
     }
 
-    // Manual order matching
-    pub fn match_listing(ctx: Context<MatchListing>) -> Result<()> {
-        instructions::match_listing::process_match_listing(ctx)
+    // Anyone can close a sold listing, rent goes back to the seller
+    pub fn close_inactive_listing(ctx: Context<CloseInactiveListing>) -> Result<()> {
+        instructions::close_inactive_listing::process_close_inactive_listing(ctx)
+    }
+
+    // Batched cleanup sweep over (listing, seller) pairs in remaining_accounts
+    pub fn close_inactive_listings<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CloseInactiveListings<'info>>
+    ) -> Result<()> {
+        instructions::close_inactive_listings::process_close_inactive_listings(ctx)
+    }
+
+    // Manual order matching (proof only needed for gated listings / early access),
+    // optionally relisting the NFT for the buyer at relist_price; the buyer pays at
+    // most max_total (price + taker fee)
+    pub fn match_listing(
+        ctx: Context<MatchListing>,
+        proof: Vec<[u8; 32]>,
+        relist_price: Option<u64>,
+        max_total: u64
+    ) -> Result<()> {
+        instructions::match_listing::process_match_listing(ctx, proof, relist_price, max_total)
+    }
+
+    // Claim accrued trading rewards (minted if the reward config is mint authority, else paid from the vault)
+    pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
+        instructions::claim_rewards::process_claim_rewards(ctx)
+    }
+
+    // only admin - upgrade the market account to the current layout
+    pub fn migrate_market(ctx: Context<MigrateMarket>) -> Result<()> {
+        instructions::migrate_market::process_migrate_market(ctx)
+    }
+
+    // Anyone can upgrade a listing to the current layout (payer covers extra rent)
+    pub fn migrate_listing(ctx: Context<MigrateListing>) -> Result<()> {
+        instructions::migrate_listing::process_migrate_listing(ctx)
+    }
+
+    // Read-only price/volume oracle for a collection (TWAP = delta cumulative / delta time)
+    pub fn get_collection_stats(ctx: Context<GetCollectionStats>) -> Result<CollectionStatsView> {
+        instructions::get_collection_stats::process_get_collection_stats(ctx)
+    }
+
+    // Create a launchpad drop with its own collection
+    pub fn create_drop(ctx: Context<CreateDrop>, config: DropConfig) -> Result<()> {
+        instructions::create_drop::process_create_drop(ctx, config)
+    }
+
+    // Primary sale mint from a drop (proof only needed in allowlist phase)
+    pub fn mint_drop(ctx: Context<MintDrop>, proof: Vec<[u8; 32]>) -> Result<()> {
+        instructions::mint_drop::process_mint_drop(ctx, proof)
+    }
+
+    // Escrow assets (+ lamports) in exchange for named assets from the taker
+    pub fn create_swap<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CreateSwap<'info>>,
+        seed: u64,
+        taker: Pubkey,
+        requested_assets: Vec<Pubkey>,
+        offered_lamports: u64
+    ) -> Result<()> {
+        instructions::create_swap::process_create_swap(ctx, seed, taker, requested_assets, offered_lamports)
+    }
+
+    // Lender escrows lamports offered against any asset of a collection
+    pub fn create_loan_offer(
+        ctx: Context<CreateLoanOffer>,
+        seed: u64,
+        collection: Pubkey,
+        amount: u64,
+        apr_bps: u64,
+        duration: i64
+    ) -> Result<()> {
+        instructions::create_loan_offer::process_create_loan_offer(ctx, seed, collection, amount, apr_bps, duration)
+    }
+
+    // Lender withdraws an untaken offer
+    pub fn cancel_loan_offer(ctx: Context<CancelLoanOffer>) -> Result<()> {
+        instructions::cancel_loan_offer::process_cancel_loan_offer(ctx)
+    }
+
+    // Borrower escrows an asset and receives the principal
+    pub fn accept_loan_offer(ctx: Context<AcceptLoanOffer>) -> Result<()> {
+        instructions::accept_loan_offer::process_accept_loan_offer(ctx)
+    }
+
+    // Borrower repays principal + interest before due_at and gets the asset back
+    pub fn repay_loan(ctx: Context<RepayLoan>) -> Result<()> {
+        instructions::repay_loan::process_repay_loan(ctx)
+    }
+
+    // Lender takes the asset after due_at
+    pub fn foreclose_loan(ctx: Context<ForecloseLoan>) -> Result<()> {
+        instructions::foreclose_loan::process_foreclose_loan(ctx)
+    }
+
+    // Owner escrows an asset for rent at a per-day price
+    pub fn list_rental(ctx: Context<ListRental>, daily_price: u64, max_days: u16) -> Result<()> {
+        instructions::list_rental::process_list_rental(ctx, daily_price, max_days)
+    }
+
+    // Renter pays upfront for a number of days
+    pub fn rent_asset(ctx: Context<RentAsset>, days: u16) -> Result<()> {
+        instructions::rent_asset::process_rent_asset(ctx, days)
+    }
+
+    // Anyone clears the renter record once the period has ended
+    pub fn reclaim_rental(ctx: Context<ReclaimRental>) -> Result<()> {
+        instructions::reclaim_rental::process_reclaim_rental(ctx)
+    }
+
+    // Owner takes the asset back while it is not rented
+    pub fn remove_rental(ctx: Context<RemoveRental>) -> Result<()> {
+        instructions::remove_rental::process_remove_rental(ctx)
+    }
+
+    // Seller prices a listing in USD (settled in SOL via a Pyth feed) or back in lamports
+    pub fn set_usd_price(ctx: Context<SetUsdPrice>, usd_pricing: Option<UsdPricing>) -> Result<()> {
+        instructions::set_usd_price::process_set_usd_price(ctx, usd_pricing)
+    }
+
+    // Seller offers (or withdraws) an installment plan on an escrowed listing
+    pub fn set_installment_plan(ctx: Context<SetInstallmentPlan>, plan: Option<InstallmentPlan>) -> Result<()> {
+        instructions::set_installment_plan::process_set_installment_plan(ctx, plan)
+    }
+
+    // Buyer pays the down payment, the NFT moves to the purchase escrow
+    pub fn start_installment_purchase(ctx: Context<StartInstallmentPurchase>, proof: Vec<[u8; 32]>) -> Result<()> {
+        instructions::start_installment_purchase::process_start_installment_purchase(ctx, proof)
+    }
+
+    // Buyer pays the next installment, the last one releases the NFT
+    pub fn pay_installment(ctx: Context<PayInstallment>) -> Result<()> {
+        instructions::pay_installment::process_pay_installment(ctx)
+    }
+
+    // Seller takes the NFT back after a missed installment, refunding refund_bps
+    pub fn reclaim_installment(ctx: Context<ReclaimInstallment>) -> Result<()> {
+        instructions::reclaim_installment::process_reclaim_installment(ctx)
+    }
+
+    // List several assets (any collections) as one lot at one price
+    pub fn add_bundle_listing<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AddBundleListing<'info>>,
+        seed: u64,
+        price: u64
+    ) -> Result<()> {
+        instructions::add_bundle_listing::process_add_bundle_listing(ctx, seed, price)
+    }
+
+    // Buy every asset of a bundle atomically
+    pub fn match_bundle_listing<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MatchBundleListing<'info>>,
+        max_total: u64
+    ) -> Result<()> {
+        instructions::match_bundle_listing::process_match_bundle_listing(ctx, max_total)
+    }
+
+    // Seller delists the bundle and gets every asset back
+    pub fn remove_bundle_listing<'info>(ctx: Context<'_, '_, 'info, 'info, RemoveBundleListing<'info>>) -> Result<()> {
+        instructions::remove_bundle_listing::process_remove_bundle_listing(ctx)
+    }
+
+    // Escrow an asset in a vault and mint a fixed supply of SPL fractions to the curator
+    pub fn fractionalize(
+        ctx: Context<Fractionalize>,
+        supply: u64,
+        reserve_price: u64,
+        auction_duration: i64
+    ) -> Result<()> {
+        instructions::fractionalize::process_fractionalize(ctx, supply, reserve_price, auction_duration)
+    }
+
+    // Open a buyout auction at or above the reserve price, or outbid the current bidder
+    pub fn place_buyout_bid(ctx: Context<PlaceBuyoutBid>, bid: u64) -> Result<()> {
+        instructions::place_buyout_bid::process_place_buyout_bid(ctx, bid)
+    }
+
+    // Anyone hands the asset to the winning bidder once the auction has ended
+    pub fn settle_buyout(ctx: Context<SettleBuyout>) -> Result<()> {
+        instructions::settle_buyout::process_settle_buyout(ctx)
+    }
+
+    // Fraction holder burns their fractions for a pro-rata share of the proceeds
+    pub fn redeem_fractions(ctx: Context<RedeemFractions>) -> Result<()> {
+        instructions::redeem_fractions::process_redeem_fractions(ctx)
+    }
+
+    // Bidder escrows lamports for any asset of a collection with the given traits
+    pub fn create_trait_bid(
+        ctx: Context<CreateTraitBid>,
+        seed: u64,
+        collection: Pubkey,
+        traits: Vec<TraitFilter>,
+        amount: u64
+    ) -> Result<()> {
+        instructions::create_trait_bid::process_create_trait_bid(ctx, seed, collection, traits, amount)
+    }
+
+    // Bidder withdraws an unfilled bid
+    pub fn cancel_trait_bid(ctx: Context<CancelTraitBid>) -> Result<()> {
+        instructions::cancel_trait_bid::process_cancel_trait_bid(ctx)
+    }
+
+    // Owner sells a matching asset into the bid
+    pub fn fill_trait_bid(ctx: Context<FillTraitBid>) -> Result<()> {
+        instructions::fill_trait_bid::process_fill_trait_bid(ctx)
+    }
+
+    // Owner opens a bonding-curve pool for a collection
+    pub fn create_pool(
+        ctx: Context<CreatePool>,
+        seed: u64,
+        curve: BondingCurve,
+        spot_price: u64,
+        delta: u64,
+        spread_bps: u16
+    ) -> Result<()> {
+        instructions::create_pool::process_create_pool(ctx, seed, curve, spot_price, delta, spread_bps)
+    }
+
+    // Owner adds lamports the pool can spend buying NFTs
+    pub fn deposit_pool_sol(ctx: Context<DepositPoolSol>, amount: u64) -> Result<()> {
+        instructions::deposit_pool_sol::process_deposit_pool_sol(ctx, amount)
+    }
+
+    // Owner takes lamports out of the pool
+    pub fn withdraw_pool_sol(ctx: Context<WithdrawPoolSol>, amount: u64) -> Result<()> {
+        instructions::withdraw_pool_sol::process_withdraw_pool_sol(ctx, amount)
+    }
+
+    // Owner adds an NFT the pool can sell
+    pub fn deposit_pool_nft(ctx: Context<DepositPoolNft>) -> Result<()> {
+        instructions::deposit_pool_nft::process_deposit_pool_nft(ctx)
+    }
+
+    // Owner takes an NFT out of the pool
+    pub fn withdraw_pool_nft(ctx: Context<WithdrawPoolNft>) -> Result<()> {
+        instructions::withdraw_pool_nft::process_withdraw_pool_nft(ctx)
+    }
+
+    // Trader buys an NFT from the pool at the spot price (up to max_price)
+    pub fn buy_from_pool(ctx: Context<BuyFromPool>, max_price: u64) -> Result<()> {
+        instructions::buy_from_pool::process_buy_from_pool(ctx, max_price)
+    }
+
+    // Trader sells an NFT into the pool at the bid price (at least min_price)
+    pub fn sell_to_pool(ctx: Context<SellToPool>, min_price: u64) -> Result<()> {
+        instructions::sell_to_pool::process_sell_to_pool(ctx, min_price)
+    }
+
+    // Owner closes an empty pool and gets the remaining lamports back
+    pub fn close_pool(ctx: Context<ClosePool>) -> Result<()> {
+        instructions::close_pool::process_close_pool(ctx)
+    }
+
+    // Taker completes the swap atomically
+    pub fn accept_swap<'info>(ctx: Context<'_, '_, 'info, 'info, AcceptSwap<'info>>) -> Result<()> {
+        instructions::accept_swap::process_accept_swap(ctx)
+    }
+
+    // Maker cancels and gets everything back
+    pub fn cancel_swap<'info>(ctx: Context<'_, '_, 'info, 'info, CancelSwap<'info>>) -> Result<()> {
+        instructions::cancel_swap::process_cancel_swap(ctx)
     }
 }
 
@@ -74,7 +411,84 @@ pub struct Market {
     pub admin: Pubkey,
     pub fee_recipient: Pubkey,
     pub taker_fee_bps: u64, // basis points (buyer pays this fee to market)
-    pub bump: u8
+    pub bump: u8,
+    pub allowlist_root: Option<[u8; 32]>, // early access allowlist (all listings)
+    pub early_access_ends_at: i64,        // allowlist_root enforced until this timestamp
+    pub fee_rounding: FeeRounding,        // rounding of fractional lamport fees
+    pub min_fee: u64,                     // minimum fee in lamports (dust floor)
+    pub min_price: u64,                   // listing price bounds in lamports
+    pub max_price: u64,                   // (overridden by CollectionConfig)
+    #[max_len(8)]
+    pub fee_tiers: Vec<FeeTier>,          // ascending by volume_threshold
+    pub version: u8,                      // layout version (see Market::VERSION)
+    pub event_seq: u64,                   // sequence number of the last emitted event
+    pub active_listings: u64,             // listings currently for sale
+    pub total_listings: u64,              // lifetime listings created
+    pub total_sales: u64,                 // lifetime sales
+    pub total_volume: u128,               // lifetime sale volume in lamports
+    pub total_fees: u128,                 // lifetime taker + maker fees in lamports
+    pub sol_usd_feed: Pubkey,             // Pyth SOL/USD feed for USD-priced listings (default = disabled)
+    pub reserved: [u8; 32],               // zeroed space for future fields
+}
+
+impl Market {
+    // v1 = legacy layout without version byte / reserved space
+    pub const VERSION: u8 = 2;
+    pub const MAX_FEE_TIERS: usize = 8;
+
+    // Monotonic sequence number stamped on every event (first event is 1)
+    pub fn next_event_seq(&mut self) -> Result<u64> {
+        self.event_seq = self.event_seq.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+        Ok(self.event_seq)
+    }
+
+    // Marketplace health counters
+    pub fn record_listing(&mut self) -> Result<()> {
+        self.active_listings = self.active_listings.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+        self.total_listings = self.total_listings.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    // Saturating: listings created before the counters existed were never counted
+    pub fn record_delisting(&mut self) {
+        self.active_listings = self.active_listings.saturating_sub(1);
+    }
+
+    pub fn record_sale(&mut self, price: u64, fees: u64) -> Result<()> {
+        self.record_delisting();
+        self.record_volume(price, fees)
+    }
+
+    // Sales that never had a listing (pool trades)
+    pub fn record_volume(&mut self, price: u64, fees: u64) -> Result<()> {
+        self.total_sales = self.total_sales.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+        self.total_volume = self.total_volume.checked_add(price as u128).ok_or(ErrorCode::MathOverflow)?;
+        self.total_fees = self.total_fees.checked_add(fees as u128).ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    // Highest tier whose threshold the trader's lifetime volume has reached
+    pub fn fee_tier(&self, volume: u64) -> Option<&FeeTier> {
+        self.fee_tiers
+            .iter()
+            .rev()
+            .find(|tier| volume >= tier.volume_threshold)
+    }
+
+    // Price bounds for a collection: its CollectionConfig if one exists, else market-wide
+    pub fn check_price(&self, price: u64, collection_config: &AccountInfo) -> Result<()> {
+        let (min_price, max_price) = if collection_config.data_is_empty() {
+            (self.min_price, self.max_price)
+        } else {
+            require_keys_eq!(*collection_config.owner, crate::ID);
+            let config = CollectionConfig::try_deserialize(&mut &collection_config.data.borrow()[..])?;
+            (config.min_price, config.max_price)
+        };
+
+        require!(price >= min_price, ErrorCode::PriceTooLow);
+        require!(price <= max_price, ErrorCode::PriceTooHigh);
+        Ok(())
+    }
 }
 
 #[account]
@@ -87,6 +501,471 @@ pub struct Listing {
     pub is_active: bool,             // Whether the listing is still active
     pub created_at: i64,             // Unix timestamp when listed
     pub bump: u8,                    // PDA bump
+    pub allowlist_root: Option<[u8; 32]>, // Only wallets in this Merkle tree can buy
+    pub reserved_buyer: Option<Pubkey>,   // Only this wallet can buy (negotiated OTC deal)
+    pub mode: ListingMode,                // Where the NFT sits while listed
+    pub version: u8,                      // Layout version (see Listing::VERSION)
+    pub installment_plan: Option<InstallmentPlan>, // Buyer may pay over time (escrow mode only)
+    pub usd_pricing: Option<UsdPricing>,  // Priced in USD, `price` becomes the lamport floor
+    pub reserved: [u8; 99],               // Zeroed space for future fields
+}
+
+impl Listing {
+    // v1 = legacy layout without version byte / reserved space
+    pub const VERSION: u8 = 2;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum FeeRounding {
+    Floor,      // always round down (favours payer)
+    Ceil,       // always round up (favours market)
+    HalfEven,   // banker's rounding, unbiased over many trades
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub struct FeeTier {
+    pub volume_threshold: u64,       // Lifetime volume (bought + sold) in lamports
+    pub taker_fee_bps: u64,          // Fee paid by the buyer on top of the price
+    pub maker_fee_bps: u64,          // Fee deducted from the seller's proceeds
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub struct UsdPricing {
+    pub usd_price: u64,              // Price in USD with 6 decimals (settled via Market::sol_usd_feed)
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub struct InstallmentPlan {
+    pub down_payment: u64,           // Lamports paid when the purchase starts
+    pub installment_count: u8,       // Remaining price is split evenly over this many payments
+    pub interval: i64,               // Seconds between installment due dates
+    pub refund_bps: u16,             // Share of paid amounts refunded to the buyer on default
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum ListingMode {
+    Escrow,     // NFT transferred to the listing PDA
+    Delegated,  // NFT stays with seller, frozen with the listing PDA as freeze/transfer delegate
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct CollectionConfig {
+    pub collection: Pubkey,
+    pub min_price: u64,              // listing price bounds in lamports
+    pub max_price: u64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct RelayerApproval {
+    pub market: Pubkey,
+    pub relayer: Pubkey,             // may sign as fee payer on behalf of buyers
+    pub added_at: i64,               // Unix timestamp when approved
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct CollectionStats {
+    pub collection: Pubkey,
+    pub last_price: u64,             // Last sale price in lamports
+    pub volume: u128,                // Cumulative sale volume in lamports
+    pub sale_count: u64,             // Number of sales
+    pub price_cumulative: u128,      // Sum of last_price * seconds it held, as of last_sale_at
+    pub first_sale_at: i64,          // Unix timestamp of first sale
+    pub last_sale_at: i64,           // Unix timestamp of last sale
+    pub bump: u8,                    // PDA bump
+}
+
+impl CollectionStats {
+    // TWAP accumulator as of `now`, carrying the last price forward since the last sale
+    pub fn price_cumulative_at(&self, now: i64) -> Result<u128> {
+        if self.sale_count == 0 {
+            return Ok(0);
+        }
+        let elapsed = now.saturating_sub(self.last_sale_at).max(0) as u128;
+        self.price_cumulative
+            .checked_add(elapsed.checked_mul(self.last_price as u128).ok_or(ErrorCode::MathOverflow)?)
+            .ok_or(error!(ErrorCode::MathOverflow))
+    }
+
+    pub fn record_sale(&mut self, price: u64, now: i64) -> Result<()> {
+        self.price_cumulative = self.price_cumulative_at(now)?;
+        if self.sale_count == 0 {
+            self.first_sale_at = now;
+        }
+        self.last_price = price;
+        self.last_sale_at = now;
+        self.volume = self.volume.checked_add(price as u128).ok_or(ErrorCode::MathOverflow)?;
+        self.sale_count = self.sale_count.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct TraderStats {
+    pub wallet: Pubkey,
+    pub volume_bought: u64,          // Lamports spent on purchases (excl. fees)
+    pub volume_sold: u64,            // Lamports received from sales
+    pub trade_count: u64,            // Purchases + sales
+    pub first_trade_at: i64,         // Unix timestamp of first trade
+    pub last_trade_at: i64,          // Unix timestamp of last trade
+    pub bump: u8,                    // PDA bump
+    pub rewards_accrued: u64,        // Unclaimed reward tokens (base units)
+    pub rewards_claimed: u64,        // Lifetime claimed reward tokens
+}
+
+impl TraderStats {
+    // Lifetime volume used for fee tier selection
+    pub fn volume(&self) -> u64 {
+        self.volume_bought.saturating_add(self.volume_sold)
+    }
+
+    pub fn record_trade(&mut self, bought: u64, sold: u64, now: i64) -> Result<()> {
+        if self.trade_count == 0 {
+            self.first_trade_at = now;
+        }
+        self.last_trade_at = now;
+        self.volume_bought = self.volume_bought.checked_add(bought).ok_or(ErrorCode::MathOverflow)?;
+        self.volume_sold = self.volume_sold.checked_add(sold).ok_or(ErrorCode::MathOverflow)?;
+        self.trade_count = self.trade_count.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    pub fn accrue_rewards(&mut self, amount: u64) -> Result<()> {
+        self.rewards_accrued = self.rewards_accrued.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct RewardConfig {
+    pub market: Pubkey,              // Market this reward program belongs to
+    pub reward_mint: Pubkey,         // SPL mint paid out as rewards
+    pub emission_rate: u64,          // Reward base units per lamport, scaled by RATE_SCALE
+    pub enabled: bool,               // Accrual on/off (accrued rewards stay claimable)
+    pub bump: u8,                    // PDA bump
+}
+
+impl RewardConfig {
+    pub const RATE_SCALE: u128 = 1_000_000_000;
+
+    // Rewards earned by each side of a trade of `volume` lamports
+    pub fn reward_for(&self, volume: u64) -> Result<u64> {
+        let reward = (volume as u128)
+            .checked_mul(self.emission_rate as u128)
+            .ok_or(ErrorCode::MathOverflow)?
+            / Self::RATE_SCALE;
+        u64::try_from(reward).map_err(|_| ErrorCode::MathOverflow.into())
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Drop {
+    pub creator: Pubkey,                  // Receives primary sale proceeds
+    pub collection: Pubkey,               // Collection minted into (drop PDA is update authority)
+    #[max_len(32)]
+    pub name: String,                     // Asset name prefix
+    #[max_len(200)]
+    pub uri: String,                      // Base metadata uri
+    pub price: u64,                       // Price in lamports per mint
+    pub max_supply: u64,                  // Hard cap on minted assets
+    pub minted: u64,                      // Assets minted so far
+    pub wallet_limit: u64,                // Max mints per wallet (0 = unlimited)
+    pub allowlist_root: Option<[u8; 32]>, // Merkle root gating the allowlist phase
+    pub allowlist_start: i64,             // Allowlist phase opens
+    pub public_start: i64,                // Public phase opens
+    pub bump: u8,                         // PDA bump
+}
+
+impl Drop {
+    pub const MAX_NAME_LEN: usize = 32;
+    pub const MAX_URI_LEN: usize = 200;
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct DropMintRecord {
+    pub drop: Pubkey,
+    pub wallet: Pubkey,
+    pub minted: u64,                 // Mints by this wallet
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct SwapOffer {
+    pub maker: Pubkey,                 // Party A, deposited the offered assets
+    pub taker: Pubkey,                 // Party B, must hand over the requested assets
+    pub seed: u64,                     // Lets a maker run several swaps at once
+    #[max_len(5)]
+    pub offered_assets: Vec<Pubkey>,   // Escrowed in the swap PDA
+    #[max_len(5)]
+    pub requested_assets: Vec<Pubkey>, // Sent from taker to maker on accept
+    pub offered_lamports: u64,         // Escrowed in the swap PDA on top of rent
+    pub created_at: i64,               // Unix timestamp when created
+    pub bump: u8,                      // PDA bump
+}
+
+impl SwapOffer {
+    pub const MAX_ASSETS: usize = 5;
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct BundleListing {
+    pub seller: Pubkey,                // The person who listed the bundle
+    pub seed: u64,                     // Lets a seller list several bundles at once
+    #[max_len(5)]
+    pub assets: Vec<Pubkey>,           // Escrowed in the bundle PDA, sold together
+    pub price: u64,                    // Price for the whole lot in lamports
+    pub created_at: i64,               // Unix timestamp when listed
+    pub bump: u8,                      // PDA bump
+}
+
+impl BundleListing {
+    pub const MAX_ASSETS: usize = 5;
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct FractionVault {
+    pub curator: Pubkey,               // Deposited the NFT, received the initial fraction supply
+    pub collection: Pubkey,            // Collection of the asset
+    pub asset: Pubkey,                 // Escrowed in the vault PDA until a buyout settles
+    pub fraction_mint: Pubkey,         // SPL mint of the fractions (PDA, vault is mint authority)
+    pub supply: u64,                   // Fractions minted to the curator
+    pub reserve_price: u64,            // Minimum opening buyout bid in lamports
+    pub auction_duration: i64,         // Seconds a buyout auction runs after the first bid
+    pub state: VaultState,             // Active -> Auction -> Sold
+    pub bidder: Option<Pubkey>,        // Highest buyout bidder
+    pub winning_bid: u64,              // Highest bid, escrowed in the vault PDA
+    pub auction_ends_at: i64,          // Unix timestamp the buyout auction can be settled
+    pub proceeds: u64,                 // Lamports left for fraction holders to redeem
+    pub created_at: i64,               // Unix timestamp when fractionalized
+    pub bump: u8,                      // PDA bump
+}
+
+impl FractionVault {
+    // Outbids must beat the current bid by at least this much
+    pub const MIN_BID_INCREMENT_BPS: u64 = 500;
+
+    // Share of the remaining proceeds for `amount` of the outstanding fractions
+    pub fn redemption_for(&self, amount: u64, outstanding: u64) -> Result<u64> {
+        require!(outstanding > 0, ErrorCode::NothingToRedeem);
+        let share = (self.proceeds as u128)
+            .checked_mul(amount as u128)
+            .ok_or(ErrorCode::MathOverflow)?
+            / outstanding as u128;
+        u64::try_from(share).map_err(|_| ErrorCode::MathOverflow.into())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum VaultState {
+    Active,     // NFT escrowed, fractions trade freely
+    Auction,    // Buyout auction running
+    Sold,       // Buyout settled, fractions redeem for proceeds
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct TraitBid {
+    pub bidder: Pubkey,                // Escrowed the bid, receives the NFT on fill
+    pub collection: Pubkey,            // Any asset of this collection with the traits is accepted
+    pub seed: u64,                     // Lets a bidder post several bids at once
+    #[max_len(4)]
+    pub traits: Vec<TraitFilter>,      // All of these attributes must be on the asset
+    pub amount: u64,                   // Bid in lamports, escrowed in the bid PDA
+    pub created_at: i64,               // Unix timestamp when posted
+    pub bump: u8,                      // PDA bump
+}
+
+impl TraitBid {
+    pub const MAX_TRAITS: usize = 4;
+    pub const MAX_KEY_LEN: usize = 32;
+    pub const MAX_VALUE_LEN: usize = 64;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace, Debug)]
+pub struct TraitFilter {
+    #[max_len(32)]
+    pub key: String,                 // Attribute key, e.g. "Background"
+    #[max_len(64)]
+    pub value: String,               // Required value, e.g. "Gold"
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Pool {
+    pub owner: Pubkey,                 // Deposits and withdraws, earns the spread
+    pub collection: Pubkey,            // Pool only trades assets of this collection
+    pub seed: u64,                     // Lets an owner run several pools at once
+    pub curve: BondingCurve,           // How the spot price moves after each trade
+    pub spot_price: u64,               // Price of the next NFT bought from the pool in lamports
+    pub delta: u64,                    // Lamports (linear) or bps (exponential) per trade
+    pub spread_bps: u16,               // Discount on the price the pool pays for NFTs
+    pub sol_balance: u64,              // Lamports available to buy NFTs (excludes rent)
+    pub nft_count: u32,                // NFTs escrowed in the pool PDA
+    pub created_at: i64,               // Unix timestamp when opened
+    pub bump: u8,                      // PDA bump
+}
+
+impl Pool {
+    // Exponential curves move at most 100% per trade
+    pub const MAX_EXPONENTIAL_DELTA_BPS: u64 = 10_000;
+
+    // Spot price after the pool sells an NFT
+    pub fn price_after_buy(&self) -> Result<u64> {
+        let price = match self.curve {
+            BondingCurve::Linear => self.spot_price as u128 + self.delta as u128,
+            BondingCurve::Exponential => {
+                self.spot_price as u128 * (fees::BPS_DENOMINATOR + self.delta as u128) / fees::BPS_DENOMINATOR
+            }
+        };
+        u64::try_from(price).map_err(|_| ErrorCode::MathOverflow.into())
+    }
+
+    // Spot price after the pool buys an NFT; the pool stops buying once it would hit zero
+    pub fn price_after_sell(&self) -> Result<u64> {
+        let price = match self.curve {
+            BondingCurve::Linear => self.spot_price.saturating_sub(self.delta),
+            BondingCurve::Exponential => {
+                let price = self.spot_price as u128 * fees::BPS_DENOMINATOR
+                    / (fees::BPS_DENOMINATOR + self.delta as u128);
+                u64::try_from(price).map_err(|_| ErrorCode::MathOverflow)?
+            }
+        };
+        require!(price > 0, ErrorCode::PoolPriceExhausted);
+        Ok(price)
+    }
+
+    // Lamports the pool pays for an NFT: the next spot price down, less the spread
+    pub fn bid_price(&self) -> Result<u64> {
+        let price = self.price_after_sell()?;
+        let spread = fees::compute_fee(price, self.spread_bps as u64, FeeRounding::Ceil, 0)?;
+        price.checked_sub(spread).ok_or(ErrorCode::MathOverflow.into())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum BondingCurve {
+    Linear,         // spot price moves by delta lamports
+    Exponential,    // spot price moves by delta bps
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct LoanOffer {
+    pub lender: Pubkey,                // Funds the loan, receives the NFT on default
+    pub collection: Pubkey,            // Any asset of this collection is accepted as collateral
+    pub seed: u64,                     // Lets a lender post several offers at once
+    pub amount: u64,                   // Principal in lamports, escrowed in the offer PDA
+    pub apr_bps: u64,                  // Annual interest rate in basis points
+    pub duration: i64,                 // Loan length in seconds
+    pub created_at: i64,               // Unix timestamp when posted
+    pub bump: u8,                      // PDA bump
+}
+
+impl LoanOffer {
+    // 1,000% APR keeps interest well inside u64 for any principal the borrower can repay
+    pub const MAX_APR_BPS: u64 = 100_000;
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Loan {
+    pub lender: Pubkey,                // Receives principal + interest, or the NFT on default
+    pub borrower: Pubkey,              // Escrowed the NFT, can repay until due_at
+    pub collection: Pubkey,            // Collection of the collateral
+    pub asset: Pubkey,                 // Collateral, escrowed in the loan PDA
+    pub principal: u64,                // Lamports lent
+    pub apr_bps: u64,                  // Annual interest rate in basis points
+    pub start_at: i64,                 // Unix timestamp the loan started
+    pub due_at: i64,                   // Repay before, foreclose after
+    pub bump: u8,                      // PDA bump
+}
+
+impl Loan {
+    pub const SECONDS_PER_YEAR: u128 = 365 * 24 * 60 * 60;
+
+    // Simple interest pro rata on elapsed time, rounded up in favor of the lender
+    pub fn interest_due(&self, now: i64) -> Result<u64> {
+        let elapsed = now.saturating_sub(self.start_at).max(0) as u128;
+        let numerator = (self.principal as u128)
+            .checked_mul(self.apr_bps as u128)
+            .and_then(|value| value.checked_mul(elapsed))
+            .ok_or(ErrorCode::MathOverflow)?;
+        let denominator = fees::BPS_DENOMINATOR * Self::SECONDS_PER_YEAR;
+        u64::try_from(numerator.div_ceil(denominator)).map_err(|_| ErrorCode::MathOverflow.into())
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Rental {
+    pub owner: Pubkey,                 // Escrowed the NFT, receives rent payments
+    pub collection: Pubkey,            // Collection of the rented asset
+    pub asset: Pubkey,                 // Escrowed in the rental PDA
+    pub daily_price: u64,              // Lamports per day, paid upfront
+    pub max_days: u16,                 // Longest period a renter can book
+    pub renter: Option<Pubkey>,        // Current (or last, until reclaimed) renter
+    pub rented_until: i64,             // Unix timestamp the current rental ends
+    pub created_at: i64,               // Unix timestamp when listed
+    pub bump: u8,                      // PDA bump
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct InstallmentPurchase {
+    pub listing: Pubkey,               // Listing the plan was taken from
+    pub seller: Pubkey,                // Receives every payment, reclaims the NFT on default
+    pub buyer: Pubkey,                 // Receives the NFT after the last installment
+    pub collection: Pubkey,            // Collection of the asset
+    pub asset: Pubkey,                 // Escrowed in the purchase PDA until paid off or defaulted
+    pub price: u64,                    // Full listing price in lamports
+    pub paid: u64,                     // Lamports paid to the seller so far (excl. fees)
+    pub fees_paid: u64,                // Marketplace fees paid so far
+    pub installment_amount: u64,       // Regular installment, the last one settles the remainder
+    pub installment_count: u8,         // Installments after the down payment
+    pub installments_paid: u8,         // Installments paid so far
+    pub interval: i64,                 // Seconds between due dates
+    pub next_due_at: i64,              // Next installment must be paid by this timestamp
+    pub refund_bps: u16,               // Share of `paid` refunded to the buyer on default
+    pub started_at: i64,               // Unix timestamp of the down payment
+    pub bump: u8,                      // PDA bump
+}
+
+impl InstallmentPurchase {
+    // Regular installment, or whatever is left of the price for the last one
+    pub fn next_installment(&self) -> Result<u64> {
+        if self.installments_paid.saturating_add(1) >= self.installment_count {
+            self.price.checked_sub(self.paid).ok_or(ErrorCode::MathOverflow.into())
+        } else {
+            Ok(self.installment_amount)
+        }
+    }
+
+    pub fn default_refund(&self) -> Result<u64> {
+        let refund = (self.paid as u128)
+            .checked_mul(self.refund_bps as u128)
+            .ok_or(ErrorCode::MathOverflow)?
+            / fees::BPS_DENOMINATOR;
+        u64::try_from(refund).map_err(|_| ErrorCode::MathOverflow.into())
+    }
+}
+
+impl Rental {
+    pub const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+    pub fn is_rented(&self, now: i64) -> bool {
+        self.renter.is_some() && now < self.rented_until
+    }
 }
 
 
@@ -114,6 +993,7 @@ pub struct InitMarket<'info> {
 
 // UPDATE MARKET
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct UpdateMarket<'info> {
     pub admin: Signer <'info>,
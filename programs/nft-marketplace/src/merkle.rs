@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+use solana_sha256_hasher::hashv;
+
+// Leaf committed to an allowlist tree for a given wallet
+pub fn wallet_leaf(wallet: &Pubkey) -> [u8; 32] {
+    hashv(&[wallet.as_ref()]).to_bytes()
+}
+
+// Verify a proof against a root using sorted-pair hashing (OpenZeppelin style),
+// so proofs don't need to carry left/right position flags
+pub fn verify(proof: &[[u8; 32]], root: [u8; 32], leaf: [u8; 32]) -> bool {
+    let mut computed = leaf;
+    for node in proof {
+        computed = if computed <= *node {
+            hashv(&[&computed, node]).to_bytes()
+        } else {
+            hashv(&[node, &computed]).to_bytes()
+        };
+    }
+    computed == root
+}
@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+use crate::ErrorCode;
+
+// Pyth oracle program (mainnet), owner of the legacy price accounts
+pub const PYTH_PROGRAM_ID: Pubkey = pubkey!("FsJ3A3u2vn5cTVofAjvy6y5kwABJAqYWpe4975bi2epH");
+
+// Reject prices older than this many seconds
+pub const MAX_PRICE_AGE: i64 = 60;
+// Reject prices whose confidence interval is wider than this share of the price
+pub const MAX_CONFIDENCE_BPS: u128 = 200;
+// USD amounts are stored with 6 decimals
+pub const USD_DECIMALS: u32 = 6;
+
+// Legacy Pyth price account layout (only the fields we read)
+const MAGIC: u32 = 0xa1b2_c3d4;
+const ACCOUNT_TYPE_PRICE: u32 = 3;
+const STATUS_TRADING: u32 = 1;
+const EXPO_OFFSET: usize = 20;
+const TIMESTAMP_OFFSET: usize = 96;
+const AGG_PRICE_OFFSET: usize = 208;
+const AGG_CONF_OFFSET: usize = 216;
+const AGG_STATUS_OFFSET: usize = 224;
+const MIN_ACCOUNT_LEN: usize = AGG_STATUS_OFFSET + 4;
+
+pub struct OraclePrice {
+    pub price: u64,   // aggregate price, always positive
+    pub conf: u64,    // confidence interval, same exponent as price
+    pub expo: i32,    // price = price * 10^expo USD per SOL
+    pub timestamp: i64,
+}
+
+fn read<const N: usize>(data: &[u8], offset: usize) -> Result<[u8; N]> {
+    data.get(offset..offset + N)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| ErrorCode::InvalidPriceFeed.into())
+}
+
+// Parse a Pyth price account and check it is live, fresh and tight enough
+pub fn load_price(feed: &AccountInfo, now: i64) -> Result<OraclePrice> {
+    require_keys_eq!(*feed.owner, PYTH_PROGRAM_ID, ErrorCode::InvalidPriceFeed);
+    let data = feed.try_borrow_data()?;
+    require!(data.len() >= MIN_ACCOUNT_LEN, ErrorCode::InvalidPriceFeed);
+    require!(u32::from_le_bytes(read(&data, 0)?) == MAGIC, ErrorCode::InvalidPriceFeed);
+    require!(u32::from_le_bytes(read(&data, 8)?) == ACCOUNT_TYPE_PRICE, ErrorCode::InvalidPriceFeed);
+    require!(
+        u32::from_le_bytes(read(&data, AGG_STATUS_OFFSET)?) == STATUS_TRADING,
+        ErrorCode::InvalidPriceFeed
+    );
+
+    let price = i64::from_le_bytes(read(&data, AGG_PRICE_OFFSET)?);
+    require!(price > 0, ErrorCode::InvalidPriceFeed);
+
+    let oracle_price = OraclePrice {
+        price: price as u64,
+        conf: u64::from_le_bytes(read(&data, AGG_CONF_OFFSET)?),
+        expo: i32::from_le_bytes(read(&data, EXPO_OFFSET)?),
+        timestamp: i64::from_le_bytes(read(&data, TIMESTAMP_OFFSET)?),
+    };
+
+    require!(
+        now.saturating_sub(oracle_price.timestamp) <= MAX_PRICE_AGE,
+        ErrorCode::StalePrice
+    );
+    require!(
+        oracle_price.conf as u128 * crate::fees::BPS_DENOMINATOR
+            <= oracle_price.price as u128 * MAX_CONFIDENCE_BPS,
+        ErrorCode::PriceConfidenceTooWide
+    );
+    Ok(oracle_price)
+}
+
+// Lamports worth `usd_amount` (6 decimals) at the oracle SOL/USD price, rounded up
+pub fn lamports_for_usd(usd_amount: u64, price: &OraclePrice) -> Result<u64> {
+    // lamports = usd / 10^6 * 10^9 / (price * 10^expo)
+    let mut numerator = (usd_amount as u128)
+        .checked_mul(10u128.pow(9 - USD_DECIMALS))
+        .ok_or(ErrorCode::MathOverflow)?;
+    let mut denominator = price.price as u128;
+    let scale = 10u128
+        .checked_pow(price.expo.unsigned_abs())
+        .ok_or(ErrorCode::MathOverflow)?;
+    if price.expo < 0 {
+        numerator = numerator.checked_mul(scale).ok_or(ErrorCode::MathOverflow)?;
+    } else {
+        denominator = denominator.checked_mul(scale).ok_or(ErrorCode::MathOverflow)?;
+    }
+    u64::try_from(numerator.div_ceil(denominator)).map_err(|_| ErrorCode::MathOverflow.into())
+}
+
@@ -0,0 +1,96 @@
+use nft_marketplace::fees::{compute_fee, compute_fee_from_proceeds};
+use nft_marketplace::FeeRounding;
+use proptest::prelude::*;
+
+// Reference: exact fee as a fraction of lamports (numerator over 10_000)
+fn exact(price: u64, bps: u64) -> (u128, u128) {
+    let product = price as u128 * bps as u128;
+    (product / 10_000, product % 10_000)
+}
+
+proptest! {
+    #[test]
+    fn floor_matches_integer_division(price in any::<u64>(), bps in 0u64..=10_000) {
+        let fee = compute_fee(price, bps, FeeRounding::Floor, 0).unwrap();
+        prop_assert_eq!(fee as u128, exact(price, bps).0);
+    }
+
+    #[test]
+    fn ceil_rounds_up_only_on_remainder(price in any::<u64>(), bps in 0u64..=10_000) {
+        let fee = compute_fee(price, bps, FeeRounding::Ceil, 0).unwrap();
+        let (quotient, remainder) = exact(price, bps);
+        prop_assert_eq!(fee as u128, quotient + u128::from(remainder > 0));
+    }
+
+    #[test]
+    fn policies_are_ordered_and_within_one_lamport(price in any::<u64>(), bps in 0u64..=10_000) {
+        let floor = compute_fee(price, bps, FeeRounding::Floor, 0).unwrap();
+        let half_even = compute_fee(price, bps, FeeRounding::HalfEven, 0).unwrap();
+        let ceil = compute_fee(price, bps, FeeRounding::Ceil, 0).unwrap();
+        prop_assert!(floor <= half_even && half_even <= ceil);
+        prop_assert!(ceil - floor <= 1);
+    }
+
+    #[test]
+    fn fee_never_exceeds_price(price in any::<u64>(), bps in 0u64..=10_000) {
+        for rounding in [FeeRounding::Floor, FeeRounding::Ceil, FeeRounding::HalfEven] {
+            prop_assert!(compute_fee(price, bps, rounding, 0).unwrap() <= price);
+        }
+    }
+
+    #[test]
+    fn half_even_rounds_to_nearest(price in any::<u64>(), bps in 0u64..=10_000) {
+        let fee = compute_fee(price, bps, FeeRounding::HalfEven, 0).unwrap() as u128;
+        let (quotient, remainder) = exact(price, bps);
+        match (remainder * 2).cmp(&10_000) {
+            std::cmp::Ordering::Less => prop_assert_eq!(fee, quotient),
+            std::cmp::Ordering::Greater => prop_assert_eq!(fee, quotient + 1),
+            std::cmp::Ordering::Equal => prop_assert_eq!(fee % 2, 0),
+        }
+    }
+
+    #[test]
+    fn min_fee_is_a_floor(price in any::<u64>(), bps in 0u64..=10_000, min_fee in any::<u64>()) {
+        let rounded = compute_fee(price, bps, FeeRounding::Floor, 0).unwrap();
+        let fee = compute_fee(price, bps, FeeRounding::Floor, min_fee).unwrap();
+        prop_assert_eq!(fee, rounded.max(min_fee));
+    }
+
+    #[test]
+    fn fee_from_proceeds_is_capped_at_the_amount(price in any::<u64>(), bps in 0u64..=10_000, min_fee in any::<u64>()) {
+        let fee = compute_fee_from_proceeds(price, bps, FeeRounding::Floor, min_fee).unwrap();
+        prop_assert_eq!(fee, compute_fee(price, bps, FeeRounding::Floor, min_fee).unwrap().min(price));
+        prop_assert!(price.checked_sub(fee).is_some());
+    }
+
+    #[test]
+    fn any_bps_errors_instead_of_panicking(price in any::<u64>(), bps in any::<u64>()) {
+        let result = compute_fee(price, bps, FeeRounding::Floor, 0);
+        prop_assert_eq!(result.is_ok(), exact(price, bps).0 <= u64::MAX as u128);
+    }
+}
+
+#[test]
+fn tiny_listings_pay_fee_when_rounding_up() {
+    // 1 lamport at 2% rounds to zero with floor
+    assert_eq!(compute_fee(1, 200, FeeRounding::Floor, 0).unwrap(), 0);
+    assert_eq!(compute_fee(1, 200, FeeRounding::Ceil, 0).unwrap(), 1);
+    assert_eq!(compute_fee(1, 200, FeeRounding::Floor, 5_000).unwrap(), 5_000);
+}
+
+#[test]
+fn half_even_breaks_ties_to_even() {
+    // price * 5000 bps = exactly half a lamport above the quotient
+    assert_eq!(compute_fee(1, 5_000, FeeRounding::HalfEven, 0).unwrap(), 0); // 0.5 -> 0
+    assert_eq!(compute_fee(3, 5_000, FeeRounding::HalfEven, 0).unwrap(), 2); // 1.5 -> 2
+    assert_eq!(compute_fee(5, 5_000, FeeRounding::HalfEven, 0).unwrap(), 2); // 2.5 -> 2
+}
+
+#[test]
+fn max_price_max_fee_does_not_panic() {
+    assert_eq!(
+        compute_fee(u64::MAX, 10_000, FeeRounding::Ceil, 0).unwrap(),
+        u64::MAX
+    );
+    assert!(compute_fee(u64::MAX, u64::MAX, FeeRounding::Floor, 0).is_err());
+}
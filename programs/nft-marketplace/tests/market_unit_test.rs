@@ -35,6 +35,409 @@ fn get_mpl_core_id() -> [u8; 32] {
     return pubkey.to_bytes();
 }
 
+// SPL programs are loaded by LiteSVM by default
+fn token_program_id() -> Pubkey {
+    "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA".parse().unwrap()
+}
+
+fn associated_token_program_id() -> Pubkey {
+    "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL".parse().unwrap()
+}
+
+fn associated_token_address(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[owner.as_ref(), token_program_id().as_ref(), mint.as_ref()],
+        &associated_token_program_id(),
+    ).0
+}
+
+// Anchor event CPI signer, passed with the program id to every instruction that emits
+fn event_authority_pda(program_id: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"__event_authority"], program_id).0
+}
+
+// Assert a transaction was rejected with the given Anchor error
+fn assert_anchor_error(result: litesvm::types::TransactionResult, error: &str) {
+    let failure = result.expect_err("Transaction should fail");
+    assert!(
+        failure.meta.logs.iter().any(|log| log.contains(&format!("Error Code: {}.", error))),
+        "Expected {} in logs: {:#?}",
+        error,
+        failure.meta.logs
+    );
+}
+
+// Leaf committed to an allowlist Merkle tree for a wallet
+fn wallet_leaf(wallet: &Pubkey) -> [u8; 32] {
+    let mut hasher = solana_sdk::hash::Hasher::default();
+    hasher.hash(wallet.as_ref());
+    hasher.result().to_bytes()
+}
+
+// Convert an mpl-core (solana_program) instruction into a solana_sdk instruction
+fn to_sdk_instruction(ix: solana_program::instruction::Instruction) -> Instruction {
+    Instruction {
+        program_id: Pubkey::new_from_array(ix.program_id.to_bytes()),
+        accounts: ix.accounts.iter().map(|meta| {
+            AccountMeta {
+                pubkey: Pubkey::new_from_array(meta.pubkey.to_bytes()),
+                is_signer: meta.is_signer,
+                is_writable: meta.is_writable,
+            }
+        }).collect(),
+        data: ix.data,
+    }
+}
+
+// Optional add_listing arguments
+#[derive(Default)]
+struct ListingOptions {
+    allowlist_root: Option<[u8; 32]>,
+    reserved_buyer: Option<Pubkey>,
+    delegated: bool,
+}
+
+// Shared setup: program + mpl-core loaded and the market initialized
+struct TestMarket {
+    svm: LiteSVM,
+    program_id: Pubkey,
+    mpl_core_id: Pubkey,
+    market_pda: Pubkey,
+    admin: Keypair,
+    fee_recipient: Keypair,
+}
+
+impl TestMarket {
+    fn new(taker_fee_bps: u64) -> Self {
+        let mut svm = LiteSVM::new();
+
+        let program_id = Pubkey::new_from_array(nft_marketplace::ID.to_bytes());
+        let program_bytes = include_bytes!("../../../target/deploy/nft_marketplace.so");
+        svm.add_program(program_id, program_bytes).unwrap();
+
+        let mpl_core_id = Pubkey::new_from_array(get_mpl_core_id());
+        let mpl_core_bytes = include_bytes!("mpl_core.so");
+        svm.add_program(mpl_core_id, mpl_core_bytes).unwrap();
+
+        let admin = Keypair::new();
+        let fee_recipient = Keypair::new();
+        svm.airdrop(&admin.pubkey(), 10_000_000_000).unwrap();
+
+        let (market_pda, _) = Pubkey::find_program_address(&[b"market"], &program_id);
+
+        let mut data = Vec::new();
+        data.extend_from_slice(&anchor_discriminator("global", "init_market"));
+        data.extend_from_slice(&fee_recipient.pubkey().to_bytes());
+        data.extend_from_slice(&taker_fee_bps.to_le_bytes());
+
+        let init_market_ix = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(admin.pubkey(), true),
+                AccountMeta::new(market_pda, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            ],
+            data,
+        };
+
+        let mut market = TestMarket { svm, program_id, mpl_core_id, market_pda, admin, fee_recipient };
+        let admin = market.admin.insecure_clone();
+        market.send(&[init_market_ix], &admin, &[]).expect("Market initialization should succeed");
+        market
+    }
+
+    // Fund a new wallet
+    fn wallet(&mut self) -> Keypair {
+        let wallet = Keypair::new();
+        self.svm.airdrop(&wallet.pubkey(), 10_000_000_000).unwrap();
+        wallet
+    }
+
+    // Sign with payer plus extra signers and send
+    #[allow(clippy::result_large_err)] // callers inspect the failed transaction's logs
+    fn send(
+        &mut self,
+        instructions: &[Instruction],
+        payer: &Keypair,
+        signers: &[&Keypair],
+    ) -> litesvm::types::TransactionResult {
+        let mut all_signers = vec![payer];
+        all_signers.extend_from_slice(signers);
+        let tx = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&payer.pubkey()),
+            &all_signers,
+            self.svm.latest_blockhash(),
+        );
+        let result = self.svm.send_transaction(tx);
+        if let Err(e) = &result {
+            for log in &e.meta.logs {
+                println!("  {}", log);
+            }
+        }
+        result
+    }
+
+    // Create a collection through the program, returns (collection, update_authority)
+    fn create_collection(&mut self, payer: &Keypair) -> (Keypair, Keypair) {
+        let collection = Keypair::new();
+        let update_authority = Keypair::new();
+        let name = "Test Collection";
+        let uri = "https://example.com/collection.json";
+
+        let mut data = Vec::new();
+        data.extend_from_slice(&anchor_discriminator("global", "create_collection"));
+        data.extend_from_slice(&(name.len() as u32).to_le_bytes());
+        data.extend_from_slice(name.as_bytes());
+        data.extend_from_slice(&(uri.len() as u32).to_le_bytes());
+        data.extend_from_slice(uri.as_bytes());
+
+        let ix = Instruction {
+            program_id: self.program_id,
+            accounts: vec![
+                AccountMeta::new(collection.pubkey(), true),
+                AccountMeta::new_readonly(update_authority.pubkey(), true),
+                AccountMeta::new(payer.pubkey(), true),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(self.mpl_core_id, false),
+                AccountMeta::new(self.market_pda, false),
+                AccountMeta::new_readonly(event_authority_pda(&self.program_id), false),
+                AccountMeta::new_readonly(self.program_id, false),
+            ],
+            data,
+        };
+        self.send(&[ix], payer, &[&collection, &update_authority])
+            .expect("Collection creation should succeed");
+        (collection, update_authority)
+    }
+
+    // Mint an mpl-core asset into a collection, owned by `owner`
+    fn mint_asset(&mut self, owner: &Keypair, collection: &Pubkey, update_authority: &Keypair) -> Keypair {
+        let asset = Keypair::new();
+        let sp = |key: &Pubkey| solana_program::pubkey::Pubkey::new_from_array(key.to_bytes());
+
+        let ix = mpl_core::instructions::CreateV2Builder::new()
+            .asset(sp(&asset.pubkey()))
+            .collection(Some(sp(collection)))
+            .authority(Some(sp(&update_authority.pubkey())))
+            .payer(sp(&owner.pubkey()))
+            .owner(Some(sp(&owner.pubkey())))
+            .system_program(sp(&SYSTEM_PROGRAM_ID))
+            .name("Test NFT".to_string())
+            .uri("https://example.com/nft.json".to_string())
+            .instruction();
+
+        self.send(&[to_sdk_instruction(ix)], owner, &[&asset, update_authority])
+            .expect("Asset creation should succeed");
+        asset
+    }
+
+    // Mint an asset carrying an Attributes plugin with the given (key, value) traits
+    fn mint_asset_with_traits(
+        &mut self,
+        owner: &Keypair,
+        collection: &Pubkey,
+        update_authority: &Keypair,
+        traits: &[(&str, &str)],
+    ) -> Keypair {
+        use mpl_core::types::{Attribute, Attributes, Plugin, PluginAuthorityPair};
+
+        let asset = Keypair::new();
+        let sp = |key: &Pubkey| solana_program::pubkey::Pubkey::new_from_array(key.to_bytes());
+        let attribute_list = traits
+            .iter()
+            .map(|(key, value)| Attribute { key: key.to_string(), value: value.to_string() })
+            .collect();
+
+        let ix = mpl_core::instructions::CreateV2Builder::new()
+            .asset(sp(&asset.pubkey()))
+            .collection(Some(sp(collection)))
+            .authority(Some(sp(&update_authority.pubkey())))
+            .payer(sp(&owner.pubkey()))
+            .owner(Some(sp(&owner.pubkey())))
+            .system_program(sp(&SYSTEM_PROGRAM_ID))
+            .name("Test NFT".to_string())
+            .uri("https://example.com/nft.json".to_string())
+            .plugins(vec![PluginAuthorityPair {
+                plugin: Plugin::Attributes(Attributes { attribute_list }),
+                authority: None,
+            }])
+            .instruction();
+
+        self.send(&[to_sdk_instruction(ix)], owner, &[&asset, update_authority])
+            .expect("Asset creation should succeed");
+        asset
+    }
+
+    // Move the cluster clock forward
+    fn warp(&mut self, seconds: i64) {
+        let mut clock: solana_sdk::clock::Clock = self.svm.get_sysvar();
+        clock.unix_timestamp += seconds;
+        self.svm.set_sysvar(&clock);
+        self.svm.expire_blockhash();
+    }
+
+    // Current owner of an mpl-core asset
+    fn asset_owner(&self, asset: &Pubkey) -> Pubkey {
+        let account = self.svm.get_account(asset).expect("Asset should exist");
+        let base = mpl_core::accounts::BaseAssetV1::from_bytes(&account.data).unwrap();
+        Pubkey::new_from_array(base.owner.to_bytes())
+    }
+
+    fn listing_pda(&self, seller: &Pubkey, collection: &Pubkey, asset: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"listing", seller.as_ref(), collection.as_ref(), asset.as_ref()],
+            &self.program_id,
+        ).0
+    }
+
+    fn collection_config_pda(&self, collection: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"collection_config", collection.as_ref()],
+            &self.program_id,
+        ).0
+    }
+
+    fn collection_stats_pda(&self, collection: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"collection_stats", collection.as_ref()],
+            &self.program_id,
+        ).0
+    }
+
+    fn trader_stats_pda(&self, wallet: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[b"trader_stats", wallet.as_ref()], &self.program_id).0
+    }
+
+    fn reward_config_pda(&self) -> Pubkey {
+        Pubkey::find_program_address(&[b"reward_config", self.market_pda.as_ref()], &self.program_id).0
+    }
+
+    fn add_listing_ix(
+        &self,
+        seller: &Pubkey,
+        collection: &Pubkey,
+        asset: &Pubkey,
+        price: u64,
+        options: ListingOptions,
+    ) -> Instruction {
+        let mut data = Vec::new();
+        data.extend_from_slice(&anchor_discriminator("global", "add_listing"));
+        data.extend_from_slice(&price.to_le_bytes());
+        match options.allowlist_root {
+            Some(root) => {
+                data.push(1);
+                data.extend_from_slice(&root);
+            }
+            None => data.push(0),
+        }
+        match options.reserved_buyer {
+            Some(buyer) => {
+                data.push(1);
+                data.extend_from_slice(&buyer.to_bytes());
+            }
+            None => data.push(0),
+        }
+        data.push(options.delegated as u8); // mode: Escrow = 0, Delegated = 1
+
+        Instruction {
+            program_id: self.program_id,
+            accounts: vec![
+                AccountMeta::new(*seller, true),
+                AccountMeta::new(self.listing_pda(seller, collection, asset), false),
+                AccountMeta::new(self.market_pda, false),
+                AccountMeta::new_readonly(*collection, false),
+                AccountMeta::new_readonly(self.collection_config_pda(collection), false),
+                AccountMeta::new(*asset, false),
+                AccountMeta::new_readonly(self.mpl_core_id, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(event_authority_pda(&self.program_id), false),
+                AccountMeta::new_readonly(self.program_id, false),
+            ],
+            data,
+        }
+    }
+
+    fn remove_listing_ix(&self, seller: &Pubkey, collection: &Pubkey, asset: &Pubkey) -> Instruction {
+        Instruction {
+            program_id: self.program_id,
+            accounts: vec![
+                AccountMeta::new(*seller, true),
+                AccountMeta::new(self.listing_pda(seller, collection, asset), false),
+                AccountMeta::new_readonly(*collection, false),
+                AccountMeta::new(*asset, false),
+                AccountMeta::new_readonly(self.mpl_core_id, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new(self.market_pda, false),
+                AccountMeta::new_readonly(event_authority_pda(&self.program_id), false),
+                AccountMeta::new_readonly(self.program_id, false),
+            ],
+            data: anchor_discriminator("global", "remove_listing").to_vec(),
+        }
+    }
+
+    fn match_listing_ix(
+        &self,
+        buyer: &Pubkey,
+        seller: &Pubkey,
+        collection: &Pubkey,
+        asset: &Pubkey,
+        proof: &[[u8; 32]],
+    ) -> Instruction {
+        let mut data = Vec::new();
+        data.extend_from_slice(&anchor_discriminator("global", "match_listing"));
+        data.extend_from_slice(&(proof.len() as u32).to_le_bytes());
+        for node in proof {
+            data.extend_from_slice(node);
+        }
+        data.push(0); // relist_price: None
+        data.extend_from_slice(&u64::MAX.to_le_bytes()); // max_total: no cap
+
+        Instruction {
+            program_id: self.program_id,
+            accounts: vec![
+                AccountMeta::new(*buyer, true),
+                AccountMeta::new(*buyer, true), // payer: buyer pays its own rent
+                AccountMeta::new(*seller, false),
+                AccountMeta::new(self.listing_pda(seller, collection, asset), false),
+                AccountMeta::new(self.market_pda, false),
+                AccountMeta::new(self.fee_recipient.pubkey(), false),
+                AccountMeta::new(*asset, false),
+                AccountMeta::new_readonly(*collection, false),
+                AccountMeta::new_readonly(self.mpl_core_id, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new(self.collection_stats_pda(collection), false),
+                AccountMeta::new(self.trader_stats_pda(buyer), false),
+                AccountMeta::new(self.trader_stats_pda(seller), false),
+                AccountMeta::new_readonly(self.reward_config_pda(), false),
+                AccountMeta::new_readonly(self.program_id, false), // price_feed: None
+                AccountMeta::new_readonly(self.collection_config_pda(collection), false),
+                AccountMeta::new(self.program_id, false), // relist: None
+                AccountMeta::new_readonly(self.program_id, false), // relayer_approval: None
+                AccountMeta::new_readonly(event_authority_pda(&self.program_id), false),
+                AccountMeta::new_readonly(self.program_id, false),
+            ],
+            data,
+        }
+    }
+
+    // Admin-only market setter (UpdateMarket accounts)
+    fn update_market_ix(&self, name: &str, args: &[u8]) -> Instruction {
+        let mut data = anchor_discriminator("global", name).to_vec();
+        data.extend_from_slice(args);
+        Instruction {
+            program_id: self.program_id,
+            accounts: vec![
+                AccountMeta::new_readonly(self.admin.pubkey(), true),
+                AccountMeta::new(self.market_pda, false),
+                AccountMeta::new_readonly(event_authority_pda(&self.program_id), false),
+                AccountMeta::new_readonly(self.program_id, false),
+            ],
+            data,
+        }
+    }
+}
+
 #[test]
 fn init_market() {
     // Create the test environment
@@ -139,6 +542,30 @@ fn test_create_collection() {
     // Fund the payer
     svm.airdrop(&payer.pubkey(), 10_000_000_000).unwrap();
 
+    // The market sequences every event, so it must exist first
+    let (market_pda, _) = Pubkey::find_program_address(&[b"market"], &program_id);
+    let mut init_market_data = Vec::new();
+    init_market_data.extend_from_slice(&anchor_discriminator("global", "init_market"));
+    init_market_data.extend_from_slice(&payer.pubkey().to_bytes()); // fee_recipient
+    init_market_data.extend_from_slice(&200u64.to_le_bytes());     // taker_fee_bps
+
+    let init_market_ix = Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer.pubkey(), true),
+            AccountMeta::new(market_pda, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ],
+        data: init_market_data,
+    };
+    let tx = Transaction::new_signed_with_payer(
+        &[init_market_ix],
+        Some(&payer.pubkey()),
+        &[&payer],
+        svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).expect("Market initialization should succeed");
+
     // Collection parameters
     let name = "My NFT Collection";
     let uri = "https://example.com/collection.json";
@@ -160,7 +587,7 @@ fn test_create_collection() {
     data.extend_from_slice(&(uri_bytes.len() as u32).to_le_bytes());
     data.extend_from_slice(uri_bytes);
 
-    // Create instruction (no market/fee_recipient needed)
+    // Create instruction (market only sequences the event)
     let instruction = Instruction {
         program_id,
         accounts: vec![
@@ -169,6 +596,9 @@ fn test_create_collection() {
             AccountMeta::new(payer.pubkey(), true),                   // payer (signer)
             AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),      // system_program
             AccountMeta::new_readonly(mpl_core_id, false),            // mpl_core_program
+            AccountMeta::new(market_pda, false),                 // market
+            AccountMeta::new_readonly(event_authority_pda(&program_id), false), // event_authority
+            AccountMeta::new_readonly(program_id, false),        // program
         ],
         data,
     };
@@ -279,6 +709,9 @@ fn test_add_listing() {
             AccountMeta::new(seller.pubkey(), true),
             AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
             AccountMeta::new_readonly(mpl_core_id, false),
+            AccountMeta::new(market_pda, false),
+            AccountMeta::new_readonly(event_authority_pda(&program_id), false),
+            AccountMeta::new_readonly(program_id, false),
         ],
         data: create_collection_data,
     };
@@ -365,17 +798,28 @@ fn test_add_listing() {
     let mut add_listing_data = Vec::new();
     add_listing_data.extend_from_slice(&anchor_discriminator("global", "add_listing"));
     add_listing_data.extend_from_slice(&listing_price.to_le_bytes());
+    add_listing_data.push(0); // allowlist_root: None
+    add_listing_data.push(0); // reserved_buyer: None
+    add_listing_data.push(0); // mode: Escrow
+
+    let (collection_config_pda, _) = Pubkey::find_program_address(
+        &[b"collection_config", collection.pubkey().as_ref()],
+        &program_id,
+    );
 
     let add_listing_ix = Instruction {
         program_id,
         accounts: vec![
             AccountMeta::new(seller.pubkey(), true),           // seller
             AccountMeta::new(listing_pda, false),              // listing PDA
-            AccountMeta::new_readonly(market_pda, false),      // market
+            AccountMeta::new(market_pda, false),               // market
             AccountMeta::new_readonly(collection.pubkey(), false), // collection
+            AccountMeta::new_readonly(collection_config_pda, false), // collection_config (not initialized)
             AccountMeta::new(asset.pubkey(), false),           // asset (mutable for transfer)
             AccountMeta::new_readonly(mpl_core_id, false),     // mpl_core_program
             AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false), // system_program
+            AccountMeta::new_readonly(event_authority_pda(&program_id), false), // event_authority
+            AccountMeta::new_readonly(program_id, false),        // program
         ],
         data: add_listing_data,
     };
@@ -489,6 +933,9 @@ fn test_match_listing() {
             AccountMeta::new(seller.pubkey(), true),
             AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
             AccountMeta::new_readonly(mpl_core_id, false),
+            AccountMeta::new(market_pda, false),
+            AccountMeta::new_readonly(event_authority_pda(&program_id), false),
+            AccountMeta::new_readonly(program_id, false),
         ],
         data: create_collection_data,
     };
@@ -560,17 +1007,28 @@ fn test_match_listing() {
     let mut add_listing_data = Vec::new();
     add_listing_data.extend_from_slice(&anchor_discriminator("global", "add_listing"));
     add_listing_data.extend_from_slice(&listing_price.to_le_bytes());
+    add_listing_data.push(0); // allowlist_root: None
+    add_listing_data.push(0); // reserved_buyer: None
+    add_listing_data.push(0); // mode: Escrow
+
+    let (collection_config_pda, _) = Pubkey::find_program_address(
+        &[b"collection_config", collection.pubkey().as_ref()],
+        &program_id,
+    );
 
     let add_listing_ix = Instruction {
         program_id,
         accounts: vec![
             AccountMeta::new(seller.pubkey(), true),
             AccountMeta::new(listing_pda, false),
-            AccountMeta::new_readonly(market_pda, false),
+            AccountMeta::new(market_pda, false),
             AccountMeta::new_readonly(collection.pubkey(), false),
+            AccountMeta::new_readonly(collection_config_pda, false),
             AccountMeta::new(asset.pubkey(), false),           // asset must be mutable for transfer
             AccountMeta::new_readonly(mpl_core_id, false),     // mpl_core_program
             AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(event_authority_pda(&program_id), false), // event_authority
+            AccountMeta::new_readonly(program_id, false),        // program
         ],
         data: add_listing_data,
     };
@@ -596,19 +1054,50 @@ fn test_match_listing() {
     // Step 5: Match listing (purchase)
     let mut match_listing_data = Vec::new();
     match_listing_data.extend_from_slice(&anchor_discriminator("global", "match_listing"));
+    match_listing_data.extend_from_slice(&0u32.to_le_bytes()); // proof: empty vec
+    match_listing_data.push(0); // relist_price: None
+    match_listing_data.extend_from_slice(&u64::MAX.to_le_bytes()); // max_total: no cap
+
+    let (collection_stats_pda, _) = Pubkey::find_program_address(
+        &[b"collection_stats", collection.pubkey().as_ref()],
+        &program_id,
+    );
+    let (buyer_stats_pda, _) = Pubkey::find_program_address(
+        &[b"trader_stats", buyer.pubkey().as_ref()],
+        &program_id,
+    );
+    let (seller_stats_pda, _) = Pubkey::find_program_address(
+        &[b"trader_stats", seller.pubkey().as_ref()],
+        &program_id,
+    );
+    let (reward_config_pda, _) = Pubkey::find_program_address(
+        &[b"reward_config", market_pda.as_ref()],
+        &program_id,
+    );
 
     let match_listing_ix = Instruction {
         program_id,
         accounts: vec![
             AccountMeta::new(buyer.pubkey(), true),            // buyer
+            AccountMeta::new(buyer.pubkey(), true),            // payer (unsponsored, buyer pays rent)
             AccountMeta::new(seller.pubkey(), false),          // seller (receives payment, no signature needed)
             AccountMeta::new(listing_pda, false),              // listing
-            AccountMeta::new_readonly(market_pda, false),      // market
+            AccountMeta::new(market_pda, false),               // market
             AccountMeta::new(fee_recipient.pubkey(), false),   // fee_recipient
             AccountMeta::new(asset.pubkey(), false),           // asset
             AccountMeta::new_readonly(collection.pubkey(), false), // collection
             AccountMeta::new_readonly(mpl_core_id, false),     // mpl_core_program
             AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false), // system_program
+            AccountMeta::new(collection_stats_pda, false),     // collection_stats (created on first sale)
+            AccountMeta::new(buyer_stats_pda, false),          // buyer trader_stats
+            AccountMeta::new(seller_stats_pda, false),         // seller trader_stats
+            AccountMeta::new_readonly(reward_config_pda, false), // reward_config (not configured)
+            AccountMeta::new_readonly(program_id, false),        // price_feed (None, lamport-priced listing)
+            AccountMeta::new_readonly(collection_config_pda, false), // collection_config (not configured)
+            AccountMeta::new(program_id, false),                 // relist (None, NFT goes to buyer)
+            AccountMeta::new_readonly(program_id, false),        // relayer_approval (None, no relayer)
+            AccountMeta::new_readonly(event_authority_pda(&program_id), false), // event_authority
+            AccountMeta::new_readonly(program_id, false),        // program
         ],
         data: match_listing_data,
     };
@@ -739,6 +1228,9 @@ fn test_remove_listing() {
             AccountMeta::new(seller.pubkey(), true),
             AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
             AccountMeta::new_readonly(mpl_core_id, false),
+            AccountMeta::new(market_pda, false),
+            AccountMeta::new_readonly(event_authority_pda(&program_id), false),
+            AccountMeta::new_readonly(program_id, false),
         ],
         data: create_collection_data,
     };
@@ -810,17 +1302,28 @@ fn test_remove_listing() {
     let mut add_listing_data = Vec::new();
     add_listing_data.extend_from_slice(&anchor_discriminator("global", "add_listing"));
     add_listing_data.extend_from_slice(&listing_price.to_le_bytes());
+    add_listing_data.push(0); // allowlist_root: None
+    add_listing_data.push(0); // reserved_buyer: None
+    add_listing_data.push(0); // mode: Escrow
+
+    let (collection_config_pda, _) = Pubkey::find_program_address(
+        &[b"collection_config", collection.pubkey().as_ref()],
+        &program_id,
+    );
 
     let add_listing_ix = Instruction {
         program_id,
         accounts: vec![
             AccountMeta::new(seller.pubkey(), true),
             AccountMeta::new(listing_pda, false),
-            AccountMeta::new_readonly(market_pda, false),
+            AccountMeta::new(market_pda, false),
             AccountMeta::new_readonly(collection.pubkey(), false),
+            AccountMeta::new_readonly(collection_config_pda, false),
             AccountMeta::new(asset.pubkey(), false),
             AccountMeta::new_readonly(mpl_core_id, false),
             AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(event_authority_pda(&program_id), false),
+            AccountMeta::new_readonly(program_id, false),
         ],
         data: add_listing_data,
     };
@@ -851,6 +1354,9 @@ fn test_remove_listing() {
             AccountMeta::new(asset.pubkey(), false),           // asset
             AccountMeta::new_readonly(mpl_core_id, false),     // mpl_core_program
             AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false), // system_program
+            AccountMeta::new(market_pda, false),                 // market
+            AccountMeta::new_readonly(event_authority_pda(&program_id), false), // event_authority
+            AccountMeta::new_readonly(program_id, false),        // program
         ],
         data: remove_listing_data,
     };
@@ -882,4 +1388,2582 @@ fn test_remove_listing() {
     let listing_account = svm.get_account(&listing_pda);
     assert!(listing_account.is_none(), "Listing account should be closed and rent returned");
     println!(" Listing account closed and rent returned to seller");
-}
\ No newline at end of file
+}
+#[test]
+fn test_launchpad_drop() {
+    let mut svm = LiteSVM::new();
+
+    let program_id = Pubkey::new_from_array(nft_marketplace::ID.to_bytes());
+    let program_bytes = include_bytes!("../../../target/deploy/nft_marketplace.so");
+    svm.add_program(program_id, program_bytes).unwrap();
+
+    // MPL Core program ID
+    let mpl_core_id = Pubkey::new_from_array(get_mpl_core_id());
+    let mpl_core_bytes = include_bytes!("mpl_core.so");
+    svm.add_program(mpl_core_id, mpl_core_bytes).unwrap();
+
+    let admin = Keypair::new();
+    let fee_recipient = Keypair::new();
+    let creator = Keypair::new();
+    let collection = Keypair::new();
+    let buyer = Keypair::new();
+    let outsider = Keypair::new();
+
+    // Fund accounts
+    svm.airdrop(&admin.pubkey(), 10_000_000_000).unwrap();
+    svm.airdrop(&creator.pubkey(), 10_000_000_000).unwrap();
+    svm.airdrop(&buyer.pubkey(), 10_000_000_000).unwrap();
+    svm.airdrop(&outsider.pubkey(), 10_000_000_000).unwrap();
+
+    let taker_fee_bps: u64 = 200; // 2% taker fee
+
+    // Step 1: Initialize market
+    let (market_pda, _) = Pubkey::find_program_address(&[b"market"], &program_id);
+
+    let mut init_market_data = Vec::new();
+    init_market_data.extend_from_slice(&anchor_discriminator("global", "init_market"));
+    init_market_data.extend_from_slice(&fee_recipient.pubkey().to_bytes());
+    init_market_data.extend_from_slice(&taker_fee_bps.to_le_bytes());
+
+    let init_market_ix = Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(admin.pubkey(), true),
+            AccountMeta::new(market_pda, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ],
+        data: init_market_data,
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[init_market_ix],
+        Some(&admin.pubkey()),
+        &[&admin],
+        svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).expect("Market initialization should succeed");
+
+    // Step 2: Create drop (allowlist phase open, public phase far in the future)
+    // A single-leaf tree: root is the buyer's leaf and the proof is empty
+    let mut hasher = solana_sdk::hash::Hasher::default();
+    hasher.hash(buyer.pubkey().as_ref());
+    let allowlist_root = hasher.result().to_bytes();
+
+    let drop_name = "Genesis";
+    let drop_uri = "https://example.com/genesis";
+    let drop_price: u64 = 500_000_000; // 0.5 SOL
+
+    let (drop_pda, _) = Pubkey::find_program_address(
+        &[b"drop", collection.pubkey().as_ref()],
+        &program_id,
+    );
+
+    let mut create_drop_data = Vec::new();
+    create_drop_data.extend_from_slice(&anchor_discriminator("global", "create_drop"));
+    create_drop_data.extend_from_slice(&(drop_name.len() as u32).to_le_bytes());
+    create_drop_data.extend_from_slice(drop_name.as_bytes());
+    create_drop_data.extend_from_slice(&(drop_uri.len() as u32).to_le_bytes());
+    create_drop_data.extend_from_slice(drop_uri.as_bytes());
+    create_drop_data.extend_from_slice(&drop_price.to_le_bytes());  // price
+    create_drop_data.extend_from_slice(&10u64.to_le_bytes());       // max_supply
+    create_drop_data.extend_from_slice(&1u64.to_le_bytes());        // wallet_limit
+    create_drop_data.push(1);                                       // allowlist_root: Some
+    create_drop_data.extend_from_slice(&allowlist_root);
+    create_drop_data.extend_from_slice(&0i64.to_le_bytes());        // allowlist_start
+    create_drop_data.extend_from_slice(&i64::MAX.to_le_bytes());    // public_start
+
+    let create_drop_ix = Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(creator.pubkey(), true),          // creator
+            AccountMeta::new(collection.pubkey(), true),       // collection (new keypair)
+            AccountMeta::new(drop_pda, false),                 // drop PDA
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false), // system_program
+            AccountMeta::new_readonly(mpl_core_id, false),     // mpl_core_program
+            AccountMeta::new(market_pda, false),                 // market
+            AccountMeta::new_readonly(event_authority_pda(&program_id), false), // event_authority
+            AccountMeta::new_readonly(program_id, false),        // program
+        ],
+        data: create_drop_data,
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[create_drop_ix],
+        Some(&creator.pubkey()),
+        &[&creator, &collection],
+        svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).expect("Drop creation should succeed");
+    println!(" Drop created: {}", drop_pda);
+
+    let creator_balance_before = svm.get_balance(&creator.pubkey()).unwrap();
+
+    // Step 3: Allowlisted buyer mints
+    let mint_drop_ix = |minter: &Pubkey, asset: &Pubkey| {
+        let (mint_record_pda, _) = Pubkey::find_program_address(
+            &[b"drop_mint", drop_pda.as_ref(), minter.as_ref()],
+            &program_id,
+        );
+
+        let mut data = Vec::new();
+        data.extend_from_slice(&anchor_discriminator("global", "mint_drop"));
+        data.extend_from_slice(&0u32.to_le_bytes()); // proof: empty vec
+
+        Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(*minter, true),                   // buyer
+                AccountMeta::new(*asset, true),                    // asset (new keypair)
+                AccountMeta::new(drop_pda, false),                 // drop
+                AccountMeta::new(mint_record_pda, false),          // mint_record
+                AccountMeta::new(market_pda, false),               // market
+                AccountMeta::new(creator.pubkey(), false),         // creator
+                AccountMeta::new(fee_recipient.pubkey(), false),   // fee_recipient
+                AccountMeta::new(collection.pubkey(), false),      // collection
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false), // system_program
+                AccountMeta::new_readonly(mpl_core_id, false),     // mpl_core_program
+                AccountMeta::new_readonly(event_authority_pda(&program_id), false), // event_authority
+                AccountMeta::new_readonly(program_id, false),        // program
+            ],
+            data,
+        }
+    };
+
+    // The drop PDA signs as collection update authority, so mpl-core must be the real program
+    let asset = Keypair::new();
+    let mut fake_program_ix = mint_drop_ix(&buyer.pubkey(), &asset.pubkey());
+    fake_program_ix.accounts[9] = AccountMeta::new_readonly(program_id, false);
+    let tx = Transaction::new_signed_with_payer(
+        &[fake_program_ix],
+        Some(&buyer.pubkey()),
+        &[&buyer, &asset],
+        svm.latest_blockhash(),
+    );
+    assert_anchor_error(svm.send_transaction(tx), "ConstraintAddress");
+
+    let tx = Transaction::new_signed_with_payer(
+        &[mint_drop_ix(&buyer.pubkey(), &asset.pubkey())],
+        Some(&buyer.pubkey()),
+        &[&buyer, &asset],
+        svm.latest_blockhash(),
+    );
+    let tx_result = svm.send_transaction(tx).expect("Allowlisted mint should succeed");
+    for log in &tx_result.logs {
+        println!("  {}", log);
+    }
+    assert!(svm.get_account(&asset.pubkey()).is_some(), "Asset should be minted");
+
+    let expected_fee = drop_price * taker_fee_bps / 10000;
+    let creator_balance_after = svm.get_balance(&creator.pubkey()).unwrap();
+    assert_eq!(
+        creator_balance_after - creator_balance_before,
+        drop_price - expected_fee,
+        "Creator should receive price minus marketplace fee"
+    );
+    assert_eq!(
+        svm.get_balance(&fee_recipient.pubkey()).unwrap(),
+        expected_fee,
+        "Fee recipient should receive the marketplace fee"
+    );
+
+    // Step 4: Wallet limit of 1 rejects a second mint
+    let second_asset = Keypair::new();
+    let tx = Transaction::new_signed_with_payer(
+        &[mint_drop_ix(&buyer.pubkey(), &second_asset.pubkey())],
+        Some(&buyer.pubkey()),
+        &[&buyer, &second_asset],
+        svm.latest_blockhash(),
+    );
+    assert!(svm.send_transaction(tx).is_err(), "Second mint should exceed wallet limit");
+
+    // Step 5: Wallet outside the allowlist is rejected
+    let outsider_asset = Keypair::new();
+    let tx = Transaction::new_signed_with_payer(
+        &[mint_drop_ix(&outsider.pubkey(), &outsider_asset.pubkey())],
+        Some(&outsider.pubkey()),
+        &[&outsider, &outsider_asset],
+        svm.latest_blockhash(),
+    );
+    assert!(svm.send_transaction(tx).is_err(), "Non-allowlisted mint should fail");
+    println!(" Allowlist and wallet limit enforced");
+}
+
+#[test]
+fn test_gated_listing() {
+    let mut t = TestMarket::new(200);
+
+    let seller = t.wallet();
+    let buyer = t.wallet();
+    let outsider = t.wallet();
+
+    let (collection, update_authority) = t.create_collection(&seller);
+    let asset = t.mint_asset(&seller, &collection.pubkey(), &update_authority);
+
+    // Two-leaf tree: [buyer, pass holder], proof for buyer is the sibling leaf
+    let pass_holder = Keypair::new();
+    let buyer_leaf = wallet_leaf(&buyer.pubkey());
+    let sibling_leaf = wallet_leaf(&pass_holder.pubkey());
+    let mut hasher = solana_sdk::hash::Hasher::default();
+    if buyer_leaf <= sibling_leaf {
+        hasher.hashv(&[&buyer_leaf, &sibling_leaf]);
+    } else {
+        hasher.hashv(&[&sibling_leaf, &buyer_leaf]);
+    }
+    let root = hasher.result().to_bytes();
+
+    let add_listing_ix = t.add_listing_ix(
+        &seller.pubkey(),
+        &collection.pubkey(),
+        &asset.pubkey(),
+        1_000_000_000,
+        ListingOptions { allowlist_root: Some(root), ..Default::default() },
+    );
+    t.send(&[add_listing_ix], &seller, &[]).expect("Gated listing should succeed");
+    println!(" Gated listing created");
+
+    // Outsider reusing the buyer's proof is rejected
+    let outsider_ix = t.match_listing_ix(
+        &outsider.pubkey(),
+        &seller.pubkey(),
+        &collection.pubkey(),
+        &asset.pubkey(),
+        &[sibling_leaf],
+    );
+    assert!(t.send(&[outsider_ix], &outsider, &[]).is_err(), "Outsider purchase should fail");
+
+    // Allowlisted buyer with a valid proof succeeds
+    let buyer_ix = t.match_listing_ix(
+        &buyer.pubkey(),
+        &seller.pubkey(),
+        &collection.pubkey(),
+        &asset.pubkey(),
+        &[sibling_leaf],
+    );
+    t.send(&[buyer_ix], &buyer, &[]).expect("Allowlisted purchase should succeed");
+    println!(" Allowlisted buyer purchased gated listing");
+}
+
+#[test]
+fn test_market_early_access() {
+    let mut t = TestMarket::new(200);
+
+    let seller = t.wallet();
+    let buyer = t.wallet();
+    let outsider = t.wallet();
+
+    let (collection, update_authority) = t.create_collection(&seller);
+    let asset = t.mint_asset(&seller, &collection.pubkey(), &update_authority);
+
+    // Admin opens an early access period for the buyer only
+    let mut data = Vec::new();
+    data.extend_from_slice(&anchor_discriminator("global", "set_market_allowlist"));
+    data.push(1);
+    data.extend_from_slice(&wallet_leaf(&buyer.pubkey()));
+    data.extend_from_slice(&i64::MAX.to_le_bytes()); // early_access_ends_at
+
+    let set_allowlist_ix = Instruction {
+        program_id: t.program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(t.admin.pubkey(), true),
+            AccountMeta::new(t.market_pda, false),
+            AccountMeta::new_readonly(event_authority_pda(&t.program_id), false),
+            AccountMeta::new_readonly(t.program_id, false),
+        ],
+        data,
+    };
+    let admin = t.admin.insecure_clone();
+    t.send(&[set_allowlist_ix], &admin, &[]).expect("Admin should set market allowlist");
+    // Market: ..., event_seq @120
+    let event_seq = u64::from_le_bytes(t.svm.get_account(&t.market_pda).unwrap().data[120..128].try_into().unwrap());
+    assert_eq!(event_seq, 1, "MarketAllowlistUpdated");
+
+    // Listing without its own root still falls under early access
+    let add_listing_ix = t.add_listing_ix(
+        &seller.pubkey(),
+        &collection.pubkey(),
+        &asset.pubkey(),
+        1_000_000_000,
+        ListingOptions::default(),
+    );
+    t.send(&[add_listing_ix], &seller, &[]).expect("Listing should succeed");
+
+    let outsider_ix = t.match_listing_ix(
+        &outsider.pubkey(),
+        &seller.pubkey(),
+        &collection.pubkey(),
+        &asset.pubkey(),
+        &[],
+    );
+    assert!(t.send(&[outsider_ix], &outsider, &[]).is_err(), "Outsider should wait for public access");
+
+    let buyer_ix = t.match_listing_ix(
+        &buyer.pubkey(),
+        &seller.pubkey(),
+        &collection.pubkey(),
+        &asset.pubkey(),
+        &[],
+    );
+    t.send(&[buyer_ix], &buyer, &[]).expect("Early access buyer should purchase");
+    println!(" Early access enforced by market allowlist");
+}
+
+#[test]
+fn test_reserved_buyer_listing() {
+    let mut t = TestMarket::new(200);
+
+    let seller = t.wallet();
+    let buyer = t.wallet();
+    let outsider = t.wallet();
+
+    let (collection, update_authority) = t.create_collection(&seller);
+    let asset = t.mint_asset(&seller, &collection.pubkey(), &update_authority);
+
+    // Seller reserves the listing for a pre-negotiated buyer
+    let add_listing_ix = t.add_listing_ix(
+        &seller.pubkey(),
+        &collection.pubkey(),
+        &asset.pubkey(),
+        2_000_000_000,
+        ListingOptions { reserved_buyer: Some(buyer.pubkey()), ..Default::default() },
+    );
+    t.send(&[add_listing_ix], &seller, &[]).expect("Reserved listing should succeed");
+
+    let outsider_ix = t.match_listing_ix(
+        &outsider.pubkey(),
+        &seller.pubkey(),
+        &collection.pubkey(),
+        &asset.pubkey(),
+        &[],
+    );
+    assert!(t.send(&[outsider_ix], &outsider, &[]).is_err(), "Only the reserved buyer can purchase");
+
+    let buyer_ix = t.match_listing_ix(
+        &buyer.pubkey(),
+        &seller.pubkey(),
+        &collection.pubkey(),
+        &asset.pubkey(),
+        &[],
+    );
+    t.send(&[buyer_ix], &buyer, &[]).expect("Reserved buyer purchase should succeed");
+    println!(" Reserved listing settled with the negotiated buyer");
+}
+
+#[test]
+fn test_swap_assets() {
+    let mut t = TestMarket::new(200);
+
+    let maker = t.wallet();
+    let taker = t.wallet();
+
+    let (collection_a, authority_a) = t.create_collection(&maker);
+    let (collection_b, authority_b) = t.create_collection(&taker);
+    let maker_asset = t.mint_asset(&maker, &collection_a.pubkey(), &authority_a);
+    let taker_asset = t.mint_asset(&taker, &collection_b.pubkey(), &authority_b);
+
+    let seed: u64 = 1;
+    let offered_lamports: u64 = 100_000_000; // 0.1 SOL sweetener
+    let (swap_pda, _) = Pubkey::find_program_address(
+        &[b"swap", maker.pubkey().as_ref(), &seed.to_le_bytes()],
+        &t.program_id,
+    );
+
+    // Step 1: Maker escrows their asset + lamports, asking for the taker's asset
+    let mut create_swap_data = Vec::new();
+    create_swap_data.extend_from_slice(&anchor_discriminator("global", "create_swap"));
+    create_swap_data.extend_from_slice(&seed.to_le_bytes());
+    create_swap_data.extend_from_slice(&taker.pubkey().to_bytes());
+    create_swap_data.extend_from_slice(&1u32.to_le_bytes()); // requested_assets len
+    create_swap_data.extend_from_slice(&taker_asset.pubkey().to_bytes());
+    create_swap_data.extend_from_slice(&offered_lamports.to_le_bytes());
+
+    let create_swap_ix = Instruction {
+        program_id: t.program_id,
+        accounts: vec![
+            AccountMeta::new(maker.pubkey(), true),              // maker
+            AccountMeta::new(swap_pda, false),                   // swap PDA
+            AccountMeta::new_readonly(t.mpl_core_id, false),     // mpl_core_program
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false), // system_program
+            AccountMeta::new(t.market_pda, false),               // market
+            AccountMeta::new_readonly(event_authority_pda(&t.program_id), false), // event_authority
+            AccountMeta::new_readonly(t.program_id, false),      // program
+            AccountMeta::new(maker_asset.pubkey(), false),       // offered asset
+            AccountMeta::new_readonly(collection_a.pubkey(), false), // offered asset collection
+        ],
+        data: create_swap_data,
+    };
+    t.send(&[create_swap_ix], &maker, &[]).expect("Swap creation should succeed");
+    assert_eq!(t.asset_owner(&maker_asset.pubkey()), swap_pda, "Offered asset should be in escrow");
+    println!(" Swap created: {}", swap_pda);
+
+    let taker_balance_before = t.svm.get_balance(&taker.pubkey()).unwrap();
+
+    // Step 2: Taker completes the swap
+    let mut accept_swap_data = Vec::new();
+    accept_swap_data.extend_from_slice(&anchor_discriminator("global", "accept_swap"));
+
+    let accept_swap_ix = Instruction {
+        program_id: t.program_id,
+        accounts: vec![
+            AccountMeta::new(taker.pubkey(), true),              // taker
+            AccountMeta::new(maker.pubkey(), false),             // maker
+            AccountMeta::new(swap_pda, false),                   // swap PDA
+            AccountMeta::new_readonly(t.mpl_core_id, false),     // mpl_core_program
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false), // system_program
+            AccountMeta::new(t.market_pda, false),               // market
+            AccountMeta::new_readonly(event_authority_pda(&t.program_id), false), // event_authority
+            AccountMeta::new_readonly(t.program_id, false),      // program
+            AccountMeta::new(maker_asset.pubkey(), false),       // offered asset
+            AccountMeta::new_readonly(collection_a.pubkey(), false),
+            AccountMeta::new(taker_asset.pubkey(), false),       // requested asset
+            AccountMeta::new_readonly(collection_b.pubkey(), false),
+        ],
+        data: accept_swap_data,
+    };
+    t.send(&[accept_swap_ix], &taker, &[]).expect("Swap acceptance should succeed");
+
+    assert_eq!(t.asset_owner(&maker_asset.pubkey()), taker.pubkey(), "Taker should own the offered asset");
+    assert_eq!(t.asset_owner(&taker_asset.pubkey()), maker.pubkey(), "Maker should own the requested asset");
+
+    // Taker paid only the transaction fee, and received the sweetener
+    let taker_balance_after = t.svm.get_balance(&taker.pubkey()).unwrap();
+    assert!(taker_balance_after > taker_balance_before, "Taker should receive offered lamports");
+    assert!(t.svm.get_account(&swap_pda).is_none(), "Swap account should be closed");
+    println!(" Swap completed atomically");
+}
+
+#[test]
+fn test_cancel_swap() {
+    let mut t = TestMarket::new(200);
+
+    let maker = t.wallet();
+    let taker = Keypair::new();
+
+    let (collection, update_authority) = t.create_collection(&maker);
+    let maker_asset = t.mint_asset(&maker, &collection.pubkey(), &update_authority);
+    let wanted_asset = Keypair::new();
+
+    let seed: u64 = 7;
+    let (swap_pda, _) = Pubkey::find_program_address(
+        &[b"swap", maker.pubkey().as_ref(), &seed.to_le_bytes()],
+        &t.program_id,
+    );
+
+    let mut create_swap_data = Vec::new();
+    create_swap_data.extend_from_slice(&anchor_discriminator("global", "create_swap"));
+    create_swap_data.extend_from_slice(&seed.to_le_bytes());
+    create_swap_data.extend_from_slice(&taker.pubkey().to_bytes());
+    create_swap_data.extend_from_slice(&1u32.to_le_bytes());
+    create_swap_data.extend_from_slice(&wanted_asset.pubkey().to_bytes());
+    create_swap_data.extend_from_slice(&500_000_000u64.to_le_bytes());
+
+    let create_swap_ix = Instruction {
+        program_id: t.program_id,
+        accounts: vec![
+            AccountMeta::new(maker.pubkey(), true),
+            AccountMeta::new(swap_pda, false),
+            AccountMeta::new_readonly(t.mpl_core_id, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new(t.market_pda, false),
+            AccountMeta::new_readonly(event_authority_pda(&t.program_id), false),
+            AccountMeta::new_readonly(t.program_id, false),
+            AccountMeta::new(maker_asset.pubkey(), false),
+            AccountMeta::new_readonly(collection.pubkey(), false),
+        ],
+        data: create_swap_data,
+    };
+    t.send(&[create_swap_ix], &maker, &[]).expect("Swap creation should succeed");
+
+    let maker_balance_before = t.svm.get_balance(&maker.pubkey()).unwrap();
+
+    let mut cancel_swap_data = Vec::new();
+    cancel_swap_data.extend_from_slice(&anchor_discriminator("global", "cancel_swap"));
+
+    let cancel_swap_ix = Instruction {
+        program_id: t.program_id,
+        accounts: vec![
+            AccountMeta::new(maker.pubkey(), true),              // maker
+            AccountMeta::new(swap_pda, false),                   // swap PDA
+            AccountMeta::new_readonly(t.mpl_core_id, false),     // mpl_core_program
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false), // system_program
+            AccountMeta::new(t.market_pda, false),               // market
+            AccountMeta::new_readonly(event_authority_pda(&t.program_id), false), // event_authority
+            AccountMeta::new_readonly(t.program_id, false),      // program
+            AccountMeta::new(maker_asset.pubkey(), false),
+            AccountMeta::new_readonly(collection.pubkey(), false),
+        ],
+        data: cancel_swap_data,
+    };
+    t.send(&[cancel_swap_ix], &maker, &[]).expect("Swap cancellation should succeed");
+
+    assert_eq!(t.asset_owner(&maker_asset.pubkey()), maker.pubkey(), "Asset should return to maker");
+    let maker_balance_after = t.svm.get_balance(&maker.pubkey()).unwrap();
+    assert!(
+        maker_balance_after > maker_balance_before + 500_000_000 - 10_000,
+        "Escrowed lamports and rent should return to maker"
+    );
+    assert!(t.svm.get_account(&swap_pda).is_none(), "Swap account should be closed");
+    println!(" Swap canceled and escrow returned");
+}
+
+#[test]
+fn test_delegated_listing() {
+    let mut t = TestMarket::new(200);
+
+    let seller = t.wallet();
+    let buyer = t.wallet();
+
+    let (collection, update_authority) = t.create_collection(&seller);
+    let asset = t.mint_asset(&seller, &collection.pubkey(), &update_authority);
+    let listing_price: u64 = 1_000_000_000;
+
+    // Non-custodial listing: NFT stays in the seller's wallet
+    let add_listing_ix = t.add_listing_ix(
+        &seller.pubkey(),
+        &collection.pubkey(),
+        &asset.pubkey(),
+        listing_price,
+        ListingOptions { delegated: true, ..Default::default() },
+    );
+    t.send(&[add_listing_ix], &seller, &[]).expect("Delegated listing should succeed");
+    assert_eq!(t.asset_owner(&asset.pubkey()), seller.pubkey(), "Seller should keep the NFT while listed");
+    println!(" Delegated listing created, NFT frozen in seller wallet");
+
+    let seller_balance_before = t.svm.get_balance(&seller.pubkey()).unwrap();
+
+    // Purchase thaws and transfers atomically
+    let match_listing_ix = t.match_listing_ix(
+        &buyer.pubkey(),
+        &seller.pubkey(),
+        &collection.pubkey(),
+        &asset.pubkey(),
+        &[],
+    );
+    t.send(&[match_listing_ix], &buyer, &[]).expect("Purchase should succeed");
+
+    assert_eq!(t.asset_owner(&asset.pubkey()), buyer.pubkey(), "Buyer should own the NFT");
+    let seller_balance_after = t.svm.get_balance(&seller.pubkey()).unwrap();
+    assert_eq!(seller_balance_after - seller_balance_before, listing_price, "Seller should receive the price");
+
+    // The listing PDA's delegates must not follow the NFT into the buyer's wallet
+    let account = t.svm.get_account(&asset.pubkey()).expect("Asset should exist");
+    let sold_asset = mpl_core::Asset::deserialize(&account.data).unwrap();
+    assert!(sold_asset.plugin_list.freeze_delegate.is_none(), "Freeze delegate should be removed");
+    assert!(sold_asset.plugin_list.transfer_delegate.is_none(), "Transfer delegate should be removed");
+    println!(" Delegated listing sold");
+}
+
+#[test]
+fn test_remove_delegated_listing() {
+    let mut t = TestMarket::new(200);
+
+    let seller = t.wallet();
+    let (collection, update_authority) = t.create_collection(&seller);
+    let asset = t.mint_asset(&seller, &collection.pubkey(), &update_authority);
+
+    let add_listing_ix = t.add_listing_ix(
+        &seller.pubkey(),
+        &collection.pubkey(),
+        &asset.pubkey(),
+        1_000_000_000,
+        ListingOptions { delegated: true, ..Default::default() },
+    );
+    t.send(&[add_listing_ix], &seller, &[]).expect("Delegated listing should succeed");
+
+    let listing_pda = t.listing_pda(&seller.pubkey(), &collection.pubkey(), &asset.pubkey());
+
+    let mut remove_listing_data = Vec::new();
+    remove_listing_data.extend_from_slice(&anchor_discriminator("global", "remove_listing"));
+
+    let remove_listing_ix = Instruction {
+        program_id: t.program_id,
+        accounts: vec![
+            AccountMeta::new(seller.pubkey(), true),
+            AccountMeta::new(listing_pda, false),
+            AccountMeta::new_readonly(collection.pubkey(), false),
+            AccountMeta::new(asset.pubkey(), false),
+            AccountMeta::new_readonly(t.mpl_core_id, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new(t.market_pda, false),
+            AccountMeta::new_readonly(event_authority_pda(&t.program_id), false),
+            AccountMeta::new_readonly(t.program_id, false),
+        ],
+        data: remove_listing_data,
+    };
+    t.send(&[remove_listing_ix], &seller, &[]).expect("Delegated listing removal should succeed");
+
+    assert!(t.svm.get_account(&listing_pda).is_none(), "Listing account should be closed");
+
+    // Seller can move the NFT freely again
+    let other_wallet = Keypair::new();
+    let sp = |key: &Pubkey| solana_program::pubkey::Pubkey::new_from_array(key.to_bytes());
+    let transfer_ix = mpl_core::instructions::TransferV1Builder::new()
+        .asset(sp(&asset.pubkey()))
+        .collection(Some(sp(&collection.pubkey())))
+        .payer(sp(&seller.pubkey()))
+        .new_owner(sp(&other_wallet.pubkey()))
+        .instruction();
+    t.send(&[to_sdk_instruction(transfer_ix)], &seller, &[]).expect("Thawed NFT should transfer");
+    assert_eq!(t.asset_owner(&asset.pubkey()), other_wallet.pubkey());
+    println!(" Delegated listing removed and NFT unfrozen");
+}
+
+#[test]
+fn test_fee_rounding_policy() {
+    let mut t = TestMarket::new(200);
+
+    let seller = t.wallet();
+    let buyer = t.wallet();
+
+    let (collection, update_authority) = t.create_collection(&seller);
+    let asset = t.mint_asset(&seller, &collection.pubkey(), &update_authority);
+
+    // Admin switches to ceil rounding (FeeRounding::Ceil = 1) with no minimum fee
+    let mut data = Vec::new();
+    data.extend_from_slice(&anchor_discriminator("global", "set_fee_policy"));
+    data.push(1);                                 // fee_rounding: Ceil
+    data.extend_from_slice(&0u64.to_le_bytes());  // min_fee
+
+    let set_fee_policy_ix = Instruction {
+        program_id: t.program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(t.admin.pubkey(), true),
+            AccountMeta::new(t.market_pda, false),
+            AccountMeta::new_readonly(event_authority_pda(&t.program_id), false),
+            AccountMeta::new_readonly(t.program_id, false),
+        ],
+        data,
+    };
+    let admin = t.admin.insecure_clone();
+    // Market: ..., event_seq @120
+    let event_seq = |t: &TestMarket| {
+        u64::from_le_bytes(t.svm.get_account(&t.market_pda).unwrap().data[120..128].try_into().unwrap())
+    };
+    let seq_before = event_seq(&t);
+    t.send(&[set_fee_policy_ix], &admin, &[]).expect("Admin should set fee policy");
+    assert_eq!(event_seq(&t), seq_before + 1, "FeePolicyUpdated");
+
+    // 2% of 1_000_000_001 is 20_000_000.02 lamports
+    let listing_price: u64 = 1_000_000_001;
+    let add_listing_ix = t.add_listing_ix(
+        &seller.pubkey(),
+        &collection.pubkey(),
+        &asset.pubkey(),
+        listing_price,
+        ListingOptions::default(),
+    );
+    t.send(&[add_listing_ix], &seller, &[]).expect("Listing should succeed");
+
+    let match_listing_ix = t.match_listing_ix(
+        &buyer.pubkey(),
+        &seller.pubkey(),
+        &collection.pubkey(),
+        &asset.pubkey(),
+        &[],
+    );
+    t.send(&[match_listing_ix], &buyer, &[]).expect("Purchase should succeed");
+
+    assert_eq!(
+        t.svm.get_balance(&t.fee_recipient.pubkey()).unwrap(),
+        20_000_001,
+        "Fractional fee should round up"
+    );
+    println!(" Fee rounded up per market policy");
+}
+
+#[test]
+fn test_price_bounds() {
+    let mut t = TestMarket::new(200);
+
+    let seller = t.wallet();
+    let (collection, update_authority) = t.create_collection(&seller);
+    let asset = t.mint_asset(&seller, &collection.pubkey(), &update_authority);
+
+    // Market default rejects free listings
+    let free_listing_ix = t.add_listing_ix(
+        &seller.pubkey(),
+        &collection.pubkey(),
+        &asset.pubkey(),
+        0,
+        ListingOptions::default(),
+    );
+    assert!(t.send(&[free_listing_ix], &seller, &[]).is_err(), "Zero price listing should fail");
+
+    // Admin tightens market bounds to 0.1 - 10 SOL
+    let mut data = Vec::new();
+    data.extend_from_slice(&anchor_discriminator("global", "set_price_bounds"));
+    data.extend_from_slice(&100_000_000u64.to_le_bytes());
+    data.extend_from_slice(&10_000_000_000u64.to_le_bytes());
+
+    let set_price_bounds_ix = Instruction {
+        program_id: t.program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(t.admin.pubkey(), true),
+            AccountMeta::new(t.market_pda, false),
+            AccountMeta::new_readonly(event_authority_pda(&t.program_id), false),
+            AccountMeta::new_readonly(t.program_id, false),
+        ],
+        data,
+    };
+    let admin = t.admin.insecure_clone();
+    t.send(&[set_price_bounds_ix], &admin, &[]).expect("Admin should set price bounds");
+
+    let cheap_listing_ix = t.add_listing_ix(
+        &seller.pubkey(),
+        &collection.pubkey(),
+        &asset.pubkey(),
+        50_000_000,
+        ListingOptions::default(),
+    );
+    assert!(t.send(&[cheap_listing_ix], &seller, &[]).is_err(), "Listing below market minimum should fail");
+
+    // Collection override raises the floor to 1 SOL
+    let mut data = Vec::new();
+    data.extend_from_slice(&anchor_discriminator("global", "set_collection_price_bounds"));
+    data.extend_from_slice(&1_000_000_000u64.to_le_bytes());
+    data.extend_from_slice(&5_000_000_000u64.to_le_bytes());
+
+    let set_collection_bounds_ix = Instruction {
+        program_id: t.program_id,
+        accounts: vec![
+            AccountMeta::new(t.admin.pubkey(), true),                         // admin
+            AccountMeta::new(t.market_pda, false),                            // market
+            AccountMeta::new_readonly(collection.pubkey(), false),            // collection
+            AccountMeta::new(t.collection_config_pda(&collection.pubkey()), false), // collection_config
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),              // system_program
+            AccountMeta::new_readonly(event_authority_pda(&t.program_id), false), // event_authority
+            AccountMeta::new_readonly(t.program_id, false),      // program
+        ],
+        data,
+    };
+    t.send(&[set_collection_bounds_ix], &admin, &[]).expect("Admin should set collection bounds");
+
+    let below_floor_ix = t.add_listing_ix(
+        &seller.pubkey(),
+        &collection.pubkey(),
+        &asset.pubkey(),
+        500_000_000,
+        ListingOptions::default(),
+    );
+    assert!(t.send(&[below_floor_ix], &seller, &[]).is_err(), "Listing below collection floor should fail");
+
+    let valid_listing_ix = t.add_listing_ix(
+        &seller.pubkey(),
+        &collection.pubkey(),
+        &asset.pubkey(),
+        2_000_000_000,
+        ListingOptions::default(),
+    );
+    t.send(&[valid_listing_ix], &seller, &[]).expect("Listing within collection bounds should succeed");
+    println!(" Market and collection price bounds enforced");
+}
+
+#[test]
+fn test_collection_stats_oracle() {
+    let mut t = TestMarket::new(200);
+
+    let seller = t.wallet();
+    let buyer = t.wallet();
+
+    let (collection, update_authority) = t.create_collection(&seller);
+    let prices: [u64; 2] = [1_000_000_000, 3_000_000_000];
+
+    // Two sales in the same collection
+    for price in prices {
+        let asset = t.mint_asset(&seller, &collection.pubkey(), &update_authority);
+        let add_listing_ix = t.add_listing_ix(
+            &seller.pubkey(),
+            &collection.pubkey(),
+            &asset.pubkey(),
+            price,
+            ListingOptions::default(),
+        );
+        t.send(&[add_listing_ix], &seller, &[]).expect("Listing should succeed");
+
+        let match_listing_ix = t.match_listing_ix(
+            &buyer.pubkey(),
+            &seller.pubkey(),
+            &collection.pubkey(),
+            &asset.pubkey(),
+            &[],
+        );
+        t.send(&[match_listing_ix], &buyer, &[]).expect("Purchase should succeed");
+    }
+
+    // Read the oracle through the view instruction's return data
+    let mut data = Vec::new();
+    data.extend_from_slice(&anchor_discriminator("global", "get_collection_stats"));
+
+    let get_stats_ix = Instruction {
+        program_id: t.program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(t.collection_stats_pda(&collection.pubkey()), false),
+        ],
+        data,
+    };
+    let tx_result = t.send(&[get_stats_ix], &buyer, &[]).expect("Stats view should succeed");
+    let view = tx_result.return_data.data;
+
+    let collection_key = Pubkey::new_from_array(view[0..32].try_into().unwrap());
+    let last_price = u64::from_le_bytes(view[32..40].try_into().unwrap());
+    let volume = u128::from_le_bytes(view[40..56].try_into().unwrap());
+    let sale_count = u64::from_le_bytes(view[56..64].try_into().unwrap());
+
+    assert_eq!(collection_key, collection.pubkey());
+    assert_eq!(last_price, prices[1], "Last price should be the latest sale");
+    assert_eq!(volume, (prices[0] + prices[1]) as u128, "Volume should sum both sales");
+    assert_eq!(sale_count, 2);
+    println!(" Collection oracle: last price {}, volume {}, sales {}", last_price, volume, sale_count);
+}
+
+#[test]
+fn test_trader_stats() {
+    let mut t = TestMarket::new(200);
+
+    let seller = t.wallet();
+    let buyer = t.wallet();
+
+    let (collection, update_authority) = t.create_collection(&seller);
+    let asset = t.mint_asset(&seller, &collection.pubkey(), &update_authority);
+    let listing_price: u64 = 1_500_000_000;
+
+    let add_listing_ix = t.add_listing_ix(
+        &seller.pubkey(),
+        &collection.pubkey(),
+        &asset.pubkey(),
+        listing_price,
+        ListingOptions::default(),
+    );
+    t.send(&[add_listing_ix], &seller, &[]).expect("Listing should succeed");
+
+    // Seller cannot buy their own listing
+    let self_buy_ix = t.match_listing_ix(
+        &seller.pubkey(),
+        &seller.pubkey(),
+        &collection.pubkey(),
+        &asset.pubkey(),
+        &[],
+    );
+    assert!(t.send(&[self_buy_ix], &seller, &[]).is_err(), "Self trade should fail");
+
+    let match_listing_ix = t.match_listing_ix(
+        &buyer.pubkey(),
+        &seller.pubkey(),
+        &collection.pubkey(),
+        &asset.pubkey(),
+        &[],
+    );
+    t.send(&[match_listing_ix], &buyer, &[]).expect("Purchase should succeed");
+
+    // TraderStats layout: discriminator, wallet, volume_bought, volume_sold, trade_count
+    let read_stats = |t: &TestMarket, wallet: &Pubkey| {
+        let account = t.svm.get_account(&t.trader_stats_pda(wallet)).expect("Trader stats should exist");
+        let data = account.data;
+        (
+            Pubkey::new_from_array(data[8..40].try_into().unwrap()),
+            u64::from_le_bytes(data[40..48].try_into().unwrap()),
+            u64::from_le_bytes(data[48..56].try_into().unwrap()),
+            u64::from_le_bytes(data[56..64].try_into().unwrap()),
+        )
+    };
+
+    let (wallet, bought, sold, trades) = read_stats(&t, &buyer.pubkey());
+    assert_eq!((wallet, bought, sold, trades), (buyer.pubkey(), listing_price, 0, 1));
+
+    let (wallet, bought, sold, trades) = read_stats(&t, &seller.pubkey());
+    assert_eq!((wallet, bought, sold, trades), (seller.pubkey(), 0, listing_price, 1));
+    println!(" Trader stats updated for buyer and seller");
+}
+
+#[test]
+fn test_fee_tiers() {
+    let mut t = TestMarket::new(200);
+
+    let buyer = t.wallet();
+    let admin = t.admin.insecure_clone();
+
+    let set_fee_tiers_ix = |t: &TestMarket, tiers: &[(u64, u64, u64)]| {
+        let mut data = Vec::new();
+        data.extend_from_slice(&anchor_discriminator("global", "set_fee_tiers"));
+        data.extend_from_slice(&(tiers.len() as u32).to_le_bytes());
+        for (volume_threshold, taker_fee_bps, maker_fee_bps) in tiers {
+            data.extend_from_slice(&volume_threshold.to_le_bytes());
+            data.extend_from_slice(&taker_fee_bps.to_le_bytes());
+            data.extend_from_slice(&maker_fee_bps.to_le_bytes());
+        }
+        Instruction {
+            program_id: t.program_id,
+            accounts: vec![
+                AccountMeta::new_readonly(t.admin.pubkey(), true),
+                AccountMeta::new(t.market_pda, false),
+                AccountMeta::new_readonly(event_authority_pda(&t.program_id), false),
+                AccountMeta::new_readonly(t.program_id, false),
+            ],
+            data,
+        }
+    };
+
+    // Thresholds must be ascending
+    let invalid_ix = set_fee_tiers_ix(&t, &[(1_000_000_000, 50, 0), (0, 100, 50)]);
+    assert!(t.send(&[invalid_ix], &admin, &[]).is_err(), "Descending tiers should fail");
+
+    // New traders: 1% taker / 0.5% maker, 1 SOL+ volume: 0.5% taker / no maker fee
+    let valid_ix = set_fee_tiers_ix(&t, &[(0, 100, 50), (1_000_000_000, 50, 0)]);
+    t.send(&[valid_ix], &admin, &[]).expect("Admin should set fee tiers");
+
+    let listing_price: u64 = 2_000_000_000;
+    let sell = |t: &mut TestMarket| {
+        let seller = t.wallet();
+        let (collection, update_authority) = t.create_collection(&seller);
+        let asset = t.mint_asset(&seller, &collection.pubkey(), &update_authority);
+
+        let add_listing_ix = t.add_listing_ix(
+            &seller.pubkey(),
+            &collection.pubkey(),
+            &asset.pubkey(),
+            listing_price,
+            ListingOptions::default(),
+        );
+        t.send(&[add_listing_ix], &seller, &[]).expect("Listing should succeed");
+
+        let seller_before = t.svm.get_balance(&seller.pubkey()).unwrap();
+        let fee_recipient_before = t.svm.get_balance(&t.fee_recipient.pubkey()).unwrap_or(0);
+
+        let match_listing_ix = t.match_listing_ix(
+            &buyer.pubkey(),
+            &seller.pubkey(),
+            &collection.pubkey(),
+            &asset.pubkey(),
+            &[],
+        );
+        t.send(&[match_listing_ix], &buyer, &[]).expect("Purchase should succeed");
+
+        (
+            t.svm.get_balance(&seller.pubkey()).unwrap() - seller_before,
+            t.svm.get_balance(&t.fee_recipient.pubkey()).unwrap() - fee_recipient_before,
+        )
+    };
+
+    // First purchase: buyer and seller both in the base tier
+    let (seller_received, fees_collected) = sell(&mut t);
+    assert_eq!(seller_received, listing_price - 10_000_000, "Seller should pay 0.5% maker fee");
+    assert_eq!(fees_collected, 20_000_000 + 10_000_000, "Market should collect taker + maker fee");
+
+    // Second purchase: buyer now has 2 SOL volume, new seller is still base tier
+    let (seller_received, fees_collected) = sell(&mut t);
+    assert_eq!(seller_received, listing_price - 10_000_000, "New seller should pay 0.5% maker fee");
+    assert_eq!(fees_collected, 10_000_000 + 10_000_000, "Buyer should get the discounted taker fee");
+    println!(" Fee tiers applied by trader volume");
+}
+
+#[test]
+fn test_trading_rewards() {
+    let mut t = TestMarket::new(200);
+
+    let seller = t.wallet();
+    let buyer = t.wallet();
+    let admin = t.admin.insecure_clone();
+    let reward_config = t.reward_config_pda();
+
+    // Reward mint with the reward config PDA as mint authority (SPL Mint layout, 82 bytes)
+    let reward_mint = Pubkey::new_unique();
+    let mut mint_data = vec![0u8; 82];
+    mint_data[0..4].copy_from_slice(&1u32.to_le_bytes());   // mint_authority: Some
+    mint_data[4..36].copy_from_slice(reward_config.as_ref());
+    mint_data[44] = 6;                                        // decimals
+    mint_data[45] = 1;                                        // is_initialized
+    t.svm.set_account(
+        reward_mint,
+        solana_sdk::account::Account {
+            lamports: t.svm.minimum_balance_for_rent_exemption(82),
+            data: mint_data,
+            owner: token_program_id(),
+            executable: false,
+            rent_epoch: 0,
+        },
+    ).unwrap();
+
+    // 0.001 reward units per lamport of volume
+    let emission_rate: u64 = 1_000_000;
+    let mut data = Vec::new();
+    data.extend_from_slice(&anchor_discriminator("global", "set_reward_config"));
+    data.extend_from_slice(&emission_rate.to_le_bytes());
+    data.push(1); // enabled
+
+    let set_reward_config_ix = Instruction {
+        program_id: t.program_id,
+        accounts: vec![
+            AccountMeta::new(admin.pubkey(), true),                                   // admin
+            AccountMeta::new(t.market_pda, false),                                    // market
+            AccountMeta::new(reward_config, false),                                   // reward_config
+            AccountMeta::new_readonly(reward_mint, false),                            // reward_mint
+            AccountMeta::new(associated_token_address(&reward_config, &reward_mint), false), // reward_vault
+            AccountMeta::new_readonly(token_program_id(), false),                     // token_program
+            AccountMeta::new_readonly(associated_token_program_id(), false),          // associated_token_program
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),                      // system_program
+            AccountMeta::new_readonly(event_authority_pda(&t.program_id), false), // event_authority
+            AccountMeta::new_readonly(t.program_id, false),      // program
+        ],
+        data,
+    };
+    t.send(&[set_reward_config_ix], &admin, &[]).expect("Admin should configure rewards");
+
+    let (collection, update_authority) = t.create_collection(&seller);
+    let asset = t.mint_asset(&seller, &collection.pubkey(), &update_authority);
+    let listing_price: u64 = 2_000_000_000;
+
+    let add_listing_ix = t.add_listing_ix(
+        &seller.pubkey(),
+        &collection.pubkey(),
+        &asset.pubkey(),
+        listing_price,
+        ListingOptions::default(),
+    );
+    t.send(&[add_listing_ix], &seller, &[]).expect("Listing should succeed");
+
+    let match_listing_ix = t.match_listing_ix(
+        &buyer.pubkey(),
+        &seller.pubkey(),
+        &collection.pubkey(),
+        &asset.pubkey(),
+        &[],
+    );
+    t.send(&[match_listing_ix], &buyer, &[]).expect("Purchase should succeed");
+
+    // TraderStats: ..., bump @80, rewards_accrued @81, rewards_claimed @89
+    let read_rewards = |t: &TestMarket, wallet: &Pubkey| {
+        let data = t.svm.get_account(&t.trader_stats_pda(wallet)).unwrap().data;
+        (
+            u64::from_le_bytes(data[81..89].try_into().unwrap()),
+            u64::from_le_bytes(data[89..97].try_into().unwrap()),
+        )
+    };
+    let expected_reward = 2_000_000;
+    assert_eq!(read_rewards(&t, &buyer.pubkey()), (expected_reward, 0));
+    assert_eq!(read_rewards(&t, &seller.pubkey()), (expected_reward, 0));
+
+    let claim_rewards_ix = Instruction {
+        program_id: t.program_id,
+        accounts: vec![
+            AccountMeta::new(buyer.pubkey(), true),                                   // claimer
+            AccountMeta::new(t.market_pda, false),                                    // market
+            AccountMeta::new_readonly(reward_config, false),                          // reward_config
+            AccountMeta::new(reward_mint, false),                                     // reward_mint
+            AccountMeta::new(associated_token_address(&reward_config, &reward_mint), false), // reward_vault
+            AccountMeta::new(associated_token_address(&buyer.pubkey(), &reward_mint), false), // claimer_token_account
+            AccountMeta::new(t.trader_stats_pda(&buyer.pubkey()), false),             // trader_stats
+            AccountMeta::new_readonly(token_program_id(), false),                     // token_program
+            AccountMeta::new_readonly(associated_token_program_id(), false),          // associated_token_program
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),                      // system_program
+            AccountMeta::new_readonly(event_authority_pda(&t.program_id), false), // event_authority
+            AccountMeta::new_readonly(t.program_id, false),      // program
+        ],
+        data: anchor_discriminator("global", "claim_rewards").to_vec(),
+    };
+    t.send(std::slice::from_ref(&claim_rewards_ix), &buyer, &[]).expect("Claim should succeed");
+
+    // SPL token account amount @64
+    let token_account = t.svm
+        .get_account(&associated_token_address(&buyer.pubkey(), &reward_mint))
+        .expect("Reward token account should exist");
+    assert_eq!(u64::from_le_bytes(token_account.data[64..72].try_into().unwrap()), expected_reward);
+    assert_eq!(read_rewards(&t, &buyer.pubkey()), (0, expected_reward));
+
+    t.svm.expire_blockhash();
+    assert!(t.send(&[claim_rewards_ix], &buyer, &[]).is_err(), "Nothing left to claim");
+    println!(" Trading rewards accrued and minted");
+}
+
+// Replace a program account with raw (legacy layout) bytes
+fn set_program_account(t: &mut TestMarket, address: Pubkey, data: Vec<u8>) {
+    t.svm.set_account(
+        address,
+        solana_sdk::account::Account {
+            lamports: t.svm.minimum_balance_for_rent_exemption(data.len()),
+            data,
+            owner: t.program_id,
+            executable: false,
+            rent_epoch: 0,
+        },
+    ).unwrap();
+}
+
+#[test]
+fn test_migrate_legacy_market() {
+    let mut t = TestMarket::new(200);
+    let admin = t.admin.insecure_clone();
+    let seller = t.wallet();
+
+    let (collection, update_authority) = t.create_collection(&seller);
+    let asset = t.mint_asset(&seller, &collection.pubkey(), &update_authority);
+
+    // v1 Market layout: discriminator, admin, fee_recipient, taker_fee_bps, bump
+    let (_, market_bump) = Pubkey::find_program_address(&[b"market"], &t.program_id);
+    let mut legacy = Vec::new();
+    legacy.extend_from_slice(&t.svm.get_account(&t.market_pda).unwrap().data[..8]);
+    legacy.extend_from_slice(admin.pubkey().as_ref());
+    legacy.extend_from_slice(t.fee_recipient.pubkey().as_ref());
+    legacy.extend_from_slice(&200u64.to_le_bytes());
+    legacy.push(market_bump);
+    let legacy_len = legacy.len();
+    let market_pda = t.market_pda;
+    set_program_account(&mut t, market_pda, legacy);
+
+    let add_listing_ix = t.add_listing_ix(
+        &seller.pubkey(),
+        &collection.pubkey(),
+        &asset.pubkey(),
+        1_000_000_000,
+        ListingOptions::default(),
+    );
+    assert!(t.send(std::slice::from_ref(&add_listing_ix), &seller, &[]).is_err(), "Legacy market should not deserialize");
+
+    let migrate_market_ix = |signer: &Pubkey| Instruction {
+        program_id: t.program_id,
+        accounts: vec![
+            AccountMeta::new(*signer, true),
+            AccountMeta::new(t.market_pda, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(event_authority_pda(&t.program_id), false),
+            AccountMeta::new_readonly(t.program_id, false),
+        ],
+        data: anchor_discriminator("global", "migrate_market").to_vec(),
+    };
+    let not_admin_ix = migrate_market_ix(&seller.pubkey());
+    let admin_ix = migrate_market_ix(&admin.pubkey());
+
+    assert!(t.send(&[not_admin_ix], &seller, &[]).is_err(), "Only admin can migrate the market");
+    t.send(std::slice::from_ref(&admin_ix), &admin, &[]).expect("Admin should migrate the market");
+
+    // Zero-filled fields: allowlist None, early access 0, Floor, min_fee 0,
+    // then min_price @99 and max_price @107 (defaulted), empty fee_tiers @115, version @119
+    let data = t.svm.get_account(&t.market_pda).unwrap().data;
+    assert!(data.len() > legacy_len, "Market should be reallocated");
+    assert_eq!(u64::from_le_bytes(data[99..107].try_into().unwrap()), 1, "Migrated market should reject 0-lamport listings");
+    assert_eq!(u64::from_le_bytes(data[107..115].try_into().unwrap()), u64::MAX);
+    assert_eq!(data[119], 2, "Market should be on layout v2");
+    assert_eq!(u64::from_le_bytes(data[120..128].try_into().unwrap()), 1, "Migration event is the first in sequence");
+
+    t.svm.expire_blockhash();
+    t.send(&[add_listing_ix], &seller, &[]).expect("Migrated market should accept listings");
+
+    t.svm.expire_blockhash();
+    assert!(t.send(&[admin_ix], &admin, &[]).is_err(), "Second migration should fail");
+    println!(" Legacy market migrated to v2");
+}
+
+#[test]
+fn test_migrate_legacy_listing() {
+    let mut t = TestMarket::new(200);
+    let seller = t.wallet();
+    let payer = t.wallet();
+
+    let (collection, update_authority) = t.create_collection(&seller);
+    let asset = t.mint_asset(&seller, &collection.pubkey(), &update_authority);
+    let listing_price: u64 = 1_000_000_000;
+
+    let add_listing_ix = t.add_listing_ix(
+        &seller.pubkey(),
+        &collection.pubkey(),
+        &asset.pubkey(),
+        listing_price,
+        ListingOptions::default(),
+    );
+    t.send(&[add_listing_ix], &seller, &[]).expect("Listing should succeed");
+
+    // Rewrite as v1 Listing layout: seller, collection, asset, price, is_active, created_at, bump
+    let listing_pda = t.listing_pda(&seller.pubkey(), &collection.pubkey(), &asset.pubkey());
+    let current = t.svm.get_account(&listing_pda).unwrap().data;
+    let legacy = current[..8 + 32 * 3 + 8 + 1 + 8 + 1].to_vec();
+    set_program_account(&mut t, listing_pda, legacy);
+
+    let remove_listing_ix = t.remove_listing_ix(&seller.pubkey(), &collection.pubkey(), &asset.pubkey());
+    assert!(t.send(std::slice::from_ref(&remove_listing_ix), &seller, &[]).is_err(), "Legacy listing should not deserialize");
+
+    // Anyone can migrate a listing
+    let migrate_listing_ix = Instruction {
+        program_id: t.program_id,
+        accounts: vec![
+            AccountMeta::new(payer.pubkey(), true),
+            AccountMeta::new(listing_pda, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new(t.market_pda, false),
+            AccountMeta::new_readonly(event_authority_pda(&t.program_id), false),
+            AccountMeta::new_readonly(t.program_id, false),
+        ],
+        data: anchor_discriminator("global", "migrate_listing").to_vec(),
+    };
+    t.send(std::slice::from_ref(&migrate_listing_ix), &payer, &[]).expect("Listing migration should succeed");
+
+    // allowlist_root None @122, reserved_buyer None @123, mode Escrow @124, version @125
+    let data = t.svm.get_account(&listing_pda).unwrap().data;
+    assert_eq!(&data[122..125], &[0, 0, 0]);
+    assert_eq!(data[125], 2, "Listing should be on layout v2");
+
+    t.svm.expire_blockhash();
+    assert!(t.send(&[migrate_listing_ix], &payer, &[]).is_err(), "Second migration should fail");
+
+    t.send(&[remove_listing_ix], &seller, &[]).expect("Migrated listing should be removable");
+    assert_eq!(t.asset_owner(&asset.pubkey()), seller.pubkey());
+    println!(" Legacy listing migrated to v2");
+}
+
+#[test]
+fn test_event_sequence() {
+    let mut t = TestMarket::new(200);
+
+    let seller = t.wallet();
+    let buyer = t.wallet();
+
+    // Market: ..., empty fee_tiers @115, version @119, event_seq @120
+    let event_seq = |t: &TestMarket| {
+        let data = t.svm.get_account(&t.market_pda).unwrap().data;
+        u64::from_le_bytes(data[120..128].try_into().unwrap())
+    };
+    assert_eq!(event_seq(&t), 0);
+
+    let (collection, update_authority) = t.create_collection(&seller);
+    assert_eq!(event_seq(&t), 1, "CollectionCreated");
+
+    let asset = t.mint_asset(&seller, &collection.pubkey(), &update_authority);
+    let add_listing_ix = t.add_listing_ix(
+        &seller.pubkey(),
+        &collection.pubkey(),
+        &asset.pubkey(),
+        1_000_000_000,
+        ListingOptions::default(),
+    );
+    t.send(&[add_listing_ix], &seller, &[]).expect("Listing should succeed");
+    assert_eq!(event_seq(&t), 2, "ListingCreated");
+
+    // Events are emitted by self-CPI, so the event authority must be the real PDA
+    let mut match_listing_ix = t.match_listing_ix(
+        &buyer.pubkey(),
+        &seller.pubkey(),
+        &collection.pubkey(),
+        &asset.pubkey(),
+        &[],
+    );
+    let event_authority_index = match_listing_ix.accounts.len() - 2;
+    match_listing_ix.accounts[event_authority_index] = AccountMeta::new_readonly(Keypair::new().pubkey(), false);
+    assert!(t.send(&[match_listing_ix], &buyer, &[]).is_err(), "Wrong event authority should fail");
+
+    let match_listing_ix = t.match_listing_ix(
+        &buyer.pubkey(),
+        &seller.pubkey(),
+        &collection.pubkey(),
+        &asset.pubkey(),
+        &[],
+    );
+    t.send(&[match_listing_ix], &buyer, &[]).expect("Purchase should succeed");
+    assert_eq!(event_seq(&t), 3, "ListingSold");
+
+    // Admin settings changes are sequenced too
+    let fee_recipient = Keypair::new().pubkey();
+    let mut args = fee_recipient.to_bytes().to_vec();
+    args.extend_from_slice(&300u64.to_le_bytes()); // taker_fee_bps
+    let update_market_ix = t.update_market_ix("update_market", &args);
+    let admin = t.admin.insecure_clone();
+    t.send(&[update_market_ix], &admin, &[]).expect("Admin should update market");
+    assert_eq!(event_seq(&t), 4, "MarketUpdated");
+    println!(" Events sequenced by the market");
+}
+
+#[test]
+fn test_close_inactive_listings() {
+    let mut t = TestMarket::new(200);
+
+    let seller = t.wallet();
+    let buyer = t.wallet();
+    let closer = t.wallet();
+
+    // List three assets, sell the first two
+    let (collection, update_authority) = t.create_collection(&seller);
+    let mut listings = Vec::new();
+    for sold in [true, true, false] {
+        let asset = t.mint_asset(&seller, &collection.pubkey(), &update_authority);
+        let add_listing_ix = t.add_listing_ix(
+            &seller.pubkey(),
+            &collection.pubkey(),
+            &asset.pubkey(),
+            1_000_000_000,
+            ListingOptions::default(),
+        );
+        t.send(&[add_listing_ix], &seller, &[]).expect("Listing should succeed");
+
+        if sold {
+            let match_listing_ix = t.match_listing_ix(
+                &buyer.pubkey(),
+                &seller.pubkey(),
+                &collection.pubkey(),
+                &asset.pubkey(),
+                &[],
+            );
+            t.send(&[match_listing_ix], &buyer, &[]).expect("Purchase should succeed");
+        }
+        listings.push(t.listing_pda(&seller.pubkey(), &collection.pubkey(), &asset.pubkey()));
+    }
+
+    let close_listing_ix = |t: &TestMarket, listing: &Pubkey| Instruction {
+        program_id: t.program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(closer.pubkey(), true),     // closer
+            AccountMeta::new(seller.pubkey(), false),             // seller
+            AccountMeta::new(*listing, false),                    // listing
+            AccountMeta::new(t.market_pda, false),                // market
+            AccountMeta::new_readonly(event_authority_pda(&t.program_id), false), // event_authority
+            AccountMeta::new_readonly(t.program_id, false),       // program
+        ],
+        data: anchor_discriminator("global", "close_inactive_listing").to_vec(),
+    };
+    let close_listings_ix = |t: &TestMarket, listings: &[Pubkey]| {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(closer.pubkey(), true),     // closer
+            AccountMeta::new(t.market_pda, false),                // market
+            AccountMeta::new_readonly(event_authority_pda(&t.program_id), false), // event_authority
+            AccountMeta::new_readonly(t.program_id, false),       // program
+        ];
+        for listing in listings {
+            accounts.push(AccountMeta::new(*listing, false));
+            accounts.push(AccountMeta::new(seller.pubkey(), false));
+        }
+        Instruction {
+            program_id: t.program_id,
+            accounts,
+            data: anchor_discriminator("global", "close_inactive_listings").to_vec(),
+        }
+    };
+
+    let active_ix = close_listing_ix(&t, &listings[2]);
+    assert!(t.send(&[active_ix], &closer, &[]).is_err(), "Active listing cannot be closed");
+
+    // Anyone can close a sold listing, rent goes to the seller
+    let seller_before = t.svm.get_balance(&seller.pubkey()).unwrap();
+    let rent = t.svm.get_balance(&listings[0]).unwrap();
+    let close_ix = close_listing_ix(&t, &listings[0]);
+    t.send(&[close_ix], &closer, &[]).expect("Sold listing should close");
+    assert!(t.svm.get_account(&listings[0]).is_none(), "Listing account should be closed");
+    assert_eq!(t.svm.get_balance(&seller.pubkey()).unwrap(), seller_before + rent);
+
+    // A batch containing an active listing fails as a whole
+    let mixed_ix = close_listings_ix(&t, &[listings[1], listings[2]]);
+    assert!(t.send(&[mixed_ix], &closer, &[]).is_err(), "Batch with an active listing should fail");
+
+    let batch_ix = close_listings_ix(&t, &[listings[1]]);
+    t.send(&[batch_ix], &closer, &[]).expect("Batch close should succeed");
+    assert!(t.svm.get_account(&listings[1]).is_none(), "Batched listing should be closed");
+    assert!(t.svm.get_account(&listings[2]).is_some(), "Active listing should remain");
+    println!(" Inactive listings closed, rent refunded to seller");
+}
+
+#[test]
+fn test_market_stats_counters() {
+    let mut t = TestMarket::new(200);
+
+    let seller = t.wallet();
+    let buyer = t.wallet();
+
+    // Market: ..., event_seq @120, active_listings @128, total_listings @136,
+    // total_sales @144, total_volume @152 (u128), total_fees @168 (u128)
+    let stats = |t: &TestMarket| {
+        let data = t.svm.get_account(&t.market_pda).unwrap().data;
+        (
+            u64::from_le_bytes(data[128..136].try_into().unwrap()),
+            u64::from_le_bytes(data[136..144].try_into().unwrap()),
+            u64::from_le_bytes(data[144..152].try_into().unwrap()),
+            u128::from_le_bytes(data[152..168].try_into().unwrap()),
+            u128::from_le_bytes(data[168..184].try_into().unwrap()),
+        )
+    };
+    assert_eq!(stats(&t), (0, 0, 0, 0, 0));
+
+    let (collection, update_authority) = t.create_collection(&seller);
+    let listing_price: u64 = 1_000_000_000;
+    let mut assets = Vec::new();
+    for _ in 0..2 {
+        let asset = t.mint_asset(&seller, &collection.pubkey(), &update_authority);
+        let add_listing_ix = t.add_listing_ix(
+            &seller.pubkey(),
+            &collection.pubkey(),
+            &asset.pubkey(),
+            listing_price,
+            ListingOptions::default(),
+        );
+        t.send(&[add_listing_ix], &seller, &[]).expect("Listing should succeed");
+        assets.push(asset);
+    }
+    assert_eq!(stats(&t), (2, 2, 0, 0, 0));
+
+    let match_listing_ix = t.match_listing_ix(
+        &buyer.pubkey(),
+        &seller.pubkey(),
+        &collection.pubkey(),
+        &assets[0].pubkey(),
+        &[],
+    );
+    t.send(&[match_listing_ix], &buyer, &[]).expect("Purchase should succeed");
+    assert_eq!(stats(&t), (1, 2, 1, listing_price as u128, 20_000_000));
+
+    let remove_listing_ix = t.remove_listing_ix(&seller.pubkey(), &collection.pubkey(), &assets[1].pubkey());
+    t.send(&[remove_listing_ix], &seller, &[]).expect("Removal should succeed");
+    assert_eq!(stats(&t), (0, 2, 1, listing_price as u128, 20_000_000));
+    println!(" Market counters track listings, sales, volume and fees");
+}
+
+// Loan instruction builders shared by the loan tests
+struct LoanIxs<'a> {
+    t: &'a TestMarket,
+}
+
+impl LoanIxs<'_> {
+    fn offer_pda(&self, lender: &Pubkey, seed: u64) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"loan_offer", lender.as_ref(), &seed.to_le_bytes()],
+            &self.t.program_id,
+        ).0
+    }
+
+    fn loan_pda(&self, asset: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[b"loan", asset.as_ref()], &self.t.program_id).0
+    }
+
+    fn event_metas(&self) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new(self.t.market_pda, false),
+            AccountMeta::new_readonly(event_authority_pda(&self.t.program_id), false),
+            AccountMeta::new_readonly(self.t.program_id, false),
+        ]
+    }
+
+    fn create_offer(&self, lender: &Pubkey, seed: u64, collection: &Pubkey, amount: u64, apr_bps: u64, duration: i64) -> Instruction {
+        let mut data = Vec::new();
+        data.extend_from_slice(&anchor_discriminator("global", "create_loan_offer"));
+        data.extend_from_slice(&seed.to_le_bytes());
+        data.extend_from_slice(collection.as_ref());
+        data.extend_from_slice(&amount.to_le_bytes());
+        data.extend_from_slice(&apr_bps.to_le_bytes());
+        data.extend_from_slice(&duration.to_le_bytes());
+
+        let mut accounts = vec![
+            AccountMeta::new(*lender, true),
+            AccountMeta::new(self.offer_pda(lender, seed), false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ];
+        accounts.extend(self.event_metas());
+        Instruction { program_id: self.t.program_id, accounts, data }
+    }
+
+    fn accept_offer(&self, borrower: &Pubkey, lender: &Pubkey, seed: u64, asset: &Pubkey, collection: &Pubkey) -> Instruction {
+        let mut accounts = vec![
+            AccountMeta::new(*borrower, true),
+            AccountMeta::new(*lender, false),
+            AccountMeta::new(self.offer_pda(lender, seed), false),
+            AccountMeta::new(self.loan_pda(asset), false),
+            AccountMeta::new(*asset, false),
+            AccountMeta::new_readonly(*collection, false),
+            AccountMeta::new_readonly(self.t.mpl_core_id, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ];
+        accounts.extend(self.event_metas());
+        Instruction {
+            program_id: self.t.program_id,
+            accounts,
+            data: anchor_discriminator("global", "accept_loan_offer").to_vec(),
+        }
+    }
+
+    // repay_loan (borrower signs) and foreclose_loan (lender signs) share one layout
+    fn settle(&self, name: &str, signer: &Pubkey, other: &Pubkey, asset: &Pubkey, collection: &Pubkey) -> Instruction {
+        let mut accounts = vec![
+            AccountMeta::new(*signer, true),
+            AccountMeta::new(*other, false),
+            AccountMeta::new(self.loan_pda(asset), false),
+            AccountMeta::new(*asset, false),
+            AccountMeta::new_readonly(*collection, false),
+            AccountMeta::new_readonly(self.t.mpl_core_id, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ];
+        accounts.extend(self.event_metas());
+        Instruction {
+            program_id: self.t.program_id,
+            accounts,
+            data: anchor_discriminator("global", name).to_vec(),
+        }
+    }
+}
+
+const DAY: i64 = 24 * 60 * 60;
+
+#[test]
+fn test_loan_repay() {
+    let mut t = TestMarket::new(200);
+
+    let lender = t.wallet();
+    let borrower = t.wallet();
+    let (collection, update_authority) = t.create_collection(&borrower);
+    let asset = t.mint_asset(&borrower, &collection.pubkey(), &update_authority);
+
+    // 2 SOL at 10% APR for 30 days
+    let principal: u64 = 2_000_000_000;
+    let (create_ix, accept_ix, offer_pda, loan_pda) = {
+        let loans = LoanIxs { t: &t };
+        (
+            loans.create_offer(&lender.pubkey(), 1, &collection.pubkey(), principal, 1_000, 30 * DAY),
+            loans.accept_offer(&borrower.pubkey(), &lender.pubkey(), 1, &asset.pubkey(), &collection.pubkey()),
+            loans.offer_pda(&lender.pubkey(), 1),
+            loans.loan_pda(&asset.pubkey()),
+        )
+    };
+    t.send(&[create_ix], &lender, &[]).expect("Loan offer should be created");
+
+    let borrower_before = t.svm.get_balance(&borrower.pubkey()).unwrap();
+    t.send(&[accept_ix], &borrower, &[]).expect("Borrower should accept the offer");
+
+    assert!(t.svm.get_account(&offer_pda).is_none(), "Offer should be consumed");
+    assert_eq!(t.asset_owner(&asset.pubkey()), loan_pda, "Collateral should be escrowed");
+    assert!(
+        t.svm.get_balance(&borrower.pubkey()).unwrap() > borrower_before + principal - 10_000_000,
+        "Borrower should receive the principal"
+    );
+
+    // Repay after 15 days: 2 SOL * 10% * 15/365, rounded up
+    t.warp(15 * DAY);
+    let lender_before = t.svm.get_balance(&lender.pubkey()).unwrap();
+    let repay_ix = LoanIxs { t: &t }.settle("repay_loan", &borrower.pubkey(), &lender.pubkey(), &asset.pubkey(), &collection.pubkey());
+    t.send(&[repay_ix], &borrower, &[]).expect("Repayment should succeed");
+
+    let interest = (principal as u128 * 1_000 * (15 * DAY) as u128).div_ceil(10_000 * 365 * DAY as u128) as u64;
+    assert_eq!(t.svm.get_balance(&lender.pubkey()).unwrap(), lender_before + principal + interest);
+    assert_eq!(t.asset_owner(&asset.pubkey()), borrower.pubkey(), "Collateral should be returned");
+    assert!(t.svm.get_account(&loan_pda).is_none(), "Loan should be closed");
+    println!(" Loan repaid with {} lamports interest", interest);
+}
+
+#[test]
+fn test_loan_foreclose() {
+    let mut t = TestMarket::new(200);
+
+    let lender = t.wallet();
+    let borrower = t.wallet();
+    let (collection, update_authority) = t.create_collection(&borrower);
+    let (other_collection, other_update_authority) = t.create_collection(&borrower);
+    let asset = t.mint_asset(&borrower, &collection.pubkey(), &update_authority);
+    let other_asset = t.mint_asset(&borrower, &other_collection.pubkey(), &other_update_authority);
+
+    let loans = LoanIxs { t: &t };
+    let create_ix = loans.create_offer(&lender.pubkey(), 7, &collection.pubkey(), 1_000_000_000, 500, 7 * DAY);
+    let wrong_collateral_ix = loans.accept_offer(&borrower.pubkey(), &lender.pubkey(), 7, &other_asset.pubkey(), &collection.pubkey());
+    let accept_ix = loans.accept_offer(&borrower.pubkey(), &lender.pubkey(), 7, &asset.pubkey(), &collection.pubkey());
+    let foreclose_ix = loans.settle("foreclose_loan", &lender.pubkey(), &borrower.pubkey(), &asset.pubkey(), &collection.pubkey());
+    let repay_ix = loans.settle("repay_loan", &borrower.pubkey(), &lender.pubkey(), &asset.pubkey(), &collection.pubkey());
+
+    t.send(&[create_ix], &lender, &[]).expect("Loan offer should be created");
+    assert!(t.send(&[wrong_collateral_ix], &borrower, &[]).is_err(), "Asset from another collection should be rejected");
+    t.send(&[accept_ix], &borrower, &[]).expect("Borrower should accept the offer");
+
+    assert!(t.send(std::slice::from_ref(&foreclose_ix), &lender, &[]).is_err(), "Cannot foreclose before due");
+
+    t.warp(8 * DAY);
+    assert!(t.send(&[repay_ix], &borrower, &[]).is_err(), "Cannot repay after due");
+    t.send(&[foreclose_ix], &lender, &[]).expect("Lender should foreclose");
+    assert_eq!(t.asset_owner(&asset.pubkey()), lender.pubkey(), "Lender should receive the collateral");
+    println!(" Defaulted loan foreclosed");
+}
+
+#[test]
+fn test_cancel_loan_offer() {
+    let mut t = TestMarket::new(200);
+
+    let lender = t.wallet();
+    let collection = Keypair::new().pubkey();
+
+    let loans = LoanIxs { t: &t };
+    let offer_pda = loans.offer_pda(&lender.pubkey(), 3);
+    // An APR this high would make interest overflow and the loan unrepayable
+    let extreme_apr_ix = loans.create_offer(&lender.pubkey(), 3, &collection, 1_000_000_000, 100_001, 7 * DAY);
+    let create_ix = loans.create_offer(&lender.pubkey(), 3, &collection, 1_000_000_000, 500, 7 * DAY);
+    let mut accounts = vec![
+        AccountMeta::new(lender.pubkey(), true),
+        AccountMeta::new(offer_pda, false),
+    ];
+    accounts.extend(loans.event_metas());
+    let cancel_ix = Instruction {
+        program_id: t.program_id,
+        accounts,
+        data: anchor_discriminator("global", "cancel_loan_offer").to_vec(),
+    };
+
+    assert_anchor_error(t.send(&[extreme_apr_ix], &lender, &[]), "InvalidLoanTerms");
+
+    let lender_before = t.svm.get_balance(&lender.pubkey()).unwrap();
+    t.send(&[create_ix, cancel_ix], &lender, &[]).expect("Create + cancel should succeed");
+
+    assert!(t.svm.get_account(&offer_pda).is_none(), "Offer should be closed");
+    // Only the transaction fee is lost
+    assert!(lender_before - t.svm.get_balance(&lender.pubkey()).unwrap() < 100_000);
+    println!(" Loan offer canceled and refunded");
+}
+
+#[test]
+fn test_rental() {
+    let mut t = TestMarket::new(200);
+
+    let owner = t.wallet();
+    let renter = t.wallet();
+    let other_renter = t.wallet();
+    let (collection, update_authority) = t.create_collection(&owner);
+    let asset = t.mint_asset(&owner, &collection.pubkey(), &update_authority);
+
+    let (rental_pda, _) = Pubkey::find_program_address(&[b"rental", asset.pubkey().as_ref()], &t.program_id);
+    let event_metas = vec![
+        AccountMeta::new(t.market_pda, false),
+        AccountMeta::new_readonly(event_authority_pda(&t.program_id), false),
+        AccountMeta::new_readonly(t.program_id, false),
+    ];
+
+    let daily_price: u64 = 100_000_000;
+    let mut data = anchor_discriminator("global", "list_rental").to_vec();
+    data.extend_from_slice(&daily_price.to_le_bytes());
+    data.extend_from_slice(&7u16.to_le_bytes());
+    let mut accounts = vec![
+        AccountMeta::new(owner.pubkey(), true),
+        AccountMeta::new(rental_pda, false),
+        AccountMeta::new(asset.pubkey(), false),
+        AccountMeta::new_readonly(collection.pubkey(), false),
+        AccountMeta::new_readonly(t.mpl_core_id, false),
+        AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+    ];
+    accounts.extend(event_metas.clone());
+    let list_ix = Instruction { program_id: t.program_id, accounts, data };
+    t.send(&[list_ix], &owner, &[]).expect("Rental listing should succeed");
+    assert_eq!(t.asset_owner(&asset.pubkey()), rental_pda, "Asset should be escrowed");
+
+    let rent_ix = |renter: &Pubkey, days: u16| {
+        let mut data = anchor_discriminator("global", "rent_asset").to_vec();
+        data.extend_from_slice(&days.to_le_bytes());
+        let mut accounts = vec![
+            AccountMeta::new(*renter, true),
+            AccountMeta::new(owner.pubkey(), false),
+            AccountMeta::new(rental_pda, false),
+            AccountMeta::new(t.fee_recipient.pubkey(), false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ];
+        accounts.extend(event_metas.clone());
+        Instruction { program_id: t.program_id, accounts, data }
+    };
+    let too_long_ix = rent_ix(&renter.pubkey(), 8);
+    let rent_3_days_ix = rent_ix(&renter.pubkey(), 3);
+    let other_rent_ix = rent_ix(&other_renter.pubkey(), 1);
+
+    let mut accounts = vec![
+        AccountMeta::new_readonly(other_renter.pubkey(), true),
+        AccountMeta::new(rental_pda, false),
+    ];
+    accounts.extend(event_metas.clone());
+    let reclaim_ix = Instruction {
+        program_id: t.program_id,
+        accounts,
+        data: anchor_discriminator("global", "reclaim_rental").to_vec(),
+    };
+
+    let mut accounts = vec![
+        AccountMeta::new(owner.pubkey(), true),
+        AccountMeta::new(rental_pda, false),
+        AccountMeta::new(asset.pubkey(), false),
+        AccountMeta::new_readonly(collection.pubkey(), false),
+        AccountMeta::new_readonly(t.mpl_core_id, false),
+        AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+    ];
+    accounts.extend(event_metas);
+    let remove_ix = Instruction {
+        program_id: t.program_id,
+        accounts,
+        data: anchor_discriminator("global", "remove_rental").to_vec(),
+    };
+
+    assert!(t.send(&[too_long_ix], &renter, &[]).is_err(), "Cannot rent beyond max_days");
+
+    // 3 days at 0.1 SOL, 2% marketplace fee
+    let owner_before = t.svm.get_balance(&owner.pubkey()).unwrap();
+    t.send(&[rent_3_days_ix], &renter, &[]).expect("Renting should succeed");
+    assert_eq!(t.svm.get_balance(&owner.pubkey()).unwrap(), owner_before + 294_000_000);
+    assert_eq!(t.svm.get_balance(&t.fee_recipient.pubkey()).unwrap(), 6_000_000);
+
+    let rental = t.svm.get_account(&rental_pda).unwrap();
+    assert_eq!(rental.data[114], 1, "Renter should be recorded");
+    assert_eq!(&rental.data[115..147], renter.pubkey().as_ref());
+
+    assert!(t.send(std::slice::from_ref(&other_rent_ix), &other_renter, &[]).is_err(), "Asset is already rented");
+    assert!(t.send(std::slice::from_ref(&remove_ix), &owner, &[]).is_err(), "Owner cannot remove during a rental");
+    assert!(t.send(std::slice::from_ref(&reclaim_ix), &other_renter, &[]).is_err(), "Cannot reclaim before expiry");
+
+    t.warp(3 * DAY);
+    t.send(&[reclaim_ix], &other_renter, &[]).expect("Anyone can reclaim an expired rental");
+    let rental = t.svm.get_account(&rental_pda).unwrap();
+    assert_eq!(rental.data[114], 0, "Renter record should be cleared");
+
+    t.send(&[remove_ix], &owner, &[]).expect("Owner should remove the rental");
+    assert_eq!(t.asset_owner(&asset.pubkey()), owner.pubkey(), "Asset should be returned");
+    assert!(t.svm.get_account(&rental_pda).is_none(), "Rental should be closed");
+    println!(" Rental booked, reclaimed and removed");
+}
+
+// Installment purchase instruction builders
+struct InstallmentIxs<'a> {
+    t: &'a TestMarket,
+    seller: Pubkey,
+    buyer: Pubkey,
+    collection: Pubkey,
+    asset: Pubkey,
+}
+
+impl InstallmentIxs<'_> {
+    fn listing_pda(&self) -> Pubkey {
+        self.t.listing_pda(&self.seller, &self.collection, &self.asset)
+    }
+
+    fn purchase_pda(&self) -> Pubkey {
+        Pubkey::find_program_address(&[b"installment", self.listing_pda().as_ref()], &self.t.program_id).0
+    }
+
+    fn ix(&self, name: &str, mut accounts: Vec<AccountMeta>, args: &[u8]) -> Instruction {
+        accounts.extend([
+            AccountMeta::new(self.t.market_pda, false),
+            AccountMeta::new_readonly(event_authority_pda(&self.t.program_id), false),
+            AccountMeta::new_readonly(self.t.program_id, false),
+        ]);
+        let mut data = anchor_discriminator("global", name).to_vec();
+        data.extend_from_slice(args);
+        Instruction { program_id: self.t.program_id, accounts, data }
+    }
+
+    fn set_plan(&self, down_payment: u64, installment_count: u8, interval: i64, refund_bps: u16) -> Instruction {
+        let mut args = vec![1];
+        args.extend_from_slice(&down_payment.to_le_bytes());
+        args.push(installment_count);
+        args.extend_from_slice(&interval.to_le_bytes());
+        args.extend_from_slice(&refund_bps.to_le_bytes());
+        self.ix("set_installment_plan", vec![
+            AccountMeta::new_readonly(self.seller, true),
+            AccountMeta::new(self.listing_pda(), false),
+        ], &args)
+    }
+
+    fn start(&self) -> Instruction {
+        self.ix("start_installment_purchase", vec![
+            AccountMeta::new(self.buyer, true),
+            AccountMeta::new(self.seller, false),
+            AccountMeta::new(self.listing_pda(), false),
+            AccountMeta::new(self.purchase_pda(), false),
+            AccountMeta::new(self.asset, false),
+            AccountMeta::new_readonly(self.collection, false),
+            AccountMeta::new(self.t.fee_recipient.pubkey(), false),
+            AccountMeta::new_readonly(self.t.mpl_core_id, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ], &0u32.to_le_bytes()) // empty proof
+    }
+
+    fn pay(&self) -> Instruction {
+        self.ix("pay_installment", vec![
+            AccountMeta::new(self.buyer, true),
+            AccountMeta::new(self.seller, false),
+            AccountMeta::new(self.purchase_pda(), false),
+            AccountMeta::new(self.asset, false),
+            AccountMeta::new_readonly(self.collection, false),
+            AccountMeta::new(self.t.fee_recipient.pubkey(), false),
+            AccountMeta::new_readonly(self.t.mpl_core_id, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ], &[])
+    }
+
+    fn reclaim(&self) -> Instruction {
+        self.ix("reclaim_installment", vec![
+            AccountMeta::new(self.seller, true),
+            AccountMeta::new(self.buyer, false),
+            AccountMeta::new(self.purchase_pda(), false),
+            AccountMeta::new(self.asset, false),
+            AccountMeta::new_readonly(self.collection, false),
+            AccountMeta::new_readonly(self.t.mpl_core_id, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ], &[])
+    }
+}
+
+// Seller + buyer with a 1 SOL escrow listing
+fn setup_installment_listing(t: &mut TestMarket) -> (Keypair, Keypair, Pubkey, Pubkey) {
+    let seller = t.wallet();
+    let buyer = t.wallet();
+    let (collection, update_authority) = t.create_collection(&seller);
+    let asset = t.mint_asset(&seller, &collection.pubkey(), &update_authority);
+    let list_ix = t.add_listing_ix(&seller.pubkey(), &collection.pubkey(), &asset.pubkey(), 1_000_000_000, ListingOptions::default());
+    t.send(&[list_ix], &seller, &[]).expect("Listing should succeed");
+    (seller, buyer, collection.pubkey(), asset.pubkey())
+}
+
+#[test]
+fn test_installment_purchase() {
+    let mut t = TestMarket::new(200);
+    let (seller, buyer, collection, asset) = setup_installment_listing(&mut t);
+
+    let ixs = InstallmentIxs { t: &t, seller: seller.pubkey(), buyer: buyer.pubkey(), collection, asset };
+    let invalid_plan_ix = ixs.set_plan(1_000_000_000, 3, 7 * DAY, 5_000);
+    // 0.4 SOL down, then 3 weekly installments of 0.2 SOL
+    let set_plan_ix = ixs.set_plan(400_000_000, 3, 7 * DAY, 5_000);
+    let start_ix = ixs.start();
+    let pay_ix = ixs.pay();
+    let purchase_pda = ixs.purchase_pda();
+    let listing_pda = ixs.listing_pda();
+
+    assert!(t.send(&[invalid_plan_ix], &seller, &[]).is_err(), "Down payment must be below the price");
+    t.send(&[set_plan_ix], &seller, &[]).expect("Seller should set the plan");
+    assert_eq!(t.svm.get_account(&listing_pda).unwrap().data[126], 1, "Plan should be stored on the listing");
+
+    let seller_before = t.svm.get_balance(&seller.pubkey()).unwrap();
+    t.send(&[start_ix], &buyer, &[]).expect("Buyer should start the purchase");
+    assert_eq!(t.svm.get_balance(&seller.pubkey()).unwrap(), seller_before + 400_000_000);
+    assert_eq!(t.svm.get_balance(&t.fee_recipient.pubkey()).unwrap(), 8_000_000);
+    assert_eq!(t.asset_owner(&asset), purchase_pda, "NFT should move to the purchase escrow");
+
+    for installment in 1..=3 {
+        t.warp(7 * DAY);
+        t.send(std::slice::from_ref(&pay_ix), &buyer, &[]).expect("Installment should be paid on time");
+        if installment < 3 {
+            assert_eq!(t.asset_owner(&asset), purchase_pda, "NFT stays escrowed until paid off");
+        }
+    }
+
+    assert_eq!(t.svm.get_balance(&seller.pubkey()).unwrap(), seller_before + 1_000_000_000);
+    assert_eq!(t.svm.get_balance(&t.fee_recipient.pubkey()).unwrap(), 20_000_000);
+    assert_eq!(t.asset_owner(&asset), buyer.pubkey(), "NFT should be released to the buyer");
+    assert!(t.svm.get_account(&purchase_pda).is_none(), "Purchase should be closed");
+
+    let market = t.svm.get_account(&t.market_pda).unwrap();
+    assert_eq!(u64::from_le_bytes(market.data[144..152].try_into().unwrap()), 1, "Sale should be counted");
+    println!(" Installment purchase paid off");
+}
+
+#[test]
+fn test_installment_default() {
+    let mut t = TestMarket::new(200);
+    let (seller, buyer, collection, asset) = setup_installment_listing(&mut t);
+
+    let ixs = InstallmentIxs { t: &t, seller: seller.pubkey(), buyer: buyer.pubkey(), collection, asset };
+    let set_plan_ix = ixs.set_plan(400_000_000, 3, 7 * DAY, 5_000);
+    let start_ix = ixs.start();
+    let pay_ix = ixs.pay();
+    let reclaim_ix = ixs.reclaim();
+
+    t.send(&[set_plan_ix], &seller, &[]).expect("Seller should set the plan");
+    t.send(&[start_ix], &buyer, &[]).expect("Buyer should start the purchase");
+    assert!(t.send(std::slice::from_ref(&reclaim_ix), &seller, &[]).is_err(), "Cannot reclaim before a missed installment");
+
+    t.warp(8 * DAY);
+    assert!(t.send(&[pay_ix], &buyer, &[]).is_err(), "Overdue installment is rejected");
+
+    // Seller keeps half of the 0.4 SOL down payment
+    let buyer_before = t.svm.get_balance(&buyer.pubkey()).unwrap();
+    t.send(&[reclaim_ix], &seller, &[]).expect("Seller should reclaim after default");
+    assert!(t.svm.get_balance(&buyer.pubkey()).unwrap() > buyer_before + 200_000_000, "Buyer gets the refund + purchase rent");
+    assert_eq!(t.asset_owner(&asset), seller.pubkey(), "NFT should return to the seller");
+    println!(" Defaulted installment purchase reclaimed");
+}
+
+#[test]
+fn test_bundle_listing() {
+    let mut t = TestMarket::new(200);
+
+    let seller = t.wallet();
+    let buyer = t.wallet();
+    let (collection_a, authority_a) = t.create_collection(&seller);
+    let (collection_b, authority_b) = t.create_collection(&seller);
+    let asset_a = t.mint_asset(&seller, &collection_a.pubkey(), &authority_a);
+    let asset_b = t.mint_asset(&seller, &collection_b.pubkey(), &authority_b);
+
+    let (program_id, seller_key) = (t.program_id, seller.pubkey());
+    let bundle_pda = move |seed: u64| {
+        Pubkey::find_program_address(&[b"bundle", seller_key.as_ref(), &seed.to_le_bytes()], &program_id).0
+    };
+    let bundle_ix = |name: &str, mut accounts: Vec<AccountMeta>, args: &[u8], pairs: &[(&Keypair, &Keypair)]| {
+        accounts.extend([
+            AccountMeta::new_readonly(t.mpl_core_id, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new(t.market_pda, false),
+            AccountMeta::new_readonly(event_authority_pda(&t.program_id), false),
+            AccountMeta::new_readonly(t.program_id, false),
+        ]);
+        for (asset, collection) in pairs {
+            accounts.push(AccountMeta::new(asset.pubkey(), false));
+            accounts.push(AccountMeta::new_readonly(collection.pubkey(), false));
+        }
+        let mut data = anchor_discriminator("global", name).to_vec();
+        data.extend_from_slice(args);
+        Instruction { program_id: t.program_id, accounts, data }
+    };
+    let pairs = [(&asset_a, &collection_a), (&asset_b, &collection_b)];
+    let reversed = [(&asset_b, &collection_b), (&asset_a, &collection_a)];
+
+    let price: u64 = 2_000_000_000;
+    let add_ix = |seed: u64| {
+        let mut args = seed.to_le_bytes().to_vec();
+        args.extend_from_slice(&price.to_le_bytes());
+        bundle_ix("add_bundle_listing", vec![
+            AccountMeta::new(seller.pubkey(), true),
+            AccountMeta::new(bundle_pda(seed), false),
+        ], &args, &pairs)
+    };
+    let add_1_ix = add_ix(1);
+    let add_2_ix = add_ix(2);
+    let empty_ix = bundle_ix("add_bundle_listing", vec![
+        AccountMeta::new(seller.pubkey(), true),
+        AccountMeta::new(bundle_pda(3), false),
+    ], &[3u64.to_le_bytes(), price.to_le_bytes()].concat(), &[]);
+    let remove_ix = bundle_ix("remove_bundle_listing", vec![
+        AccountMeta::new(seller.pubkey(), true),
+        AccountMeta::new(bundle_pda(1), false),
+    ], &[], &pairs);
+    let match_accounts = vec![
+        AccountMeta::new(buyer.pubkey(), true),
+        AccountMeta::new(seller.pubkey(), false),
+        AccountMeta::new(bundle_pda(2), false),
+        AccountMeta::new(t.fee_recipient.pubkey(), false),
+    ];
+    // Buyer pays price + 2% taker fee = 2_040_000_000
+    let max_total = |limit: u64| limit.to_le_bytes();
+    let wrong_order_ix = bundle_ix("match_bundle_listing", match_accounts.clone(), &max_total(u64::MAX), &reversed);
+    let slippage_ix = bundle_ix("match_bundle_listing", match_accounts.clone(), &max_total(2_039_999_999), &pairs);
+    let match_ix = bundle_ix("match_bundle_listing", match_accounts, &max_total(2_040_000_000), &pairs);
+
+    assert!(t.send(&[empty_ix], &seller, &[]).is_err(), "Empty bundle should be rejected");
+
+    // List and delist: both assets come back
+    t.send(&[add_1_ix], &seller, &[]).expect("Bundle listing should succeed");
+    assert_eq!(t.asset_owner(&asset_a.pubkey()), bundle_pda(1));
+    assert_eq!(t.asset_owner(&asset_b.pubkey()), bundle_pda(1));
+    t.send(&[remove_ix], &seller, &[]).expect("Bundle removal should succeed");
+    assert_eq!(t.asset_owner(&asset_a.pubkey()), seller.pubkey());
+    assert_eq!(t.asset_owner(&asset_b.pubkey()), seller.pubkey());
+    assert!(t.svm.get_account(&bundle_pda(1)).is_none(), "Removed bundle should be closed");
+
+    // List again and sell atomically
+    t.send(&[add_2_ix], &seller, &[]).expect("Bundle listing should succeed");
+    assert!(t.send(&[wrong_order_ix], &buyer, &[]).is_err(), "Assets must be passed in listing order");
+    assert_anchor_error(t.send(&[slippage_ix], &buyer, &[]), "SlippageExceeded");
+
+    let seller_before = t.svm.get_balance(&seller.pubkey()).unwrap();
+    t.send(&[match_ix], &buyer, &[]).expect("Bundle purchase should succeed");
+
+    let bundle_rent = t.svm.minimum_balance_for_rent_exemption(8 + 32 + 8 + 4 + 5 * 32 + 8 + 8 + 1);
+    assert_eq!(t.svm.get_balance(&seller.pubkey()).unwrap(), seller_before + price + bundle_rent);
+    assert_eq!(t.svm.get_balance(&t.fee_recipient.pubkey()).unwrap(), 40_000_000);
+    assert_eq!(t.asset_owner(&asset_a.pubkey()), buyer.pubkey());
+    assert_eq!(t.asset_owner(&asset_b.pubkey()), buyer.pubkey());
+
+    let market = t.svm.get_account(&t.market_pda).unwrap();
+    assert_eq!(u64::from_le_bytes(market.data[128..136].try_into().unwrap()), 0, "No active listings left");
+    assert_eq!(u64::from_le_bytes(market.data[144..152].try_into().unwrap()), 1, "One sale");
+    println!(" Bundle of 2 assets sold atomically");
+}
+
+// SPL token account amount @64
+fn token_balance(t: &TestMarket, token_account: &Pubkey) -> u64 {
+    let account = t.svm.get_account(token_account).expect("Token account should exist");
+    u64::from_le_bytes(account.data[64..72].try_into().unwrap())
+}
+
+#[test]
+fn test_fraction_vault_buyout() {
+    let mut t = TestMarket::new(200);
+
+    let curator = t.wallet();
+    let holder = t.wallet();
+    let bidder_a = t.wallet();
+    let bidder_b = t.wallet();
+    let (collection, update_authority) = t.create_collection(&curator);
+    let asset = t.mint_asset(&curator, &collection.pubkey(), &update_authority);
+
+    let (vault_pda, _) = Pubkey::find_program_address(&[b"vault", asset.pubkey().as_ref()], &t.program_id);
+    let (fraction_mint, _) = Pubkey::find_program_address(&[b"fraction_mint", vault_pda.as_ref()], &t.program_id);
+    let curator_ata = associated_token_address(&curator.pubkey(), &fraction_mint);
+    let holder_ata = associated_token_address(&holder.pubkey(), &fraction_mint);
+
+    let vault_ix = |name: &str, mut accounts: Vec<AccountMeta>, args: &[u8]| {
+        accounts.extend([
+            AccountMeta::new(t.market_pda, false),
+            AccountMeta::new_readonly(event_authority_pda(&t.program_id), false),
+            AccountMeta::new_readonly(t.program_id, false),
+        ]);
+        let mut data = anchor_discriminator("global", name).to_vec();
+        data.extend_from_slice(args);
+        Instruction { program_id: t.program_id, accounts, data }
+    };
+
+    // 1000 fractions, 1 SOL reserve, 1 day auction
+    let mut args = 1_000u64.to_le_bytes().to_vec();
+    args.extend_from_slice(&1_000_000_000u64.to_le_bytes());
+    args.extend_from_slice(&DAY.to_le_bytes());
+    let fractionalize_ix = vault_ix("fractionalize", vec![
+        AccountMeta::new(curator.pubkey(), true),
+        AccountMeta::new(vault_pda, false),
+        AccountMeta::new(fraction_mint, false),
+        AccountMeta::new(curator_ata, false),
+        AccountMeta::new(asset.pubkey(), false),
+        AccountMeta::new_readonly(collection.pubkey(), false),
+        AccountMeta::new_readonly(t.mpl_core_id, false),
+        AccountMeta::new_readonly(token_program_id(), false),
+        AccountMeta::new_readonly(associated_token_program_id(), false),
+        AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+    ], &args);
+
+    // previous_bidder = program id when omitted
+    let bid_ix = |bidder: &Pubkey, previous: Option<&Pubkey>, bid: u64| {
+        vault_ix("place_buyout_bid", vec![
+            AccountMeta::new(*bidder, true),
+            match previous {
+                Some(previous) => AccountMeta::new(*previous, false),
+                None => AccountMeta::new_readonly(t.program_id, false),
+            },
+            AccountMeta::new(vault_pda, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ], &bid.to_le_bytes())
+    };
+    let below_reserve_ix = bid_ix(&bidder_a.pubkey(), None, 500_000_000);
+    let opening_bid_ix = bid_ix(&bidder_a.pubkey(), None, 1_000_000_000);
+    let small_outbid_ix = bid_ix(&bidder_b.pubkey(), Some(&bidder_a.pubkey()), 1_020_000_000);
+    let outbid_ix = bid_ix(&bidder_b.pubkey(), Some(&bidder_a.pubkey()), 1_100_000_000);
+
+    let settle_ix = vault_ix("settle_buyout", vec![
+        AccountMeta::new(holder.pubkey(), true),
+        AccountMeta::new_readonly(bidder_b.pubkey(), false),
+        AccountMeta::new(vault_pda, false),
+        AccountMeta::new(asset.pubkey(), false),
+        AccountMeta::new_readonly(collection.pubkey(), false),
+        AccountMeta::new(t.fee_recipient.pubkey(), false),
+        AccountMeta::new_readonly(t.mpl_core_id, false),
+        AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+    ], &[]);
+    let redeem_ix = |holder: &Pubkey, token_account: &Pubkey| {
+        vault_ix("redeem_fractions", vec![
+            AccountMeta::new(*holder, true),
+            AccountMeta::new(vault_pda, false),
+            AccountMeta::new(fraction_mint, false),
+            AccountMeta::new(*token_account, false),
+            AccountMeta::new_readonly(token_program_id(), false),
+        ], &[])
+    };
+    let holder_redeem_ix = redeem_ix(&holder.pubkey(), &holder_ata);
+    let curator_redeem_ix = redeem_ix(&curator.pubkey(), &curator_ata);
+
+    // Step 1: Fractionalize, then hand 250 fractions to another holder
+    t.send(&[fractionalize_ix], &curator, &[]).expect("Fractionalize should succeed");
+    assert_eq!(t.asset_owner(&asset.pubkey()), vault_pda, "NFT should be escrowed in the vault");
+    assert_eq!(token_balance(&t, &curator_ata), 1_000);
+
+    let create_holder_ata_ix = Instruction {
+        program_id: associated_token_program_id(),
+        accounts: vec![
+            AccountMeta::new(holder.pubkey(), true),
+            AccountMeta::new(holder_ata, false),
+            AccountMeta::new_readonly(holder.pubkey(), false),
+            AccountMeta::new_readonly(fraction_mint, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(token_program_id(), false),
+        ],
+        data: vec![],
+    };
+    let mut transfer_data = vec![3]; // SPL Token Transfer
+    transfer_data.extend_from_slice(&250u64.to_le_bytes());
+    let transfer_fractions_ix = Instruction {
+        program_id: token_program_id(),
+        accounts: vec![
+            AccountMeta::new(curator_ata, false),
+            AccountMeta::new(holder_ata, false),
+            AccountMeta::new_readonly(curator.pubkey(), true),
+        ],
+        data: transfer_data,
+    };
+    t.send(&[create_holder_ata_ix, transfer_fractions_ix], &holder, &[&curator])
+        .expect("Fractions should be transferable");
+
+    // Step 2: Buyout auction
+    assert!(t.send(&[below_reserve_ix], &bidder_a, &[]).is_err(), "Opening bid must meet the reserve");
+    t.send(&[opening_bid_ix], &bidder_a, &[]).expect("Opening bid should succeed");
+    assert!(t.send(&[small_outbid_ix], &bidder_b, &[]).is_err(), "Outbid must beat the minimum increment");
+    assert!(t.send(std::slice::from_ref(&holder_redeem_ix), &holder, &[]).is_err(), "Cannot redeem before settlement");
+
+    let bidder_a_before = t.svm.get_balance(&bidder_a.pubkey()).unwrap();
+    t.send(&[outbid_ix], &bidder_b, &[]).expect("Outbid should succeed");
+    assert_eq!(t.svm.get_balance(&bidder_a.pubkey()).unwrap(), bidder_a_before + 1_000_000_000, "Outbid bidder refunded");
+
+    assert!(t.send(std::slice::from_ref(&settle_ix), &holder, &[]).is_err(), "Cannot settle before the auction ends");
+    t.warp(DAY);
+    t.send(&[settle_ix], &holder, &[]).expect("Anyone can settle after the auction");
+    assert_eq!(t.asset_owner(&asset.pubkey()), bidder_b.pubkey(), "Winning bidder gets the NFT");
+    assert_eq!(t.svm.get_balance(&t.fee_recipient.pubkey()).unwrap(), 22_000_000);
+
+    // Step 3: Holders redeem 1.1 SOL - 2% fee pro rata (admin pays tx fees)
+    let admin = t.admin.insecure_clone();
+    let holder_before = t.svm.get_balance(&holder.pubkey()).unwrap();
+    t.send(&[holder_redeem_ix], &admin, &[&holder]).expect("Holder should redeem");
+    assert_eq!(t.svm.get_balance(&holder.pubkey()).unwrap(), holder_before + 269_500_000);
+    assert_eq!(token_balance(&t, &holder_ata), 0, "Fractions should be burned");
+
+    let curator_before = t.svm.get_balance(&curator.pubkey()).unwrap();
+    t.send(&[curator_redeem_ix], &admin, &[&curator]).expect("Curator should redeem");
+    assert_eq!(t.svm.get_balance(&curator.pubkey()).unwrap(), curator_before + 808_500_000);
+    println!(" Vault bought out and fractions redeemed");
+}
+
+#[test]
+fn test_trait_bid() {
+    let mut t = TestMarket::new(200);
+
+    let bidder = t.wallet();
+    let seller = t.wallet();
+    let admin = t.admin.insecure_clone();
+    let (collection, update_authority) = t.create_collection(&seller);
+    let gold = t.mint_asset_with_traits(&seller, &collection.pubkey(), &update_authority, &[("Background", "Gold"), ("Eyes", "Laser")]);
+    let silver = t.mint_asset_with_traits(&seller, &collection.pubkey(), &update_authority, &[("Background", "Silver"), ("Eyes", "Laser")]);
+
+    let bid_pda = |seed: u64| {
+        Pubkey::find_program_address(&[b"trait_bid", bidder.pubkey().as_ref(), &seed.to_le_bytes()], &t.program_id).0
+    };
+    let bid_ix = |name: &str, mut accounts: Vec<AccountMeta>, args: &[u8]| {
+        accounts.extend([
+            AccountMeta::new(t.market_pda, false),
+            AccountMeta::new_readonly(event_authority_pda(&t.program_id), false),
+            AccountMeta::new_readonly(t.program_id, false),
+        ]);
+        let mut data = anchor_discriminator("global", name).to_vec();
+        data.extend_from_slice(args);
+        Instruction { program_id: t.program_id, accounts, data }
+    };
+
+    // 1 SOL for any asset with Background=Gold
+    let amount: u64 = 1_000_000_000;
+    let create_ix = |seed: u64| {
+        let mut args = seed.to_le_bytes().to_vec();
+        args.extend_from_slice(collection.pubkey().as_ref());
+        args.extend_from_slice(&1u32.to_le_bytes()); // traits len
+        for field in ["Background", "Gold"] {
+            args.extend_from_slice(&(field.len() as u32).to_le_bytes());
+            args.extend_from_slice(field.as_bytes());
+        }
+        args.extend_from_slice(&amount.to_le_bytes());
+        bid_ix("create_trait_bid", vec![
+            AccountMeta::new(bidder.pubkey(), true),
+            AccountMeta::new(bid_pda(seed), false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ], &args)
+    };
+    let fill_ix = |asset: &Pubkey| {
+        bid_ix("fill_trait_bid", vec![
+            AccountMeta::new(seller.pubkey(), true),
+            AccountMeta::new(bidder.pubkey(), false),
+            AccountMeta::new(bid_pda(1), false),
+            AccountMeta::new(*asset, false),
+            AccountMeta::new_readonly(collection.pubkey(), false),
+            AccountMeta::new(t.fee_recipient.pubkey(), false),
+            AccountMeta::new_readonly(t.mpl_core_id, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ], &[])
+    };
+    let create_1_ix = create_ix(1);
+    let create_2_ix = create_ix(2);
+    let fill_silver_ix = fill_ix(&silver.pubkey());
+    let fill_gold_ix = fill_ix(&gold.pubkey());
+    // Any executable program standing in for mpl-core would make the transfer a no-op
+    let mut fake_program_fill_ix = fill_ix(&gold.pubkey());
+    fake_program_fill_ix.accounts[6] = AccountMeta::new_readonly(t.program_id, false);
+    let cancel_ix = bid_ix("cancel_trait_bid", vec![
+        AccountMeta::new(bidder.pubkey(), true),
+        AccountMeta::new(bid_pda(2), false),
+    ], &[]);
+    let bid_1 = bid_pda(1);
+
+    t.send(&[create_1_ix], &bidder, &[]).expect("Trait bid should be created");
+    assert!(t.send(&[fill_silver_ix], &admin, &[&seller]).is_err(), "Asset without the trait should be rejected");
+    assert_anchor_error(t.send(&[fake_program_fill_ix], &admin, &[&seller]), "ConstraintAddress");
+
+    // Seller receives the bid minus the 2% fee (admin pays tx fees)
+    let seller_before = t.svm.get_balance(&seller.pubkey()).unwrap();
+    t.send(&[fill_gold_ix], &admin, &[&seller]).expect("Matching asset should fill the bid");
+    assert_eq!(t.svm.get_balance(&seller.pubkey()).unwrap(), seller_before + 980_000_000);
+    assert_eq!(t.svm.get_balance(&t.fee_recipient.pubkey()).unwrap(), 20_000_000);
+    assert_eq!(t.asset_owner(&gold.pubkey()), bidder.pubkey(), "Bidder should receive the asset");
+    assert!(t.svm.get_account(&bid_1).is_none(), "Filled bid should be closed");
+
+    // Canceling returns the escrow
+    let bidder_before = t.svm.get_balance(&bidder.pubkey()).unwrap();
+    t.send(&[create_2_ix, cancel_ix], &bidder, &[]).expect("Create + cancel should succeed");
+    assert!(bidder_before - t.svm.get_balance(&bidder.pubkey()).unwrap() < 100_000);
+    println!(" Trait bid filled by an asset with Background=Gold");
+}
+
+// Write a legacy Pyth SOL/USD price account (expo -8) owned by the Pyth program
+fn set_pyth_price(t: &mut TestMarket, feed: &Pubkey, price: i64, conf: u64, timestamp: i64) {
+    let mut data = vec![0u8; 240];
+    data[0..4].copy_from_slice(&0xa1b2_c3d4u32.to_le_bytes()); // magic
+    data[8..12].copy_from_slice(&3u32.to_le_bytes());          // account type: price
+    data[20..24].copy_from_slice(&(-8i32).to_le_bytes());      // expo
+    data[96..104].copy_from_slice(&timestamp.to_le_bytes());   // publish time
+    data[208..216].copy_from_slice(&price.to_le_bytes());      // aggregate price
+    data[216..224].copy_from_slice(&conf.to_le_bytes());       // aggregate confidence
+    data[224..228].copy_from_slice(&1u32.to_le_bytes());       // status: trading
+    t.svm.set_account(
+        *feed,
+        solana_sdk::account::Account {
+            lamports: t.svm.minimum_balance_for_rent_exemption(data.len()),
+            data,
+            owner: Pubkey::new_from_array(nft_marketplace::oracle::PYTH_PROGRAM_ID.to_bytes()),
+            executable: false,
+            rent_epoch: 0,
+        },
+    ).unwrap();
+}
+
+#[test]
+fn test_usd_priced_listing() {
+    let mut t = TestMarket::new(200);
+
+    let seller = t.wallet();
+    let buyer = t.wallet();
+    let admin = t.admin.insecure_clone();
+    let (collection, update_authority) = t.create_collection(&seller);
+    let asset = t.mint_asset(&seller, &collection.pubkey(), &update_authority);
+    let (collection, asset) = (collection.pubkey(), asset.pubkey());
+    let feed = Pubkey::new_unique();
+    let rogue_feed = Pubkey::new_unique();
+
+    // 1 SOL floor, priced at $300
+    let list_ix = t.add_listing_ix(&seller.pubkey(), &collection, &asset, 1_000_000_000, ListingOptions::default());
+    let mut data = anchor_discriminator("global", "set_usd_price").to_vec();
+    data.push(1);
+    data.extend_from_slice(&300_000_000u64.to_le_bytes());
+    let set_usd_price_ix = Instruction {
+        program_id: t.program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(seller.pubkey(), true),
+            AccountMeta::new(t.listing_pda(&seller.pubkey(), &collection, &asset), false),
+            AccountMeta::new(t.market_pda, false),
+            AccountMeta::new_readonly(event_authority_pda(&t.program_id), false),
+            AccountMeta::new_readonly(t.program_id, false),
+        ],
+        data,
+    };
+    t.send(&[list_ix], &seller, &[]).expect("Listing should succeed");
+    assert!(
+        t.send(std::slice::from_ref(&set_usd_price_ix), &seller, &[]).is_err(),
+        "USD pricing needs a market SOL/USD feed"
+    );
+    let set_feed_ix = t.update_market_ix("set_price_feed", feed.as_ref());
+    t.send(&[set_feed_ix], &admin, &[]).expect("Admin should pin the SOL/USD feed");
+    t.svm.expire_blockhash();
+    t.send(&[set_usd_price_ix], &seller, &[]).expect("USD-priced listing should be created");
+
+    let without_feed_ix = t.match_listing_ix(&buyer.pubkey(), &seller.pubkey(), &collection, &asset, &[]);
+    let price_feed_index = without_feed_ix.accounts.len() - 6;
+    let with_feed = |feed: &Pubkey, max_total: u64| {
+        let mut ix = without_feed_ix.clone();
+        ix.accounts[price_feed_index] = AccountMeta::new_readonly(*feed, false);
+        let max_total_at = ix.data.len() - 8;
+        ix.data[max_total_at..].copy_from_slice(&max_total.to_le_bytes());
+        ix
+    };
+    let match_ix = with_feed(&feed, u64::MAX);
+
+    let now = t.svm.get_sysvar::<solana_sdk::clock::Clock>().unix_timestamp;
+    set_pyth_price(&mut t, &feed, 15_000_000_000, 5_000_000, now);
+    assert!(t.send(std::slice::from_ref(&without_feed_ix), &buyer, &[]).is_err(), "Price feed is required");
+
+    // A Pyth-owned feed other than the market's pinned one is rejected
+    set_pyth_price(&mut t, &rogue_feed, 15_000_000_000, 5_000_000, now);
+    assert!(t.send(&[with_feed(&rogue_feed, u64::MAX)], &buyer, &[]).is_err(), "Unpinned feed should be rejected");
+
+    set_pyth_price(&mut t, &feed, 15_000_000_000, 5_000_000, now - 120);
+    assert!(t.send(std::slice::from_ref(&match_ix), &buyer, &[]).is_err(), "Stale price should be rejected");
+
+    set_pyth_price(&mut t, &feed, 15_000_000_000, 450_000_000, now);
+    t.svm.expire_blockhash();
+    assert!(t.send(std::slice::from_ref(&match_ix), &buyer, &[]).is_err(), "Wide confidence should be rejected");
+
+    // $400/SOL -> $300 is 0.75 SOL, below the 1 SOL floor
+    set_pyth_price(&mut t, &feed, 40_000_000_000, 5_000_000, now);
+    t.svm.expire_blockhash();
+    assert!(t.send(std::slice::from_ref(&match_ix), &buyer, &[]).is_err(), "Price below floor should be rejected");
+
+    // $150/SOL -> $300 is 2 SOL, held to the market price bounds
+    set_pyth_price(&mut t, &feed, 15_000_000_000, 5_000_000, now);
+    let mut bounds = 1u64.to_le_bytes().to_vec();
+    bounds.extend_from_slice(&1_500_000_000u64.to_le_bytes());
+    let tight_bounds_ix = t.update_market_ix("set_price_bounds", &bounds);
+    t.send(&[tight_bounds_ix], &admin, &[]).expect("Admin should set price bounds");
+    t.svm.expire_blockhash();
+    assert_anchor_error(t.send(std::slice::from_ref(&match_ix), &buyer, &[]), "PriceTooHigh");
+    let mut bounds = 1u64.to_le_bytes().to_vec();
+    bounds.extend_from_slice(&u64::MAX.to_le_bytes());
+    let open_bounds_ix = t.update_market_ix("set_price_bounds", &bounds);
+    t.send(&[open_bounds_ix], &admin, &[]).expect("Admin should reset price bounds");
+
+    // 2 SOL + 2% taker fee must fit under the buyer's cap
+    assert_anchor_error(t.send(&[with_feed(&feed, 2_039_999_999)], &buyer, &[]), "SlippageExceeded");
+
+    t.svm.expire_blockhash();
+    let seller_before = t.svm.get_balance(&seller.pubkey()).unwrap();
+    t.send(&[with_feed(&feed, 2_040_000_000)], &buyer, &[]).expect("Purchase at the oracle price should succeed");
+    assert_eq!(t.svm.get_balance(&seller.pubkey()).unwrap(), seller_before + 2_000_000_000);
+    assert_eq!(t.svm.get_balance(&t.fee_recipient.pubkey()).unwrap(), 40_000_000);
+    assert_eq!(t.asset_owner(&asset), buyer.pubkey());
+    println!(" $300 listing settled for 2 SOL at $150/SOL");
+}
+
+#[test]
+fn test_relist_on_purchase() {
+    let mut t = TestMarket::new(200);
+
+    let seller = t.wallet();
+    let buyer = t.wallet();
+    let next_buyer = t.wallet();
+    let (collection, update_authority) = t.create_collection(&seller);
+    let asset = t.mint_asset(&seller, &collection.pubkey(), &update_authority);
+    let (collection, asset) = (collection.pubkey(), asset.pubkey());
+
+    let list_ix = t.add_listing_ix(&seller.pubkey(), &collection, &asset, 1_000_000_000, ListingOptions::default());
+    t.send(&[list_ix], &seller, &[]).expect("Listing should succeed");
+
+    // relist_price: Some(3 SOL), relist account set to the buyer's listing PDA
+    let relist_pda = t.listing_pda(&buyer.pubkey(), &collection, &asset);
+    let mut relist_ix = t.match_listing_ix(&buyer.pubkey(), &seller.pubkey(), &collection, &asset, &[]);
+    relist_ix.data.truncate(relist_ix.data.len() - 9);
+    relist_ix.data.push(1);
+    relist_ix.data.extend_from_slice(&3_000_000_000u64.to_le_bytes());
+    relist_ix.data.extend_from_slice(&u64::MAX.to_le_bytes());
+    let relist_index = relist_ix.accounts.len() - 4;
+
+    // Price without the listing account (and vice versa) is rejected
+    assert!(t.send(std::slice::from_ref(&relist_ix), &buyer, &[]).is_err(), "Relist account is required");
+    let mut missing_price_ix = t.match_listing_ix(&buyer.pubkey(), &seller.pubkey(), &collection, &asset, &[]);
+    missing_price_ix.accounts[relist_index] = AccountMeta::new(relist_pda, false);
+    assert!(t.send(&[missing_price_ix], &buyer, &[]).is_err(), "Relist price is required");
+
+    relist_ix.accounts[relist_index] = AccountMeta::new(relist_pda, false);
+    t.svm.expire_blockhash();
+    t.send(std::slice::from_ref(&relist_ix), &buyer, &[]).expect("Purchase with relist should succeed");
+    assert_eq!(t.asset_owner(&asset), relist_pda, "NFT should go straight into the new escrow");
+
+    // Listing: discriminator(8) + seller(32) + collection(32) + asset(32) + price(8) + is_active(1)
+    let data = t.svm.get_account(&relist_pda).unwrap().data;
+    assert_eq!(&data[8..40], buyer.pubkey().as_ref());
+    assert_eq!(u64::from_le_bytes(data[104..112].try_into().unwrap()), 3_000_000_000);
+    assert_eq!(data[112], 1);
+
+    // Market: active_listings @128, total_sales @144
+    let market = t.svm.get_account(&t.market_pda).unwrap().data;
+    assert_eq!(u64::from_le_bytes(market[128..136].try_into().unwrap()), 1);
+    assert_eq!(u64::from_le_bytes(market[144..152].try_into().unwrap()), 1);
+
+    // The relisted NFT sells like any other listing
+    let resale_ix = t.match_listing_ix(&next_buyer.pubkey(), &buyer.pubkey(), &collection, &asset, &[]);
+    t.send(&[resale_ix], &next_buyer, &[]).expect("Relisted NFT should be purchasable");
+    assert_eq!(t.asset_owner(&asset), next_buyer.pubkey());
+    println!(" Purchased NFT relisted at 3 SOL and resold");
+}
+
+#[test]
+fn test_amm_pool() {
+    let mut t = TestMarket::new(200);
+
+    let owner = t.wallet();
+    let trader = t.wallet();
+    let admin = t.admin.insecure_clone();
+    let (collection, update_authority) = t.create_collection(&owner);
+    let stocked = t.mint_asset(&owner, &collection.pubkey(), &update_authority);
+    let traded_in = t.mint_asset(&trader, &collection.pubkey(), &update_authority);
+    let (other_collection, other_authority) = t.create_collection(&trader);
+    let outsider = t.mint_asset(&trader, &other_collection.pubkey(), &other_authority);
+    let (collection, stocked, traded_in, outsider) =
+        (collection.pubkey(), stocked.pubkey(), traded_in.pubkey(), outsider.pubkey());
+
+    let pool = Pubkey::find_program_address(&[b"pool", owner.pubkey().as_ref(), &7u64.to_le_bytes()], &t.program_id).0;
+    let (program_id, market_pda, fee_recipient, mpl_core_id) =
+        (t.program_id, t.market_pda, t.fee_recipient.pubkey(), t.mpl_core_id);
+    let pool_ix = move |name: &str, mut accounts: Vec<AccountMeta>, args: &[u8]| {
+        accounts.extend([
+            AccountMeta::new(market_pda, false),
+            AccountMeta::new_readonly(event_authority_pda(&program_id), false),
+            AccountMeta::new_readonly(program_id, false),
+        ]);
+        let mut data = anchor_discriminator("global", name).to_vec();
+        data.extend_from_slice(args);
+        Instruction { program_id, accounts, data }
+    };
+    let nft_ix = |name: &str, signer: &Pubkey, asset: &Pubkey, asset_collection: &Pubkey, trade: bool, args: &[u8]| {
+        let mut accounts = vec![
+            AccountMeta::new(*signer, true),
+            AccountMeta::new(pool, false),
+            AccountMeta::new(*asset, false),
+            AccountMeta::new_readonly(*asset_collection, false),
+        ];
+        if trade {
+            accounts.push(AccountMeta::new(fee_recipient, false));
+        }
+        accounts.extend([
+            AccountMeta::new_readonly(mpl_core_id, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ]);
+        pool_ix(name, accounts, args)
+    };
+
+    // Linear curve: 1 SOL spot, +/- 0.1 SOL per trade, 10% spread on the pool's bid
+    let mut args = 7u64.to_le_bytes().to_vec();
+    args.push(0); // BondingCurve::Linear
+    args.extend_from_slice(&1_000_000_000u64.to_le_bytes());
+    args.extend_from_slice(&100_000_000u64.to_le_bytes());
+    args.extend_from_slice(&1_000u16.to_le_bytes());
+    let create_ix = pool_ix("create_pool", vec![
+        AccountMeta::new(owner.pubkey(), true),
+        AccountMeta::new(pool, false),
+        AccountMeta::new_readonly(collection, false),
+        AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+    ], &args);
+    let deposit_sol_ix = pool_ix("deposit_pool_sol", vec![
+        AccountMeta::new(owner.pubkey(), true),
+        AccountMeta::new(pool, false),
+        AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+    ], &2_000_000_000u64.to_le_bytes());
+    let deposit_nft_ix = nft_ix("deposit_pool_nft", &owner.pubkey(), &stocked, &collection, false, &[]);
+    t.send(&[create_ix, deposit_sol_ix, deposit_nft_ix], &owner, &[]).expect("Pool should be created and stocked");
+
+    // Pool: discriminator(8) + owner(32) + collection(32) + seed(8) + curve(1) + spot_price(8)
+    // + delta(8) + spread_bps(2) + sol_balance(8) + nft_count(4)
+    let pool_state = |t: &TestMarket| {
+        let data = t.svm.get_account(&pool).unwrap().data;
+        (
+            u64::from_le_bytes(data[81..89].try_into().unwrap()),
+            u64::from_le_bytes(data[99..107].try_into().unwrap()),
+            u32::from_le_bytes(data[107..111].try_into().unwrap()),
+        )
+    };
+    assert_eq!(pool_state(&t), (1_000_000_000, 2_000_000_000, 1));
+
+    // Buying at the spot price: the pool keeps all of it, the buyer pays the 2% fee on top
+    let buy_low_ix = nft_ix("buy_from_pool", &trader.pubkey(), &stocked, &collection, true, &900_000_000u64.to_le_bytes());
+    assert!(t.send(&[buy_low_ix], &trader, &[]).is_err(), "Max price below spot should be rejected");
+    let buy_ix = nft_ix("buy_from_pool", &trader.pubkey(), &stocked, &collection, true, &1_000_000_000u64.to_le_bytes());
+    t.send(&[buy_ix], &trader, &[]).expect("Buying from the pool should succeed");
+    assert_eq!(t.asset_owner(&stocked), trader.pubkey());
+    assert_eq!(pool_state(&t), (1_100_000_000, 3_000_000_000, 0));
+    assert_eq!(t.svm.get_balance(&t.fee_recipient.pubkey()).unwrap(), 20_000_000);
+
+    // Selling one step down the curve (1 SOL) less the 10% spread: 0.9 SOL, minus 2% fee
+    let sell_outsider_ix = nft_ix("sell_to_pool", &trader.pubkey(), &outsider, &other_collection.pubkey(), true, &0u64.to_le_bytes());
+    assert!(t.send(&[sell_outsider_ix], &admin, &[&trader]).is_err(), "Other collections should be rejected");
+    let sell_ix = nft_ix("sell_to_pool", &trader.pubkey(), &traded_in, &collection, true, &900_000_000u64.to_le_bytes());
+    let trader_before = t.svm.get_balance(&trader.pubkey()).unwrap();
+    t.send(&[sell_ix], &admin, &[&trader]).expect("Selling into the pool should succeed");
+    assert_eq!(t.svm.get_balance(&trader.pubkey()).unwrap(), trader_before + 882_000_000);
+    assert_eq!(t.asset_owner(&traded_in), pool);
+    assert_eq!(pool_state(&t), (1_000_000_000, 2_100_000_000, 1));
+
+    // Owner can only close an empty pool
+    let close_ix = pool_ix("close_pool", vec![
+        AccountMeta::new(owner.pubkey(), true),
+        AccountMeta::new(pool, false),
+    ], &[]);
+    assert!(t.send(std::slice::from_ref(&close_ix), &owner, &[]).is_err(), "Pool holding NFTs should not close");
+
+    let withdraw_nft_ix = nft_ix("withdraw_pool_nft", &owner.pubkey(), &traded_in, &collection, false, &[]);
+    let withdraw_sol_ix = pool_ix("withdraw_pool_sol", vec![
+        AccountMeta::new(owner.pubkey(), true),
+        AccountMeta::new(pool, false),
+    ], &1_000_000_000u64.to_le_bytes());
+    t.svm.expire_blockhash();
+    let owner_before = t.svm.get_balance(&owner.pubkey()).unwrap();
+    let pool_lamports = t.svm.get_balance(&pool).unwrap();
+    t.send(&[withdraw_nft_ix, withdraw_sol_ix, close_ix], &admin, &[&owner]).expect("Owner should unwind the pool");
+    assert_eq!(t.asset_owner(&traded_in), owner.pubkey());
+    assert!(t.svm.get_account(&pool).is_none(), "Closed pool should be gone");
+    assert_eq!(t.svm.get_balance(&owner.pubkey()).unwrap(), owner_before + pool_lamports);
+
+    // Market counts pool trades as sales but never as listings
+    let market = t.svm.get_account(&t.market_pda).unwrap().data;
+    assert_eq!(u64::from_le_bytes(market[128..136].try_into().unwrap()), 0);
+    assert_eq!(u64::from_le_bytes(market[144..152].try_into().unwrap()), 2);
+    println!(" Pool bought at 1 SOL, sold at 0.9 SOL, unwound by the owner");
+}
+
+#[test]
+fn test_sponsored_purchase() {
+    let mut t = TestMarket::new(200);
+
+    let seller = t.wallet();
+    let buyer = t.wallet();
+    let relayer = t.wallet();
+    let admin = t.admin.insecure_clone();
+    let (collection, update_authority) = t.create_collection(&seller);
+    let (collection, first, second) = {
+        let first = t.mint_asset(&seller, &collection.pubkey(), &update_authority);
+        let second = t.mint_asset(&seller, &collection.pubkey(), &update_authority);
+        (collection.pubkey(), first.pubkey(), second.pubkey())
+    };
+    for asset in [&first, &second] {
+        let list_ix = t.add_listing_ix(&seller.pubkey(), &collection, asset, 1_000_000_000, ListingOptions::default());
+        t.send(&[list_ix], &seller, &[]).expect("Listing should succeed");
+    }
+
+    let approval = Pubkey::find_program_address(
+        &[b"relayer", t.market_pda.as_ref(), relayer.pubkey().as_ref()],
+        &t.program_id,
+    ).0;
+    let relayer_ix = |t: &TestMarket, name: &str, accounts: Vec<AccountMeta>| {
+        let mut accounts = [
+            vec![
+                AccountMeta::new(admin.pubkey(), true),
+                AccountMeta::new(t.market_pda, false),
+            ],
+            accounts,
+        ].concat();
+        accounts.extend([
+            AccountMeta::new_readonly(event_authority_pda(&t.program_id), false),
+            AccountMeta::new_readonly(t.program_id, false),
+        ]);
+        Instruction {
+            program_id: t.program_id,
+            accounts,
+            data: anchor_discriminator("global", name).to_vec(),
+        }
+    };
+    // Relayer signs as payer, buyer only authorizes the payment
+    let sponsored_ix = |t: &TestMarket, asset: &Pubkey, approval: Option<Pubkey>| {
+        let mut ix = t.match_listing_ix(&buyer.pubkey(), &seller.pubkey(), &collection, asset, &[]);
+        ix.accounts[1] = AccountMeta::new(relayer.pubkey(), true);
+        let approval_index = ix.accounts.len() - 3;
+        ix.accounts[approval_index] = AccountMeta::new_readonly(approval.unwrap_or(t.program_id), false);
+        ix
+    };
+
+    let unapproved_ix = sponsored_ix(&t, &first, None);
+    assert!(t.send(&[unapproved_ix], &relayer, &[&buyer]).is_err(), "Unapproved relayer should be rejected");
+
+    let add_ix = relayer_ix(&t, "add_relayer", vec![
+        AccountMeta::new_readonly(relayer.pubkey(), false),
+        AccountMeta::new(approval, false),
+        AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+    ]);
+    t.send(&[add_ix], &admin, &[]).expect("Admin should approve the relayer");
+
+    // Buyer pays exactly the price and taker fee, the relayer covers tx fee and stats rent
+    let buyer_before = t.svm.get_balance(&buyer.pubkey()).unwrap();
+    let relayer_before = t.svm.get_balance(&relayer.pubkey()).unwrap();
+    let approved_ix = sponsored_ix(&t, &first, Some(approval));
+    t.svm.expire_blockhash();
+    t.send(&[approved_ix], &relayer, &[&buyer]).expect("Sponsored purchase should succeed");
+    assert_eq!(t.asset_owner(&first), buyer.pubkey());
+    assert_eq!(t.svm.get_balance(&buyer.pubkey()).unwrap(), buyer_before - 1_020_000_000);
+    assert!(t.svm.get_balance(&relayer.pubkey()).unwrap() < relayer_before);
+
+    let remove_ix = relayer_ix(&t, "remove_relayer", vec![AccountMeta::new(approval, false)]);
+    t.send(&[remove_ix], &admin, &[]).expect("Admin should revoke the relayer");
+    assert!(t.svm.get_account(&approval).is_none());
+    let revoked_ix = sponsored_ix(&t, &second, Some(approval));
+    assert!(t.send(&[revoked_ix], &relayer, &[&buyer]).is_err(), "Revoked relayer should be rejected");
+    println!(" Relayer sponsored a purchase, then lost approval");
+}
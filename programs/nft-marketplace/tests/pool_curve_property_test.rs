@@ -0,0 +1,32 @@
+use nft_marketplace::{BondingCurve, Pool};
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn pool_round_trip_never_loses_the_pool_money(
+        spot_price in 1u64..=1_000_000_000_000,
+        delta in 0u64..=10_000,
+        spread_bps in 0u16..10_000,
+        exponential in any::<bool>(),
+    ) {
+        let mut pool = Pool {
+            owner: Default::default(),
+            collection: Default::default(),
+            seed: 0,
+            curve: if exponential { BondingCurve::Exponential } else { BondingCurve::Linear },
+            spot_price,
+            delta,
+            spread_bps,
+            sol_balance: 0,
+            nft_count: 0,
+            created_at: 0,
+            bump: 0,
+        };
+        // Buy at spot, then immediately sell back into the pool
+        pool.spot_price = pool.price_after_buy().unwrap();
+        if let Ok(bid) = pool.bid_price() {
+            prop_assert!(bid <= spot_price);
+            prop_assert!(pool.price_after_sell().unwrap() <= spot_price);
+        }
+    }
+}